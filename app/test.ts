@@ -123,7 +123,6 @@ import { getAssociatedTokenAddressSync } from "@solana/spl-token";
         amount: new anchor.BN(40000),
         subMarketId: new anchor.BN(1),
         choiceId: new anchor.BN(1),
-        requestedPricePerShare: new anchor.BN(1),
       },
     ])
     .signers([walletManager])
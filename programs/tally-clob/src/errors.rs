@@ -44,12 +44,102 @@ pub enum TallyClobErrors {
     MarketAlreadyResolved,
     #[msg("Estimated shares is to far off from acutal shares, cancelling order.")]
     SharesEstimationOff,
-    #[msg("Requested price is to far off from acutal price, cancelling order.")]
-    PriceEstimationOff,
+    #[msg("Order filled outside of its slippage bounds, cancelling order.")]
+    SlippageExceeded,
     #[msg("Can only withdraw usdc.")]
     NotUSDC,
     #[msg("Not a valid order")]
     NotAValidOrder,
     #[msg("Shares not equal")]
-    SharesNotEqual
+    SharesNotEqual,
+    #[msg("This role has expired.")]
+    RoleExpired,
+    #[msg("Program is paused, please try again later.")]
+    ProgramPaused,
+    #[msg("Account is not part of this audit or is out of order.")]
+    InvalidAuditAccount,
+    #[msg("This audit has already been finalized.")]
+    AuditFinalized,
+    #[msg("Sub market needs between 2 and the maximum number of choices, with sorted unique ids.")]
+    InvalidChoiceCount,
+    #[msg("Liquidity parameter is invalid or the initial pot doesn't cover the maximum loss.")]
+    InvalidLiquidityParameter,
+    #[msg("Order price and shares must be above zero.")]
+    InvalidOrderPrice,
+    #[msg("No room left on this side of the order book.")]
+    OrderBookFull,
+    #[msg("Event queue is full, consume some events first.")]
+    EventQueueFull,
+    #[msg("Order not found.")]
+    OrderNotFound,
+    #[msg("Order would trade against your own resting order.")]
+    SelfTrade,
+    #[msg("Order book or event queue doesn't belong to this market.")]
+    InvalidOrderBookAccount,
+    #[msg("Not enough complete set collateral to redeem against.")]
+    NotEnoughSetCollateral,
+    #[msg("Share mint or share token account is missing or doesn't match.")]
+    InvalidShareAccount,
+    #[msg("Sub market hasn't been voided.")]
+    MarketNotVoided,
+    #[msg("Sub market was voided, claim a refund instead.")]
+    MarketVoided,
+    #[msg("Nothing left to refund in this sub market.")]
+    NothingToRefund,
+    #[msg("Sub market resolution can't move on from its current status.")]
+    InvalidResolutionStatus,
+    #[msg("Dispute period is over.")]
+    DisputePeriodOver,
+    #[msg("Resolution is still in its dispute period, check back later.")]
+    InDisputePeriod,
+    #[msg("Dispute period can't be negative.")]
+    InvalidDisputePeriod,
+    #[msg("Oracle config needs two different choices and a max delay that isn't negative.")]
+    InvalidOracleConfig,
+    #[msg("Sub market doesn't resolve from an oracle.")]
+    NoOracleConfig,
+    #[msg("Price feed isn't the configured feed or doesn't match its layout.")]
+    InvalidOracleFeed,
+    #[msg("Oracle has no usable price for the resolution time yet.")]
+    OraclePriceUnavailable,
+    #[msg("Oracle price was published too long after the resolution time.")]
+    OraclePriceStale,
+    #[msg("Trading hasn't ended yet.")]
+    TradingNotOver,
+    #[msg("Proposal has already been disputed or settled.")]
+    InvalidProposalStatus,
+    #[msg("Proposer or disputer account doesn't match the proposal.")]
+    InvalidProposalAccount,
+    #[msg("Scalar sub markets resolve to a value, not a choice.")]
+    ScalarSubMarket,
+    #[msg("Sub market isn't scalar.")]
+    NotScalarSubMarket,
+    #[msg("Scalar sub markets need exactly two choices and a lower bound below the upper bound.")]
+    InvalidScalarRange,
+    #[msg("Payouts need one numerator per choice and have to add up to a whole share.")]
+    InvalidPayouts,
+    #[msg("Fee rates can't be over 100% and the minimum fee can't be above the maximum.")]
+    InvalidFeeSchedule,
+    #[msg("Creator and referrer shares can't add up to more than the whole fee.")]
+    InvalidFeeSplit,
+    #[msg("Fee recipient accounts are missing or don't match the market creator and referrer.")]
+    InvalidFeeRecipient,
+    #[msg("No fee rewards to claim.")]
+    NoFeeRewards,
+    #[msg("No referral rewards to claim.")]
+    NoReferralRewards,
+    #[msg("Referral tiers have to go up in volume, with at most four of them.")]
+    InvalidReferralTiers,
+    #[msg("Fee tiers have to go up in volume, with at most four of them and no discount over the whole fee.")]
+    InvalidFeeTiers,
+    #[msg("Not enough fees accrued to sweep a whole token unit.")]
+    NoFeesToSweep,
+    #[msg("The collateral mint can't change once the vault is initialized.")]
+    CollateralMintLocked,
+    #[msg("This wallet is self-custodial, only its owner can sign for it.")]
+    NotCustodial,
+    #[msg("Shares of this sub market are held as tokens.")]
+    SharesTokenized,
+    #[msg("Mock feeds are only available in test builds.")]
+    MockOracleDisabled
 }
\ No newline at end of file
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct SolvencyAudited {
+    pub audit: Pubkey,
+    pub auditor: Pubkey,
+    pub users_audited: u64,
+    pub markets_audited: u64,
+    pub liabilities: u128,
+    pub vault_balance: u128,
+    pub solvent: bool,
+    pub timestamp: i64,
+}
+#[event]
+pub struct SharesTransferred {
+    pub market: Pubkey,
+    pub sub_market_id: u64,
+    pub choice_id: u64,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub shares: u128,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+#[event]
+pub struct ResolutionProposed {
+    pub market: Pubkey,
+    pub sub_market_id: u64,
+    pub choice_id: u64,
+    pub dispute_ends_at: i64,
+    pub timestamp: i64,
+}
+#[event]
+pub struct ResolutionDisputed {
+    pub market: Pubkey,
+    pub sub_market_id: u64,
+    pub disputer: Pubkey,
+    pub timestamp: i64,
+}
+#[event]
+pub struct ResolutionFinalized {
+    pub market: Pubkey,
+    pub sub_market_id: u64,
+    pub payout_numerators: Vec<u64>,
+    pub timestamp: i64,
+}
+#[event]
+pub struct PayoutsProposed {
+    pub market: Pubkey,
+    pub sub_market_id: u64,
+    pub payout_numerators: Vec<u64>,
+    pub dispute_ends_at: i64,
+    pub timestamp: i64,
+}
+#[event]
+pub struct ScalarResolutionProposed {
+    pub market: Pubkey,
+    pub sub_market_id: u64,
+    pub value: i64,
+    pub payout_numerators: Vec<u64>,
+    pub dispute_ends_at: i64,
+    pub timestamp: i64,
+}
+#[event]
+pub struct FeesSwept {
+    pub amount: u128,
+    pub remainder: u128,
+    pub timestamp: i64,
+}
@@ -1,11 +1,27 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
 
-use crate::{errors::TallyClobErrors, User};
+use crate::{errors::TallyClobErrors, utils::{to_balance_amount, to_token_amount, transfer_to_vault}, AuthorizedUser, ProgramConfig, User};
 
+/// Credits balance that can be traded but not withdrawn. The wallet manager
+/// pays for it into the vault, so the credit is backed like any deposit.
 pub fn add_to_unreedeemable(ctx: Context<AddToUnreedeemable>, amount: u128) -> Result<()> {
-    require!(amount > 0, TallyClobErrors::AmountToAddTooLow);
+    let mint = &ctx.accounts.mint;
+    require!(mint.key() == ctx.accounts.config.usdc_mint, TallyClobErrors::NotUSDC);
 
-    ctx.accounts.user.add_to_unreedeemable(amount)?;
+    let token_amount = to_token_amount(amount, mint.decimals)?;
+    require!(token_amount > 0, TallyClobErrors::AmountToAddTooLow);
+
+    transfer_to_vault(
+        &ctx.accounts.token_program,
+        &ctx.accounts.from_usdc_account,
+        &ctx.accounts.vault,
+        &ctx.accounts.signer,
+        token_amount
+    )?;
+
+    // only credit what actually landed in the vault
+    ctx.accounts.user.add_to_unreedeemable(to_balance_amount(token_amount, mint.decimals)?)?;
 
     msg!("Your new balance is: {}", ctx.accounts.user.balance);
 
@@ -16,7 +32,27 @@ pub fn add_to_unreedeemable(ctx: Context<AddToUnreedeemable>, amount: u128) -> R
 pub struct AddToUnreedeemable<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
+    pub authorized_user: Option<Account<'info, AuthorizedUser>>,
     #[account(mut)]
     pub user: Account<'info, User>,
+    #[account(mut)]
+    pub from_usdc_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), mint.key().as_ref()],
+        bump = config.vault_bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
\ No newline at end of file
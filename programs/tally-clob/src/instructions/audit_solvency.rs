@@ -0,0 +1,101 @@
+use anchor_lang::{prelude::*, Discriminator};
+use anchor_spl::token::{Mint, TokenAccount};
+
+use crate::{errors::TallyClobErrors, events::SolvencyAudited, utils::{current_timestamp, to_balance_amount}, EventQueue, FeeLedger, Market, OrderBook, ProgramConfig, Proposal, SolvencyAudit, User};
+
+/// Adds every `User`, `Market`, `OrderBook`, `EventQueue` and `Proposal`
+/// passed in the remaining accounts to the audit's running totals. Once every account counted at the start has been
+/// seen the vault balance and unswept fees are recorded and the result is
+/// emitted.
+pub fn audit_solvency(ctx: Context<AuditSolvency>) -> Result<()> {
+    let mint = &ctx.accounts.mint;
+    let config = &ctx.accounts.config;
+    require!(mint.key() == config.usdc_mint, TallyClobErrors::NotUSDC);
+
+    let audit = &mut ctx.accounts.audit;
+    require!(!audit.finalized, TallyClobErrors::AuditFinalized);
+
+    for account in ctx.remaining_accounts.iter() {
+        require!(account.owner == ctx.program_id, TallyClobErrors::InvalidAuditAccount);
+
+        let data = account.try_borrow_data()?;
+        require!(data.len() >= 8, TallyClobErrors::InvalidAuditAccount);
+
+        if data[..8] == User::DISCRIMINATOR {
+            let user = User::try_deserialize(&mut &data[..])?;
+            audit.add_user(account.key(), &user)?;
+        } else if data[..8] == Market::DISCRIMINATOR {
+            let market = Market::try_deserialize(&mut &data[..])?;
+            audit.add_market(account.key(), &market)?;
+        } else if data[..8] == OrderBook::DISCRIMINATOR {
+            let order_book = OrderBook::try_deserialize(&mut &data[..])?;
+            audit.add_order_book(account.key(), &order_book)?;
+        } else if data[..8] == EventQueue::DISCRIMINATOR {
+            let event_queue = EventQueue::try_deserialize(&mut &data[..])?;
+            audit.add_event_queue(account.key(), &event_queue)?;
+        } else if data[..8] == Proposal::DISCRIMINATOR {
+            let proposal = Proposal::try_deserialize(&mut &data[..])?;
+            audit.add_proposal(account.key(), &proposal)?;
+        } else {
+            return err!(TallyClobErrors::InvalidAuditAccount)
+        }
+    }
+
+    msg!("users: {}/{}, markets: {}/{}", audit.users_audited, audit.user_count, audit.markets_audited, audit.market_count);
+    msg!(
+        "order books: {}/{}, event queues: {}/{}, proposals: {}/{}",
+        audit.order_books_audited, audit.order_book_count,
+        audit.event_queues_audited, audit.order_book_count,
+        audit.proposals_audited, audit.proposal_count
+    );
+
+    if !audit.is_complete() {
+        return Ok(())
+    }
+
+    audit.fee_liabilities = ctx.accounts.fee_ledger.accrued;
+    audit.vault_balance = to_balance_amount(ctx.accounts.vault.amount, mint.decimals)?;
+    audit.solvent = audit.vault_balance >= audit.total_liabilities();
+    audit.finalized = true;
+
+    emit!(SolvencyAudited {
+        audit: audit.key(),
+        auditor: audit.auditor,
+        users_audited: audit.users_audited,
+        markets_audited: audit.markets_audited,
+        liabilities: audit.total_liabilities(),
+        vault_balance: audit.vault_balance,
+        solvent: audit.solvent,
+        timestamp: current_timestamp(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AuditSolvency<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [b"solvency_audits".as_ref(), audit.auditor.as_ref(), audit.audit_id.to_le_bytes().as_ref()],
+        bump = audit.bump
+    )]
+    pub audit: Account<'info, SolvencyAudit>,
+    #[account(
+        seeds = [b"vault".as_ref(), mint.key().as_ref()],
+        bump = config.vault_bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    pub mint: Account<'info, Mint>,
+    #[account(
+        seeds = [b"fee_ledger".as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+}
\ No newline at end of file
@@ -1,23 +1,34 @@
 use anchor_lang::prelude::*;
 
-use crate::AuthorizedUser;
+use crate::{AuthorizedUser, ProgramConfig};
 
 
 pub fn authorize_user(
     ctx: Context<AuthorizeUser>,
-    authorized: bool
+    roles: u8,
+    market: Option<Pubkey>,
+    expires_at: Option<i64>
 ) -> Result<()> {
+    let authorized_user = &mut ctx.accounts.authorized_user;
 
-    ctx.accounts.authorized_user.authorized = authorized;
+    authorized_user.bump = ctx.bumps.authorized_user;
+    authorized_user.roles = roles;
+    authorized_user.market = market;
+    authorized_user.expires_at = expires_at;
 
     Ok(())
 }
 
 #[derive(Accounts)]
-#[instruction(authority: bool,user_key: Pubkey)]
+#[instruction(roles: u8, market: Option<Pubkey>, expires_at: Option<i64>, user_key: Pubkey)]
 pub struct AuthorizeUser<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
     #[account(
         init_if_needed,
         payer = signer,
@@ -27,4 +38,4 @@ pub struct AuthorizeUser<'info> {
     )]
     pub authorized_user: Account<'info, AuthorizedUser>,
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+}
@@ -1,73 +1,97 @@
 use std::borrow::BorrowMut;
 
 use anchor_lang::{context::Context, prelude::*};
-use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
 
-use crate::{errors::TallyClobErrors, utils::has_unique_elements, FinalOrder, Market, MarketPortfolio, MarketStatus, Order, User};
+use crate::{errors::TallyClobErrors, utils::{current_timestamp, has_unique_elements, load_book_routes, route_orders, save_book_routes, split_fee, BookRoute}, AuthorizedUser, FeeLedger, FinalOrder, Market, MarketPortfolio, MarketStatus, Order, ProgramConfig, Side, TradeTotals, User};
 
 pub fn bulk_buy_by_price(
     ctx: Context<BulkBuyByPrice>,
-    mut orders: Vec<Order>
+    orders: Vec<Order>
 ) -> Result<()> {
-    let mint = &ctx.accounts.mint;
-    let mint_key = mint.key().to_string();
-    let usdc_key = "5DUWZLh3zPKAAJKu7ftMJJrkBrKnq3zHPPmguzVkhSes".to_string(); // not actually usdc address for development
-
-    require!(mint_key == usdc_key, TallyClobErrors::NotUSDC);
-
-    let fee_account = &ctx.accounts.fee_usdc_account;
-    let source = &ctx.accounts.from_usdc_account;
-    let authority = &ctx.accounts.signer;
+    let config = &ctx.accounts.config;
+
+    let market_key = ctx.accounts.market.key();
+    let taker = ctx.accounts.user.key();
+    let trade_totals = process_bulk_buy_by_price(
+        &mut ctx.accounts.user,
+        &mut ctx.accounts.market,
+        &mut ctx.accounts.market_portfolio,
+        orders,
+        taker,
+        &mut load_book_routes(ctx.remaining_accounts, &market_key)?,
+        &ctx.accounts.config
+    )?;
 
-    require!(source.owner.to_string() == "7rTBUSkc8PHPW3VwGiPB4EbwHWxoSvVpMmbnAqRiGwWx", TallyClobErrors::NotAuthorized);
-    require!(fee_account.owner.to_string() == "eQv1C2XUfsn1ynM65NghBikNsH4TDnTQn5aSZYZdH79",TallyClobErrors::NotAuthorized);
-    require!(source.owner.to_string() == authority.key().to_string(), TallyClobErrors::NotAuthorized);
+    // the creator's and referrer's cut goes to their fee rewards, the rest accrues for the protocol
+    let protocol_fee_amount = split_fee(
+        trade_totals,
+        config,
+        ctx.accounts.market.creator,
+        Some(&mut ctx.accounts.user),
+        ctx.accounts.creator.iter_mut().chain(ctx.accounts.referrer.iter_mut()).collect()
+    )?;
+    ctx.accounts.fee_ledger.accrue(protocol_fee_amount)?;
 
-    let token_program = &ctx.accounts.token_program;
-    let cpi_program = token_program.to_account_info();
+    Ok(())
+}
 
+/// Applies the orders to the user, market and portfolio and returns the
+/// fees owed and volume traded, leaving the token movement to the caller.
+pub fn process_bulk_buy_by_price(
+    user: &mut User,
+    market: &mut Market,
+    market_portfolio: &mut MarketPortfolio,
+    mut orders: Vec<Order>,
+    taker: Pubkey,
+    routes: &mut [BookRoute],
+    config: &ProgramConfig
+) -> Result<TradeTotals> {
     let orders: &mut Vec<Order> = orders.borrow_mut();
 
     // check orders
     // 1. check if there is less than 10 orders,
-    require!(orders.len() <= ctx.accounts.market.sub_markets.len(), TallyClobErrors::BulkOrderTooBig);
+    require!(orders.len() <= market.sub_markets.len(), TallyClobErrors::BulkOrderTooBig);
     
     // 2. check if there are any duplicate choice_ids
     let sub_market_ids = orders.iter().map(|order| order.sub_market_id).collect::<Vec<u64>>();
     require!(has_unique_elements(sub_market_ids), TallyClobErrors::SameSubMarket);
     
     // 3. check if all the requested submarkets are in a buying period
-    let market_periods = &ctx.accounts.market
+    let market_periods = &market
         .get_buying_periods(orders)?;
     let mut is_buying_periods = market_periods.iter()
         .map(|market_period| [MarketStatus::Trading].contains(market_period));
     require!(is_buying_periods.all(|is_buying_period| !!is_buying_period), TallyClobErrors::NotBuyingPeriod);
 
-    // 4. calculate the prices
-    let order_values = ctx.accounts.market.bulk_buy_values_by_price(orders)?;
+    // the trader's volume over the last 30 days sets their fee discount
+    let now = current_timestamp();
+    let discount_bps = config.get_fee_discount(user.get_trailing_volume(now));
+    let fee_schedules = market.get_fee_schedules(orders, &config.fee_schedule, discount_bps)?;
 
-    order_values.iter().for_each(|order| msg!("order_values: shares: {}, price: {}, fee: {}", order.shares_to_buy, order.buy_price, order.fee_price));
+    // 4. fill what resting limit orders beat the curve on, the rest goes to the curve
+    let book_fills = route_orders(market, routes, orders, &fee_schedules, taker, Side::Bid, true)?;
 
-    // 5. check for slippage on the price per share
-    let actual_prices_per_share = order_values.iter()
-        .map(|values| values.buy_price as f64 / values.shares_to_buy as f64).collect::<Vec<f64>>();
-    actual_prices_per_share.iter().for_each(|pps|msg!("pps: {}", pps));
+    // 5. calculate the prices
+    let order_values = market.bulk_buy_values_by_price(orders, &fee_schedules)?;
 
-    // 6. Check if all prices are within the expected range
-    let prices_in_range = orders.iter().enumerate().map(|(index, order)| {
-        let top = order.requested_price_per_share * 1.05; // 1.05 as fixed-point
-        let bottom = order.requested_price_per_share * 0.95; // 0.95 as fixed-point
-        let within_limit = bottom < actual_prices_per_share[index] && actual_prices_per_share[index] < top;
-        within_limit
-    }).collect::<Vec<bool>>();
+    order_values.iter().for_each(|order| msg!("order_values: shares: {}, price: {}, fee: {}", order.shares_to_buy, order.buy_price, order.fee_price));
 
-    // 7. Ensure all prices are within the expected range
-    require!(prices_in_range.iter().all(|in_range| *in_range), TallyClobErrors::PriceEstimationOff);
+    // 6. check every order filled within its slippage bounds
+    for (index, order) in orders.iter().enumerate() {
+        let values = &order_values[index];
+        let book_fill = &book_fills[index];
+        order.check_buy_slippage(
+            values.shares_to_buy + book_fill.shares,
+            values.buy_price + values.fee_price + book_fill.quote + book_fill.fee
+        )?;
+    }
 
 
-    // 6. check if user has enough balance
-    let total_price = order_values.iter().map(|values|values.buy_price + values.fee_price).sum::<u128>();
-    require!(ctx.accounts.user.balance + ctx.accounts.user.unreedemable_balance >= total_price, TallyClobErrors::BalanceTooLow);
+    // 7. check if user has enough balance
+    let book_price = book_fills.iter().map(|book_fill| book_fill.quote + book_fill.fee).sum::<u128>();
+    let curve_price = order_values.iter().map(|values|values.buy_price + values.fee_price).sum::<u128>();
+    let total_price = book_price + curve_price;
+    require!(user.balance + user.unreedemable_balance >= total_price, TallyClobErrors::BalanceTooLow);
 
     // prep order
     let final_orders = orders.iter()
@@ -86,34 +110,51 @@ pub fn bulk_buy_by_price(
     final_orders.iter().for_each(|order|msg!("final_order: shares: {}, price: {}", order.shares, order.price));
 
     // Make order
-    // 1. update user balance
-    ctx.accounts.user.withdraw_from_balance(total_price)?;
+    // 1. update user balance, resting orders are paid out of the real balance
+    if book_price > 0 {
+        user.withdraw_real_balance(book_price)?;
+    }
+    if curve_price > 0 {
+        user.withdraw_from_balance(curve_price)?;
+    }
     // 2. update market pots and prices
-    ctx.accounts.market.adjust_markets_after_buy(&final_orders)?;
-    // 3. update user portfolio
-    ctx.accounts.market_portfolio.bulk_add_to_portfolio(&final_orders)?;
-
-    //send fees
-    let total_fee_amount = order_values.iter().map(|order|order.fee_price).sum::<u128>();
-
-    let fee_cpi_accounts = Transfer {
-        from: source.to_account_info().clone(),
-        to: fee_account.to_account_info().clone(),
-        authority: authority.to_account_info().clone()
-    };
-    
-    transfer (
-        CpiContext::new(cpi_program, fee_cpi_accounts),
-        total_fee_amount as u64 / 10_u64.pow(3)
-    )?;
-
-    Ok(())
+    market.adjust_markets_after_buy(&final_orders)?;
+    // 3. update user portfolio with the shares and cost from both the curve and the book
+    let portfolio_orders = final_orders.iter()
+        .zip(book_fills.iter())
+        .map(|(order, book_fill)| FinalOrder {
+            shares: order.shares + book_fill.shares,
+            price: order.price + book_fill.quote,
+            ..order.clone()
+        })
+        .filter(|order| order.shares > 0)
+        .collect::<Vec<FinalOrder>>();
+    market_portfolio.bulk_add_to_portfolio(&portfolio_orders)?;
+    // 4. write the matched order books back
+    save_book_routes(routes)?;
+
+    let fee = order_values.iter().map(|order|order.fee_price).sum::<u128>()
+        + book_fills.iter().map(|book_fill| book_fill.fee).sum::<u128>();
+    let maker_fee = book_fills.iter().map(|book_fill| book_fill.maker_fee).sum::<u128>();
+    user.record_volume(total_price, now)?;
+
+    Ok(TradeTotals { fee, volume: total_price, maker_fee })
 }
 
 #[derive(Accounts)]
 pub struct BulkBuyByPrice<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
+    pub authorized_user: Option<Account<'info, AuthorizedUser>>,
     #[account(mut)]
     pub user: Account<'info, User>,
     #[account(mut)]
@@ -127,10 +168,14 @@ pub struct BulkBuyByPrice<'info> {
     )]
     pub market_portfolio: Account<'info, MarketPortfolio>,
     pub system_program: Program<'info, System>,
-    #[account(mut )]
-    pub from_usdc_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"fee_ledger".as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+    #[account(mut)]
+    pub creator: Option<Account<'info, User>>,
     #[account(mut)]
-    pub fee_usdc_account: Account<'info, TokenAccount>,
-    pub mint: Account<'info, Mint>,
-    pub token_program: Program<'info, Token>,
+    pub referrer: Option<Account<'info, User>>,
 }
\ No newline at end of file
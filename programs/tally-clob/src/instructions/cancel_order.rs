@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::{MarketPortfolio, OrderBook, Side, User};
+
+/// Pulls a resting order and hands its escrow, maker fee escrow included,
+/// back to the owner.
+pub fn cancel_order(
+    ctx: Context<CancelOrder>,
+    side: Side,
+    order_id: u64
+) -> Result<()> {
+    let user_key = ctx.accounts.user.key();
+    let order_book = &mut ctx.accounts.order_book;
+
+    let order = order_book.remove_order(side, order_id, user_key)?;
+
+    match side {
+        Side::Bid => {
+            let refund = order.escrow + order.fee_escrow;
+            if refund > 0 {
+                ctx.accounts.user.add_to_balance(refund)?;
+            }
+        }
+        Side::Ask => {
+            ctx.accounts.market_portfolio.add_to_portfolio(&order_book.sub_market_id, &order_book.choice_id, order.shares)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelOrder<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"users".as_ref(), signer.key().as_ref()],
+        bump = user.bump
+    )]
+    pub user: Account<'info, User>,
+    #[account(
+        mut,
+        seeds = [b"market_portfolios".as_ref(), order_book.market.as_ref(), user.key().as_ref(), ],
+        bump
+    )]
+    pub market_portfolio: Account<'info, MarketPortfolio>,
+    #[account(mut)]
+    pub order_book: Account<'info, OrderBook>,
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::{AuthorizedUser, ProgramConfig, User};
+
+/// Moves the fee rewards a market creator has earned into their balance.
+pub fn claim_fee_rewards(ctx: Context<ClaimFeeRewards>) -> Result<()> {
+    let amount = ctx.accounts.user.claim_fee_rewards()?;
+    msg!("fee rewards claimed: {}", amount);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimFeeRewards<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
+    pub authorized_user: Option<Account<'info, AuthorizedUser>>,
+    #[account(mut)]
+    pub user: Account<'info, User>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::{AuthorizedUser, ProgramConfig, User};
+
+/// Moves what a referrer has earned from their referred users' trades into
+/// their balance.
+pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+    let amount = ctx.accounts.user.claim_referral_rewards()?;
+    msg!("referral rewards claimed: {}", amount);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
+    pub authorized_user: Option<Account<'info, AuthorizedUser>>,
+    #[account(mut)]
+    pub user: Account<'info, User>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::TallyClobErrors, AuthorizedUser, Market, MarketPortfolio, ProgramConfig, User};
+
+pub fn claim_refund(
+    ctx: Context<ClaimRefund>,
+    sub_market_id: u64
+) -> Result<()> {
+    process_claim_refund(
+        &mut ctx.accounts.user,
+        &mut ctx.accounts.market,
+        &mut ctx.accounts.market_portfolio,
+        sub_market_id
+    )
+}
+
+/// Refunds every unclaimed position the portfolio holds in a voided sub
+/// market and closes them out. Cost basis can add up to more than the sub
+/// market holds, since book trades and profitable exits never touch the
+/// pots, so refunds stop at whatever collateral it has left.
+pub fn process_claim_refund(
+    user: &mut User,
+    market: &mut Market,
+    market_portfolio: &mut MarketPortfolio,
+    sub_market_id: u64
+) -> Result<()> {
+    let sub_market = market.get_sub_market(&sub_market_id)?;
+    require!(sub_market.voided, TallyClobErrors::MarketNotVoided);
+
+    // tokenized shares can be unwrapped after a refund, so those are refunded whenever they're held
+    let tokenized = sub_market.is_tokenized();
+
+    let mut total_refund = 0;
+    for choice_portfolio in market_portfolio.get_sub_market_portfolio(&sub_market_id)?.choice_portfolio.iter_mut() {
+        if (choice_portfolio.claimed && !tokenized) || choice_portfolio.shares == 0 { continue }
+
+        total_refund += sub_market.get_refund(choice_portfolio)?;
+
+        choice_portfolio.withdraw_from_portfolio(choice_portfolio.shares)?;
+        choice_portfolio.claimed = !tokenized;
+    }
+
+    let total_refund = total_refund.min(sub_market.unclaimed_collateral());
+    require!(total_refund > 0, TallyClobErrors::NothingToRefund);
+
+    sub_market.record_claim(total_refund)?;
+
+    user.add_to_balance(total_refund)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
+    pub authorized_user: Option<Account<'info, AuthorizedUser>>,
+    #[account(mut)]
+    pub user: Account<'info, User>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"market_portfolios".as_ref(), market.key().as_ref(), user.key().as_ref(), ],
+        bump
+    )]
+    pub market_portfolio: Account<'info, MarketPortfolio>,
+    pub system_program: Program<'info, System>
+}
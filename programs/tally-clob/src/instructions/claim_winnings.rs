@@ -1,49 +1,69 @@
 use anchor_lang::prelude::*;
 
-use crate::{errors::TallyClobErrors, Market, MarketPortfolio, User};
+use crate::{errors::TallyClobErrors, AuthorizedUser, Market, MarketPortfolio, ProgramConfig, ResolutionStatus, User};
 
 pub fn claim_winnings(
     ctx: Context<ClaimWinnings>,
     sub_market_id: u64,
     choice_id: u64
 ) -> Result<()> {
+    process_claim_winnings(
+        &mut ctx.accounts.user,
+        &mut ctx.accounts.market,
+        &mut ctx.accounts.market_portfolio,
+        sub_market_id,
+        choice_id
+    )
+}
+
+pub fn process_claim_winnings(
+    user: &mut User,
+    market: &mut Market,
+    market_portfolio: &mut MarketPortfolio,
+    sub_market_id: u64,
+    choice_id: u64
+) -> Result<()> {
+
+    let resolved = market.get_sub_market(&sub_market_id)?.resolved;
+
+    require!(!market.get_sub_market(&sub_market_id)?.voided, TallyClobErrors::MarketVoided);
 
-    let resolved = ctx.accounts.market.get_sub_market(&sub_market_id)?.resolved;
+    let resolution = market.get_sub_market(&sub_market_id)?.resolution;
 
-    let winning_choice = ctx.accounts.market.get_sub_market(&sub_market_id)?.get_choice(&choice_id)?.winning_choice;
+    let tokenized = market.get_sub_market(&sub_market_id)?.is_tokenized();
 
-    let choice_market_portfolio = ctx.accounts.market_portfolio.get_sub_market_portfolio(&sub_market_id)?.get_choice_market_portfolio(&choice_id)?;
+    let winning_choice = market.get_sub_market(&sub_market_id)?.get_choice(&choice_id)?.is_winning();
+
+    let choice_market_portfolio = market_portfolio.get_sub_market_portfolio(&sub_market_id)?.get_choice_market_portfolio(&choice_id)?;
 
     // check if market is resolved
     require!(resolved, TallyClobErrors::MarketNotResolved);
 
+    // outcomes can still be disputed or corrected until they're final
+    require!(resolution == ResolutionStatus::Finalized, TallyClobErrors::InDisputePeriod);
+
     // check if user has a winning choice
     require!(winning_choice, TallyClobErrors::NotWinningChoice);
 
-    // check if user's shares have already been claimed
-    require!(!choice_market_portfolio.claimed, TallyClobErrors::AlreadyClaimed);
-
-    let total_pot = ctx.accounts.market.get_sub_market(&sub_market_id)?.choices.iter()
-        .map(|choice| {
-            msg!(&choice.usdc_pot.to_string());
-            choice.usdc_pot
-        })
-        .sum::<u128>();
-    
-
-    let winning_shares = ctx.accounts.market.get_sub_market(&sub_market_id)?.get_choice(&choice_id)?.minted_shares;
+    // check if user's shares have already been claimed, tokenized shares can
+    // be unwrapped after a claim so those pay whatever the portfolio holds
+    match tokenized {
+        true => require!(choice_market_portfolio.shares > 0, TallyClobErrors::AlreadyClaimed),
+        false => require!(!choice_market_portfolio.claimed, TallyClobErrors::AlreadyClaimed),
+    }
 
-    let pot_portion = choice_market_portfolio.shares / winning_shares;
-
-    let total_winnings = pot_portion * total_pot;
+    // each share is paid its choice's cut of the whole pot
+    let total_winnings = market.get_sub_market(&sub_market_id)?.get_payout(&choice_id, choice_market_portfolio.shares)?;
 
     // withdraw from shares
     choice_market_portfolio.withdraw_from_portfolio(choice_market_portfolio.shares)?;
 
-    choice_market_portfolio.claimed = true;
+    choice_market_portfolio.claimed = !tokenized;
+
+    market.get_sub_market(&sub_market_id)?.record_claim(total_winnings)?;
 
     // add to balance 
-    ctx.accounts.user.add_to_balance(total_winnings)?;
+    user.add_to_balance(total_winnings)?;
 
     // err!(TallyClobErrors::NotAValidOrder)
 
@@ -56,11 +76,25 @@ pub fn claim_winnings(
 pub struct ClaimWinnings<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
+    pub authorized_user: Option<Account<'info, AuthorizedUser>>,
     #[account(mut)]
     pub user: Account<'info, User>,
     #[account(mut)]
     pub market: Account<'info, Market>,
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"market_portfolios".as_ref(), market.key().as_ref(), user.key().as_ref(), ],
+        bump
+    )]
     pub market_portfolio: Account<'info, MarketPortfolio>,
     pub system_program: Program<'info, System>
 }
\ No newline at end of file
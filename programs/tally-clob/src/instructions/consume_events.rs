@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::TallyClobErrors, EventQueue, MarketPortfolio, OrderBook, Side, User};
+
+/// Settles the makers of queued fills, oldest first. Ask makers are paid into
+/// their `User` less their maker fee, which left the vault with the taker's
+/// trade, and bid makers get their shares in their `MarketPortfolio`; the
+/// account to credit for each fill is passed in the remaining accounts.
+/// Stops at the first fill whose account is missing.
+pub fn consume_events(
+    ctx: Context<ConsumeEvents>,
+    limit: u16
+) -> Result<()> {
+    let order_book = &ctx.accounts.order_book;
+    let event_queue = &mut ctx.accounts.event_queue;
+
+    let mut consumed = 0;
+    for event in event_queue.events.iter().take(limit as usize) {
+        let settle_key = match event.maker_side {
+            Side::Ask => event.maker,
+            Side::Bid => event.maker_portfolio,
+        };
+        let account = match ctx.remaining_accounts.iter().find(|account| account.key() == settle_key) {
+            Some(account) => account,
+            None => break,
+        };
+        require!(account.owner == ctx.program_id && account.is_writable, TallyClobErrors::NotAuthorized);
+
+        let mut data = account.try_borrow_mut_data()?;
+        match event.maker_side {
+            Side::Ask => {
+                let mut user = User::try_deserialize(&mut &data[..])?;
+                let proceeds = event.quote - event.maker_fee;
+                if proceeds > 0 {
+                    user.add_to_balance(proceeds)?;
+                }
+                user.try_serialize(&mut &mut data[..])?;
+            }
+            Side::Bid => {
+                let mut market_portfolio = MarketPortfolio::try_deserialize(&mut &data[..])?;
+                market_portfolio.add_to_portfolio(&order_book.sub_market_id, &order_book.choice_id, event.shares)?;
+                market_portfolio.add_cost(&order_book.sub_market_id, &order_book.choice_id, event.quote, 0)?;
+                market_portfolio.try_serialize(&mut &mut data[..])?;
+            }
+        }
+
+        consumed += 1;
+    }
+
+    event_queue.events.drain(..consumed);
+    msg!("consumed: {}, left: {}", consumed, event_queue.events.len());
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ConsumeEvents<'info> {
+    pub signer: Signer<'info>,
+    pub order_book: Account<'info, OrderBook>,
+    #[account(
+        mut,
+        seeds = [b"event_queues".as_ref(), order_book.key().as_ref()],
+        bump = event_queue.bump
+    )]
+    pub event_queue: Account<'info, EventQueue>,
+}
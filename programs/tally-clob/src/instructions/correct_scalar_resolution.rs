@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::{AuthorizedUser, FeeLedger, Market, ProgramConfig, User};
+
+use super::pay_resolution_fee;
+
+/// Settles a proposed or disputed scalar sub market on `value` and finalizes
+/// it straight away.
+pub fn correct_scalar_resolution(
+    ctx: Context<CorrectScalarResolution>,
+    sub_market_id: u64,
+    value: i64
+) -> Result<()> {
+    let sub_market = ctx.accounts.market.get_sub_market(&sub_market_id)?;
+    let payout_numerators = sub_market.get_scalar_payouts(value)?;
+    sub_market.correct_payouts(&payout_numerators)?;
+
+    pay_resolution_fee(
+        &mut ctx.accounts.market,
+        sub_market_id,
+        &ctx.accounts.config,
+        &mut ctx.accounts.fee_ledger,
+        ctx.accounts.creator.as_mut()
+    )
+}
+
+#[derive(Accounts)]
+pub struct CorrectScalarResolution<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
+    pub authorized_user: Account<'info, AuthorizedUser>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        mut,
+        seeds = [b"fee_ledger".as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+    #[account(mut)]
+    pub creator: Option<Account<'info, User>>,
+}
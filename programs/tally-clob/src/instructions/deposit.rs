@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{errors::TallyClobErrors, utils::{to_balance_amount, to_token_amount, transfer_to_vault}, ProgramConfig, User};
+
+pub fn deposit(ctx: Context<Deposit>, amount: u128) -> Result<()> {
+    let mint = &ctx.accounts.mint;
+    let config = &ctx.accounts.config;
+    require!(mint.key() == config.usdc_mint, TallyClobErrors::NotUSDC);
+
+    let token_amount = to_token_amount(amount, mint.decimals)?;
+    require!(token_amount > 0, TallyClobErrors::AmountToAddTooLow);
+
+    transfer_to_vault(
+        &ctx.accounts.token_program,
+        &ctx.accounts.from_usdc_account,
+        &ctx.accounts.vault,
+        &ctx.accounts.signer,
+        token_amount
+    )?;
+
+    // only credit what actually landed in the vault
+    ctx.accounts.user.add_to_balance(to_balance_amount(token_amount, mint.decimals)?)?;
+
+    msg!("Your new balance is: {}", ctx.accounts.user.balance);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(mut)]
+    pub user: Account<'info, User>,
+    #[account(mut)]
+    pub from_usdc_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), mint.key().as_ref()],
+        bump = config.vault_bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::TallyClobErrors, events::ResolutionDisputed, utils::current_timestamp, Market, ProgramConfig, Proposal, User};
+
+/// Disputes a bonded proposal by matching its bond, escalating the sub
+/// market to an arbiter.
+pub fn dispute_proposal(
+    ctx: Context<DisputeProposal>,
+    sub_market_id: u64
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    require!(proposal.disputer.is_none() && !proposal.settled, TallyClobErrors::InvalidProposalStatus);
+    require!(proposal.proposer != ctx.accounts.user.key(), TallyClobErrors::NotAuthorized);
+
+    ctx.accounts.market.get_sub_market(&sub_market_id)?.dispute_resolution()?;
+
+    if proposal.bond > 0 {
+        ctx.accounts.user.withdraw_real_balance(proposal.bond)?;
+    }
+    proposal.disputer = Some(ctx.accounts.user.key());
+
+    emit!(ResolutionDisputed {
+        market: ctx.accounts.market.key(),
+        sub_market_id,
+        disputer: ctx.accounts.signer.key(),
+        timestamp: current_timestamp(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(sub_market_id: u64)]
+pub struct DisputeProposal<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [b"users".as_ref(), signer.key().as_ref()],
+        bump = user.bump
+    )]
+    pub user: Account<'info, User>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"proposals".as_ref(), market.key().as_ref(), sub_market_id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::ResolutionDisputed, utils::current_timestamp, AuthorizedUser, Market};
+
+/// Holds a proposed outcome for an arbiter to settle.
+pub fn dispute_resolution(
+    ctx: Context<DisputeResolution>,
+    sub_market_id: u64
+) -> Result<()> {
+    ctx.accounts.market.get_sub_market(&sub_market_id)?.dispute_resolution()?;
+
+    emit!(ResolutionDisputed {
+        market: ctx.accounts.market.key(),
+        sub_market_id,
+        disputer: ctx.accounts.signer.key(),
+        timestamp: current_timestamp(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DisputeResolution<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
+    pub authorized_user: Account<'info, AuthorizedUser>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    pub system_program: Program<'info, System>,
+}
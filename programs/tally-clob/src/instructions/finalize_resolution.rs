@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::ResolutionFinalized, utils::{current_timestamp, split_fee}, FeeLedger, Market, ProgramConfig, TradeTotals, User};
+
+/// Anyone can finalize an undisputed outcome once its dispute period is over.
+pub fn finalize_resolution(
+    ctx: Context<FinalizeResolution>,
+    sub_market_id: u64
+) -> Result<()> {
+    ctx.accounts.market.get_sub_market(&sub_market_id)?.finalize_resolution()?;
+
+    pay_resolution_fee(
+        &mut ctx.accounts.market,
+        sub_market_id,
+        &ctx.accounts.config,
+        &mut ctx.accounts.fee_ledger,
+        ctx.accounts.creator.as_mut()
+    )
+}
+
+/// Accrues the protocol's cut of the finalized sub market's fee on the fee
+/// ledger.
+pub fn pay_resolution_fee<'info>(
+    market: &mut Account<'info, Market>,
+    sub_market_id: u64,
+    config: &ProgramConfig,
+    fee_ledger: &mut Account<'info, FeeLedger>,
+    creator: Option<&mut Account<'info, User>>
+) -> Result<()> {
+    let fee_schedule = market.get_fee_schedule(&sub_market_id, &config.fee_schedule)?;
+    let sub_market = market.get_sub_market(&sub_market_id)?;
+    let fee_price = sub_market.take_resolution_fee(&fee_schedule)?;
+    let payout_numerators = sub_market.get_payouts();
+
+    // the creator's cut stays in the vault as their fee rewards
+    let totals = TradeTotals { fee: fee_price, ..Default::default() };
+    let fee_price = split_fee(totals, config, market.creator, None, creator.into_iter().collect())?;
+
+    fee_ledger.accrue(fee_price)?;
+
+    emit!(ResolutionFinalized {
+        market: market.key(),
+        sub_market_id,
+        payout_numerators,
+        timestamp: current_timestamp(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FinalizeResolution<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        mut,
+        seeds = [b"fee_ledger".as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+    #[account(mut)]
+    pub creator: Option<Account<'info, User>>,
+}
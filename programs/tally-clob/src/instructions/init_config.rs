@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{program::TallyClob, FeeSchedule, FeeSplit, InitProgramConfig, ProgramConfig};
+
+pub fn init_config(
+    ctx: Context<InitConfig>,
+    init_config: InitProgramConfig
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.bump = ctx.bumps.config;
+    config.owner = init_config.owner;
+    config.wallet_manager = init_config.wallet_manager;
+    config.clob_manager = init_config.clob_manager;
+    config.fee_owner = init_config.fee_owner;
+    config.usdc_mint = ctx.accounts.mint.key();
+    config.paused = false;
+    config.vault_initialized = false;
+    config.user_count = 0;
+    config.market_count = 0;
+    config.order_book_count = 0;
+    config.proposal_count = 0;
+    config.dispute_period = ProgramConfig::DEFAULT_DISPUTE_PERIOD;
+    config.proposal_bond = ProgramConfig::DEFAULT_PROPOSAL_BOND;
+    config.fee_schedule = FeeSchedule::DEFAULT;
+    config.fee_split = FeeSplit::DEFAULT;
+    config.referral_tiers = Vec::new();
+    config.fee_tiers = Vec::new();
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitConfig<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        init,
+        payer = signer,
+        space = ProgramConfig::SIZE,
+        seeds = [b"program_config".as_ref()],
+        bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    pub mint: Account<'info, Mint>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, TallyClob>,
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
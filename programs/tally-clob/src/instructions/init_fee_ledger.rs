@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::{FeeLedger, ProgramConfig};
+
+pub fn init_fee_ledger(ctx: Context<InitFeeLedger>) -> Result<()> {
+    let fee_ledger = &mut ctx.accounts.fee_ledger;
+
+    fee_ledger.bump = ctx.bumps.fee_ledger;
+    fee_ledger.accrued = 0;
+    fee_ledger.swept = 0;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitFeeLedger<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        init,
+        payer = signer,
+        space = FeeLedger::SIZE,
+        seeds = [b"fee_ledger".as_ref()],
+        bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+    pub system_program: Program<'info, System>,
+}
@@ -1,15 +1,37 @@
 use anchor_lang::prelude::*;
 
-use crate::{state:: Market, AuthorizedUser, InitSubMarket, SubMarket};
+use crate::{errors::TallyClobErrors, state:: Market, AuthorizedUser, InitSubMarket, ProgramConfig, SubMarket, User};
 
 pub fn init_market(
     ctx: Context<InitMarket>,
     init_sub_markets: Vec<InitSubMarket>
 ) -> Result<()> {
+    require!(init_sub_markets.len() <= Market::MARKET_MAX_LENGTH, TallyClobErrors::BulkOrderTooBig);
+
+    for init_sub_market in init_sub_markets.iter() {
+        let choice_count = init_sub_market.choice_ids.len();
+        require!(
+            (2..=SubMarket::MAX_CHOICES).contains(&choice_count),
+            TallyClobErrors::InvalidChoiceCount
+        );
+        // choices are looked up by binary search
+        require!(
+            init_sub_market.choice_ids.windows(2).all(|ids| ids[0] < ids[1]),
+            TallyClobErrors::InvalidChoiceCount
+        );
+        if let Some(range) = init_sub_market.scalar {
+            require!(choice_count == 2 && range.lower < range.upper, TallyClobErrors::InvalidScalarRange);
+        }
+        init_sub_market.curve.pricing_curve().validate(init_sub_market)?;
+    }
+
     let sub_markets = init_sub_markets.iter()
         .map(|init_sub_market| SubMarket::new(init_sub_market))
         .collect::<Vec<SubMarket>>();
     ctx.accounts.market.sub_markets = sub_markets;
+    // the creator earns their cut of the market's fees as fee rewards
+    ctx.accounts.market.creator = ctx.accounts.creator.as_ref().map(|creator| creator.key());
+    ctx.accounts.config.market_count += 1;
 
     Ok(())
 }
@@ -19,7 +41,16 @@ pub fn init_market(
 pub struct InitMarket<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
     pub authorized_user: Account<'info, AuthorizedUser>,
     #[account(
         init,
@@ -30,4 +61,5 @@ pub struct InitMarket<'info> {
     )]
     pub market: Account<'info, Market>,
     pub system_program: Program<'info, System>,
+    pub creator: Option<Account<'info, User>>,
 }
\ No newline at end of file
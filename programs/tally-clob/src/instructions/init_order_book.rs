@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::TallyClobErrors, AuthorizedUser, EventQueue, Market, OrderBook, ProgramConfig};
+
+pub fn init_order_book(
+    ctx: Context<InitOrderBook>,
+    sub_market_id: u64,
+    choice_id: u64
+) -> Result<()> {
+    // the book has to be for a real choice, in a sub market whose shares aren't tokens
+    let sub_market = ctx.accounts.market.get_sub_market(&sub_market_id)?;
+    require!(!sub_market.is_tokenized(), TallyClobErrors::SharesTokenized);
+    sub_market.get_choice(&choice_id)?;
+
+    let order_book = &mut ctx.accounts.order_book;
+    order_book.bump = ctx.bumps.order_book;
+    order_book.market = ctx.accounts.market.key();
+    order_book.sub_market_id = sub_market_id;
+    order_book.choice_id = choice_id;
+    order_book.next_order_id = 0;
+
+    let event_queue = &mut ctx.accounts.event_queue;
+    event_queue.bump = ctx.bumps.event_queue;
+    event_queue.order_book = order_book.key();
+    event_queue.seq_num = 0;
+    ctx.accounts.config.order_book_count += 1;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(sub_market_id: u64, choice_id: u64)]
+pub struct InitOrderBook<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
+    pub authorized_user: Account<'info, AuthorizedUser>,
+    pub market: Account<'info, Market>,
+    #[account(
+        init,
+        payer = signer,
+        space = OrderBook::SIZE,
+        seeds = [b"order_books".as_ref(), market.key().as_ref(), sub_market_id.to_le_bytes().as_ref(), choice_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub order_book: Account<'info, OrderBook>,
+    #[account(
+        init,
+        payer = signer,
+        space = EventQueue::SIZE,
+        seeds = [b"event_queues".as_ref(), order_book.key().as_ref()],
+        bump
+    )]
+    pub event_queue: Account<'info, EventQueue>,
+    pub system_program: Program<'info, System>,
+}
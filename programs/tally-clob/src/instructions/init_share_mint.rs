@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token};
+
+use crate::{errors::TallyClobErrors, utils::BALANCE_DECIMALS, AuthorizedUser, Market};
+
+/// Creates the SPL mint for one choice's shares. Once every choice in a sub
+/// market has one, the sub market is tokenized and self-custodial trades
+/// settle positions as tokens instead of portfolio entries.
+pub fn init_share_mint(
+    ctx: Context<InitShareMint>,
+    sub_market_id: u64,
+    choice_id: u64
+) -> Result<()> {
+    let sub_market = ctx.accounts.market.get_sub_market(&sub_market_id)?;
+    require!(!sub_market.resolved, TallyClobErrors::MarketAlreadyResolved);
+
+    // the mint has to be for a real choice
+    sub_market.get_choice(&choice_id)?;
+
+    sub_market.share_mint_count += 1;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(sub_market_id: u64, choice_id: u64)]
+pub struct InitShareMint<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
+    pub authorized_user: Account<'info, AuthorizedUser>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(
+        init,
+        payer = signer,
+        seeds = [b"share_mints".as_ref(), market.key().as_ref(), sub_market_id.to_le_bytes().as_ref(), choice_id.to_le_bytes().as_ref()],
+        bump,
+        mint::decimals = BALANCE_DECIMALS,
+        mint::authority = share_mint
+    )]
+    pub share_mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
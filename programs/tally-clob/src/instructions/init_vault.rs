@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{errors::TallyClobErrors, ProgramConfig};
+
+pub fn init_vault(ctx: Context<InitVault>) -> Result<()> {
+    let mint = &ctx.accounts.mint;
+    require!(mint.key() == ctx.accounts.config.usdc_mint, TallyClobErrors::NotUSDC);
+
+    ctx.accounts.config.vault_bump = ctx.bumps.vault;
+    ctx.accounts.config.vault_initialized = true;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitVault<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        init,
+        payer = signer,
+        seeds = [b"vault".as_ref(), mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = vault
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
\ No newline at end of file
@@ -1,10 +1,20 @@
 use anchor_lang::prelude::*;
 
-use crate::User;
+use crate::{AuthorizedUser, ProgramConfig, User, VOLUME_DAYS};
 
-pub fn init_wallet(ctx: Context<InitWallet>) -> Result<()> {
+pub fn init_wallet(ctx: Context<InitWallet>, user_key: Pubkey) -> Result<()> {
+    ctx.accounts.user.bump = ctx.bumps.user;
     ctx.accounts.user.balance = 0;
     ctx.accounts.user.unreedemable_balance = 0;
+    ctx.accounts.user.owner = user_key;
+    ctx.accounts.user.fee_rewards = 0;
+    ctx.accounts.user.referral_rewards = 0;
+    ctx.accounts.user.referred_volume = 0;
+    ctx.accounts.user.daily_volume = [0; VOLUME_DAYS];
+    ctx.accounts.user.volume_day = 0;
+    ctx.accounts.user.custodial = true;
+    ctx.accounts.user.referrer = ctx.accounts.referrer.as_ref().map(|referrer| referrer.key());
+    ctx.accounts.config.user_count += 1;
     Ok(())
 }
 
@@ -13,6 +23,17 @@ pub fn init_wallet(ctx: Context<InitWallet>) -> Result<()> {
 pub struct InitWallet<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
+    pub authorized_user: Option<Account<'info, AuthorizedUser>>,
     #[account(
         init,
         payer = signer,
@@ -22,4 +43,5 @@ pub struct InitWallet<'info> {
     )]
     pub user: Account<'info, User>,
     pub system_program: Program<'info, System>,
+    pub referrer: Option<Account<'info, User>>,
 }
\ No newline at end of file
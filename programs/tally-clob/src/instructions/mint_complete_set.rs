@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::TallyClobErrors, Market, MarketPortfolio, MarketStatus, ProgramConfig, User};
+
+/// Pays `sets` of collateral for `sets` shares of every choice in a sub
+/// market. The pots and prices are left alone.
+pub fn mint_complete_set(
+    ctx: Context<MintCompleteSet>,
+    sub_market_id: u64,
+    sets: u128
+) -> Result<()> {
+    require!(sets > 0, TallyClobErrors::AmountToAddTooLow);
+
+    let sub_market = ctx.accounts.market.get_sub_market(&sub_market_id)?;
+    require!(sub_market.get_market_period()? == MarketStatus::Trading, TallyClobErrors::NotBuyingPeriod);
+    // positions in tokenized sub markets are held as share tokens, not in the portfolio
+    require!(!sub_market.is_tokenized(), TallyClobErrors::SharesTokenized);
+
+    ctx.accounts.user.withdraw_real_balance(sets)?;
+
+    sub_market.mint_complete_sets(sets)?;
+
+    // the set's cost is split evenly over its choices, the first one taking the rounding
+    let choice_count = sub_market.choices.len() as u128;
+    let market_portfolio = &mut ctx.accounts.market_portfolio;
+    for (index, choice) in sub_market.choices.iter().enumerate() {
+        let cost_basis = match index {
+            0 => sets / choice_count + sets % choice_count,
+            _ => sets / choice_count,
+        };
+        market_portfolio.add_to_portfolio(&sub_market_id, &choice.id, sets)?;
+        market_portfolio.add_cost(&sub_market_id, &choice.id, cost_basis, 0)?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MintCompleteSet<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [b"users".as_ref(), signer.key().as_ref()],
+        bump = user.bump
+    )]
+    pub user: Account<'info, User>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = MarketPortfolio::SIZE,
+        seeds = [b"market_portfolios".as_ref(), market.key().as_ref(), user.key().as_ref(), ],
+        bump
+    )]
+    pub market_portfolio: Account<'info, MarketPortfolio>,
+    pub system_program: Program<'info, System>,
+}
@@ -1,4 +1,3 @@
-pub use add_to_balance::*;
 pub use add_to_unreedeemable::*;
 pub use bulk_buy_by_shares::*;
 pub use bulk_buy_by_price::*;
@@ -12,10 +11,57 @@ pub use claim_winnings::*;
 pub use authorize_user::*;
 pub use start_trading::*;
 pub use fair_launch_order::*;
+pub use init_config::*;
+pub use update_config::*;
+pub use set_fee_owner::*;
+pub use set_paused::*;
+pub use init_vault::*;
+pub use user_init_wallet::*;
+pub use deposit::*;
+pub use user_withdraw::*;
+pub use user_bulk_buy_by_price::*;
+pub use user_bulk_buy_by_shares::*;
+pub use user_bulk_sell_by_price::*;
+pub use user_bulk_sell_by_shares::*;
+pub use user_fair_launch_order::*;
+pub use user_claim_winnings::*;
+pub use start_solvency_audit::*;
+pub use audit_solvency::*;
+pub use init_order_book::*;
+pub use place_order::*;
+pub use cancel_order::*;
+pub use consume_events::*;
+pub use mint_complete_set::*;
+pub use redeem_complete_set::*;
+pub use init_share_mint::*;
+pub use transfer_shares::*;
+pub use void_sub_market::*;
+pub use claim_refund::*;
+pub use user_claim_refund::*;
+pub use dispute_resolution::*;
+pub use finalize_resolution::*;
+pub use correct_resolution::*;
+pub use set_oracle_config::*;
+pub use resolve_from_oracle::*;
+pub use set_mock_feed::*;
+pub use propose_outcome::*;
+pub use dispute_proposal::*;
+pub use settle_proposal::*;
+pub use resolve_scalar::*;
+pub use correct_scalar_resolution::*;
+pub use resolve_weighted::*;
+pub use correct_weighted_resolution::*;
+pub use set_fee_schedule::*;
+pub use set_market_fee_schedule::*;
+pub use set_fee_split::*;
+pub use claim_fee_rewards::*;
+pub use claim_referral_rewards::*;
+pub use set_fee_tiers::*;
+pub use init_fee_ledger::*;
+pub use sweep_fees::*;
 
 
 
-pub mod add_to_balance;
 pub mod add_to_unreedeemable;
 pub mod withdraw_from_balance;
 pub mod bulk_buy_by_shares;
@@ -29,4 +75,51 @@ pub mod claim_winnings;
 pub mod authorize_user;
 pub mod start_trading;
 pub mod fair_launch_order;
-
+pub mod init_config;
+pub mod update_config;
+pub mod set_fee_owner;
+pub mod set_paused;
+pub mod init_vault;
+pub mod user_init_wallet;
+pub mod deposit;
+pub mod user_withdraw;
+pub mod user_bulk_buy_by_price;
+pub mod user_bulk_buy_by_shares;
+pub mod user_bulk_sell_by_price;
+pub mod user_bulk_sell_by_shares;
+pub mod user_fair_launch_order;
+pub mod user_claim_winnings;
+pub mod start_solvency_audit;
+pub mod audit_solvency;
+pub mod init_order_book;
+pub mod place_order;
+pub mod cancel_order;
+pub mod consume_events;
+pub mod mint_complete_set;
+pub mod redeem_complete_set;
+pub mod init_share_mint;
+pub mod transfer_shares;
+pub mod void_sub_market;
+pub mod claim_refund;
+pub mod user_claim_refund;
+pub mod dispute_resolution;
+pub mod finalize_resolution;
+pub mod correct_resolution;
+pub mod set_oracle_config;
+pub mod resolve_from_oracle;
+pub mod set_mock_feed;
+pub mod propose_outcome;
+pub mod dispute_proposal;
+pub mod settle_proposal;
+pub mod resolve_scalar;
+pub mod correct_scalar_resolution;
+pub mod resolve_weighted;
+pub mod correct_weighted_resolution;
+pub mod set_fee_schedule;
+pub mod set_market_fee_schedule;
+pub mod set_fee_split;
+pub mod claim_fee_rewards;
+pub mod claim_referral_rewards;
+pub mod set_fee_tiers;
+pub mod init_fee_ledger;
+pub mod sweep_fees;
\ No newline at end of file
@@ -0,0 +1,185 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::TallyClobErrors, get_maker_fee, get_order_cost, utils::{current_timestamp, split_fee}, EventQueue, FeeLedger, Market, MarketPortfolio, MarketStatus, OrderBook, ProgramConfig, RestingOrder, Side, TradeTotals, User};
+
+/// Crosses a limit order with the other side of the book and rests whatever
+/// doesn't fill. The taker is settled on every fill and pays the taker fee on
+/// the lot; makers are settled when their fills are consumed from the event
+/// queue. What rests keeps the user's maker rate for when it fills.
+pub fn place_order(
+    ctx: Context<PlaceOrder>,
+    sub_market_id: u64,
+    choice_id: u64,
+    side: Side,
+    price: u128,
+    shares: u128
+) -> Result<()> {
+    require!(price > 0 && shares > 0, TallyClobErrors::InvalidOrderPrice);
+
+    let config = &ctx.accounts.config;
+
+    let market_period = ctx.accounts.market.get_sub_market(&sub_market_id)?.get_market_period()?;
+    match side {
+        Side::Bid => require!(market_period == MarketStatus::Trading, TallyClobErrors::NotBuyingPeriod),
+        Side::Ask => require!(market_period == MarketStatus::Trading, TallyClobErrors::NotSellingPeriod),
+    }
+    // the book settles through the portfolio, share tokens trade against the curve
+    require!(!ctx.accounts.market.get_sub_market(&sub_market_id)?.is_tokenized(), TallyClobErrors::SharesTokenized);
+
+    let now = current_timestamp();
+    let discount_bps = config.get_fee_discount(ctx.accounts.user.get_trailing_volume(now));
+    let fee_schedule = ctx.accounts.market
+        .get_fee_schedule(&sub_market_id, &config.fee_schedule)?
+        .discounted(discount_bps);
+
+    let user_key = ctx.accounts.user.key();
+    let market_portfolio_key = ctx.accounts.market_portfolio.key();
+    let user = &mut ctx.accounts.user;
+    let market_portfolio = &mut ctx.accounts.market_portfolio;
+    let order_book = &mut ctx.accounts.order_book;
+    let event_queue = &mut ctx.accounts.event_queue;
+
+    // asks escrow every share up front, filled or not
+    if side == Side::Ask {
+        market_portfolio.sell_from_portfolio(&sub_market_id, &choice_id, shares)?;
+    }
+
+    let fills = order_book.match_order(user_key, side, price, shares)?;
+    let maker_side = match side {
+        Side::Bid => Side::Ask,
+        Side::Ask => Side::Bid,
+    };
+
+    let mut filled_shares = 0;
+    let mut filled_quote = 0;
+    let mut maker_fee = 0;
+    for fill in fills.iter() {
+        // asks are paid for the whole lot once the taker fee is known
+        if side == Side::Bid {
+            if fill.quote > 0 {
+                user.withdraw_real_balance(fill.quote)?;
+            }
+            market_portfolio.add_to_portfolio(&sub_market_id, &choice_id, fill.shares)?;
+            market_portfolio.add_cost(&sub_market_id, &choice_id, fill.quote, 0)?;
+        }
+
+        event_queue.push_fill(fill, maker_side, user_key, now)?;
+        filled_shares += fill.shares;
+        filled_quote += fill.quote;
+        maker_fee += fill.maker_fee;
+    }
+
+    if !fills.is_empty() {
+        let fee = match filled_quote {
+            0 => 0,
+            _ => fee_schedule.taker_fee(filled_quote),
+        };
+        let volume = match side {
+            Side::Bid => {
+                if fee > 0 {
+                    user.withdraw_real_balance(fee)?;
+                }
+                filled_quote + fee
+            }
+            Side::Ask => {
+                if filled_quote > fee {
+                    user.add_to_balance(filled_quote - fee)?;
+                }
+                filled_quote
+            }
+        };
+        user.record_volume(volume, now)?;
+
+        // the creator's and referrer's cut goes to their fee rewards, the rest accrues for the protocol
+        let protocol_fee_amount = split_fee(
+            TradeTotals { fee, volume, maker_fee },
+            config,
+            ctx.accounts.market.creator,
+            Some(&mut ctx.accounts.user),
+            ctx.accounts.creator.iter_mut().chain(ctx.accounts.referrer.iter_mut()).collect()
+        )?;
+        ctx.accounts.fee_ledger.accrue(protocol_fee_amount)?;
+    }
+
+    let remaining_shares = shares - filled_shares;
+    if remaining_shares == 0 {
+        return Ok(())
+    }
+
+    let user = &mut ctx.accounts.user;
+    let (escrow, fee_escrow) = match side {
+        Side::Bid => {
+            let escrow = get_order_cost(price, remaining_shares)?;
+            require!(escrow > 0, TallyClobErrors::InvalidOrderPrice);
+            let fee_escrow = get_maker_fee(escrow, fee_schedule.maker_bps);
+            user.withdraw_real_balance(escrow + fee_escrow)?;
+            (escrow, fee_escrow)
+        }
+        Side::Ask => (0, 0),
+    };
+
+    let order_id = ctx.accounts.order_book.insert_order(side, RestingOrder {
+        order_id: 0,
+        owner: user_key,
+        owner_portfolio: market_portfolio_key,
+        price,
+        shares: remaining_shares,
+        escrow,
+        placed_at: now,
+        maker_bps: fee_schedule.maker_bps,
+        fee_escrow
+    })?;
+    msg!("resting order: {}, shares: {}", order_id, remaining_shares);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(sub_market_id: u64, choice_id: u64)]
+pub struct PlaceOrder<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [b"users".as_ref(), signer.key().as_ref()],
+        bump = user.bump
+    )]
+    pub user: Account<'info, User>,
+    pub market: Account<'info, Market>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = MarketPortfolio::SIZE,
+        seeds = [b"market_portfolios".as_ref(), market.key().as_ref(), user.key().as_ref(), ],
+        bump
+    )]
+    pub market_portfolio: Account<'info, MarketPortfolio>,
+    #[account(
+        mut,
+        seeds = [b"order_books".as_ref(), market.key().as_ref(), sub_market_id.to_le_bytes().as_ref(), choice_id.to_le_bytes().as_ref()],
+        bump = order_book.bump
+    )]
+    pub order_book: Account<'info, OrderBook>,
+    #[account(
+        mut,
+        seeds = [b"event_queues".as_ref(), order_book.key().as_ref()],
+        bump = event_queue.bump
+    )]
+    pub event_queue: Account<'info, EventQueue>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        mut,
+        seeds = [b"fee_ledger".as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+    #[account(mut)]
+    pub creator: Option<Account<'info, User>>,
+    #[account(mut)]
+    pub referrer: Option<Account<'info, User>>,
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::TallyClobErrors, events::ResolutionProposed, utils::current_timestamp, Market, ProgramConfig, Proposal, User};
+
+/// Lets anyone propose the outcome of a sub market whose trading is over by
+/// escrowing the config's proposal bond. The proposal finalizes after the
+/// dispute period unless someone matches the bond to dispute it.
+pub fn propose_outcome(
+    ctx: Context<ProposeOutcome>,
+    sub_market_id: u64,
+    choice_id: u64
+) -> Result<()> {
+    let now = current_timestamp();
+    let config = &mut ctx.accounts.config;
+
+    let sub_market = ctx.accounts.market.get_sub_market(&sub_market_id)?;
+    require!(now >= sub_market.trading_end, TallyClobErrors::TradingNotOver);
+
+    let dispute_ends_at = now + config.dispute_period;
+    sub_market.propose_resolution(&choice_id, dispute_ends_at)?;
+    let payouts = sub_market.get_payouts();
+
+    if config.proposal_bond > 0 {
+        ctx.accounts.user.withdraw_real_balance(config.proposal_bond)?;
+    }
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.bump = ctx.bumps.proposal;
+    proposal.market = ctx.accounts.market.key();
+    proposal.sub_market_id = sub_market_id;
+    proposal.choice_id = choice_id;
+    proposal.payouts = payouts;
+    proposal.bond = config.proposal_bond;
+    proposal.proposer = ctx.accounts.user.key();
+    proposal.disputer = None;
+    proposal.settled = false;
+    config.proposal_count += 1;
+
+    emit!(ResolutionProposed {
+        market: ctx.accounts.market.key(),
+        sub_market_id,
+        choice_id,
+        dispute_ends_at,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(sub_market_id: u64)]
+pub struct ProposeOutcome<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [b"users".as_ref(), signer.key().as_ref()],
+        bump = user.bump
+    )]
+    pub user: Account<'info, User>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(
+        init,
+        payer = signer,
+        space = Proposal::SIZE,
+        seeds = [b"proposals".as_ref(), market.key().as_ref(), sub_market_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::TallyClobErrors, Market, MarketPortfolio, ProgramConfig, User};
+
+/// Burns `sets` shares of every choice in a sub market for `sets` of
+/// collateral, for as long as the sub market hasn't resolved.
+pub fn redeem_complete_set(
+    ctx: Context<RedeemCompleteSet>,
+    sub_market_id: u64,
+    sets: u128
+) -> Result<()> {
+    require!(sets > 0, TallyClobErrors::AmountToWithdrawTooLow);
+
+    let sub_market = ctx.accounts.market.get_sub_market(&sub_market_id)?;
+    require!(!sub_market.resolved, TallyClobErrors::MarketAlreadyResolved);
+
+    let market_portfolio = &mut ctx.accounts.market_portfolio;
+    for choice in sub_market.choices.iter() {
+        market_portfolio.sell_from_portfolio(&sub_market_id, &choice.id, sets)?;
+    }
+
+    sub_market.redeem_complete_sets(sets)?;
+
+    ctx.accounts.user.add_to_balance(sets)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RedeemCompleteSet<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [b"users".as_ref(), signer.key().as_ref()],
+        bump = user.bump
+    )]
+    pub user: Account<'info, User>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"market_portfolios".as_ref(), market.key().as_ref(), user.key().as_ref(), ],
+        bump
+    )]
+    pub market_portfolio: Account<'info, MarketPortfolio>,
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::TallyClobErrors, events::ResolutionProposed, utils::{current_timestamp, get_oracle_outcome, read_oracle_price}, Market, ProgramConfig};
+
+/// Anyone can propose the outcome of a sub market with an oracle config
+/// once its feed has a price for the resolution time. The proposal goes
+/// through the same dispute period as a manual one.
+pub fn resolve_from_oracle(
+    ctx: Context<ResolveFromOracle>,
+    sub_market_id: u64
+) -> Result<()> {
+    let sub_market = ctx.accounts.market.get_sub_market(&sub_market_id)?;
+    let oracle = sub_market.oracle.ok_or(TallyClobErrors::NoOracleConfig)?;
+
+    let price = read_oracle_price(&oracle, &ctx.accounts.feed)?;
+    let choice_id = get_oracle_outcome(&oracle, &price)?;
+    msg!("oracle price: {} * 10^{}, choice: {}", price.price, price.expo, choice_id);
+
+    let now = current_timestamp();
+    let dispute_ends_at = now + ctx.accounts.config.dispute_period;
+    sub_market.propose_resolution(&choice_id, dispute_ends_at)?;
+
+    emit!(ResolutionProposed {
+        market: ctx.accounts.market.key(),
+        sub_market_id,
+        choice_id,
+        dispute_ends_at,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ResolveFromOracle<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    /// CHECK: checked against the sub market's oracle config and read by its layout
+    pub feed: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::ScalarResolutionProposed, utils::current_timestamp, AuthorizedUser, Market, ProgramConfig};
+
+/// Proposes the value a scalar sub market settled at, which sets how its
+/// pot is split between LONG and SHORT. Goes through the same dispute period
+/// as a categorical outcome.
+pub fn resolve_scalar(
+    ctx: Context<ResolveScalar>,
+    sub_market_id: u64,
+    value: i64
+) -> Result<()> {
+    let now = current_timestamp();
+    let dispute_ends_at = now + ctx.accounts.config.dispute_period;
+
+    let sub_market = ctx.accounts.market.get_sub_market(&sub_market_id)?;
+    let payout_numerators = sub_market.get_scalar_payouts(value)?;
+    sub_market.propose_payouts(&payout_numerators, dispute_ends_at)?;
+
+    emit!(ScalarResolutionProposed {
+        market: ctx.accounts.market.key(),
+        sub_market_id,
+        value,
+        payout_numerators,
+        dispute_ends_at,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ResolveScalar<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
+    pub authorized_user: Account<'info, AuthorizedUser>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    pub system_program: Program<'info, System>,
+}
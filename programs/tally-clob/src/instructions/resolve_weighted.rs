@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::PayoutsProposed, utils::current_timestamp, AuthorizedUser, Market, ProgramConfig};
+
+/// Proposes a payout for every choice instead of a single winner, for dead
+/// heats and other partial outcomes. `payout_numerators` are in choice order
+/// and have to add up to `PAYOUT_DENOMINATOR`.
+pub fn resolve_weighted(
+    ctx: Context<ResolveWeighted>,
+    sub_market_id: u64,
+    payout_numerators: Vec<u64>
+) -> Result<()> {
+    let now = current_timestamp();
+    let dispute_ends_at = now + ctx.accounts.config.dispute_period;
+
+    let sub_market = ctx.accounts.market.get_sub_market(&sub_market_id)?;
+    let payout_numerators = sub_market.get_weighted_payouts(&payout_numerators)?;
+    sub_market.propose_payouts(&payout_numerators, dispute_ends_at)?;
+
+    emit!(PayoutsProposed {
+        market: ctx.accounts.market.key(),
+        sub_market_id,
+        payout_numerators,
+        dispute_ends_at,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ResolveWeighted<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
+    pub authorized_user: Account<'info, AuthorizedUser>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    pub system_program: Program<'info, System>,
+}
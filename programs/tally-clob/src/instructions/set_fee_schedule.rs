@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{AuthorizedUser, FeeSchedule, ProgramConfig};
+
+/// Sets the program's default fee schedule, used by every market and sub
+/// market without one of its own.
+pub fn set_fee_schedule(
+    ctx: Context<SetFeeSchedule>,
+    fee_schedule: FeeSchedule
+) -> Result<()> {
+    fee_schedule.validate()?;
+
+    ctx.accounts.config.fee_schedule = fee_schedule;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetFeeSchedule<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
+    pub authorized_user: Account<'info, AuthorizedUser>,
+    pub system_program: Program<'info, System>,
+}
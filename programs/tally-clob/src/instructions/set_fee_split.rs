@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{AuthorizedUser, FeeSplit, ProgramConfig, ReferralTier};
+
+pub fn set_fee_split(
+    ctx: Context<SetFeeSplit>,
+    fee_split: FeeSplit,
+    referral_tiers: Vec<ReferralTier>
+) -> Result<()> {
+    ctx.accounts.config.set_fee_split(fee_split, referral_tiers)
+}
+
+#[derive(Accounts)]
+pub struct SetFeeSplit<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
+    pub authorized_user: Account<'info, AuthorizedUser>,
+    pub system_program: Program<'info, System>,
+}
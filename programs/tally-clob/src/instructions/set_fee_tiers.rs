@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::{AuthorizedUser, FeeTier, ProgramConfig};
+
+pub fn set_fee_tiers(
+    ctx: Context<SetFeeTiers>,
+    fee_tiers: Vec<FeeTier>
+) -> Result<()> {
+    ctx.accounts.config.set_fee_tiers(fee_tiers)
+}
+
+#[derive(Accounts)]
+pub struct SetFeeTiers<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
+    pub authorized_user: Account<'info, AuthorizedUser>,
+    pub system_program: Program<'info, System>,
+}
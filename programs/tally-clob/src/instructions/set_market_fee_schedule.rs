@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{AuthorizedUser, FeeSchedule, Market};
+
+/// Overrides the fee schedule of a market, or of one of its sub markets when
+/// `sub_market_id` is set. `None` falls back to the next level up.
+pub fn set_market_fee_schedule(
+    ctx: Context<SetMarketFeeSchedule>,
+    sub_market_id: Option<u64>,
+    fee_schedule: Option<FeeSchedule>
+) -> Result<()> {
+    if let Some(fee_schedule) = fee_schedule {
+        fee_schedule.validate()?;
+    }
+
+    let market = &mut ctx.accounts.market;
+    match sub_market_id {
+        Some(sub_market_id) => market.get_sub_market(&sub_market_id)?.fee_schedule = fee_schedule,
+        None => market.fee_schedule = fee_schedule,
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMarketFeeSchedule<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
+    pub authorized_user: Account<'info, AuthorizedUser>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    pub system_program: Program<'info, System>,
+}
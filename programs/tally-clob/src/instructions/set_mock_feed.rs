@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::TallyClobErrors, utils::current_timestamp, AuthorizedUser, MockFeed};
+
+/// Publishes a price on a mock feed, creating it for the signer the first
+/// time. Only test builds have mock feeds.
+pub fn set_mock_feed(
+    ctx: Context<SetMockFeed>,
+    price: i64,
+    expo: i32
+) -> Result<()> {
+    require!(cfg!(feature = "test"), TallyClobErrors::MockOracleDisabled);
+
+    let mock_feed = &mut ctx.accounts.mock_feed;
+
+    if mock_feed.authority == Pubkey::default() {
+        mock_feed.bump = ctx.bumps.mock_feed;
+        mock_feed.authority = ctx.accounts.signer.key();
+    }
+    require!(mock_feed.authority == ctx.accounts.signer.key(), TallyClobErrors::NotAuthorized);
+
+    mock_feed.price = price;
+    mock_feed.expo = expo;
+    mock_feed.publish_time = current_timestamp();
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(feed_key: Pubkey)]
+pub struct SetMockFeed<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
+    pub authorized_user: Account<'info, AuthorizedUser>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = MockFeed::SIZE,
+        seeds = [b"mock_feeds".as_ref(), feed_key.as_ref()],
+        bump
+    )]
+    pub mock_feed: Account<'info, MockFeed>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::{AuthorizedUser, Market, OracleConfig};
+
+pub fn set_oracle_config(
+    ctx: Context<SetOracleConfig>,
+    sub_market_id: u64,
+    oracle: Option<OracleConfig>
+) -> Result<()> {
+    ctx.accounts.market.get_sub_market(&sub_market_id)?.set_oracle(oracle)
+}
+
+#[derive(Accounts)]
+pub struct SetOracleConfig<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
+    pub authorized_user: Account<'info, AuthorizedUser>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    pub system_program: Program<'info, System>,
+}
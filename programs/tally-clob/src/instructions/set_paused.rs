@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{AuthorizedUser, ProgramConfig};
+
+pub fn set_paused(
+    ctx: Context<SetPaused>,
+    paused: bool
+) -> Result<()> {
+    ctx.accounts.config.paused = paused;
+
+    msg!("Program paused: {}", paused);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
+    pub authorized_user: Account<'info, AuthorizedUser>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::TallyClobErrors, FeeLedger, Market, ProgramConfig, Proposal, ResolutionStatus, User};
+
+/// Pays out the bonds of a proposal once its sub market is final. The side
+/// the outcome agrees with gets every bond back; a wrong proposal nobody
+/// disputed forfeits its bond to fees. A sub market voided after the
+/// proposal sides with nobody, so each bond goes back to whoever posted it.
+pub fn settle_proposal(
+    ctx: Context<SettleProposal>,
+    sub_market_id: u64
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    require!(!proposal.settled, TallyClobErrors::InvalidProposalStatus);
+    require!(ctx.accounts.proposer.key() == proposal.proposer, TallyClobErrors::InvalidProposalAccount);
+    require!(
+        ctx.accounts.disputer.as_ref().map(|disputer| disputer.key()) == proposal.disputer,
+        TallyClobErrors::InvalidProposalAccount
+    );
+
+    let sub_market = ctx.accounts.market.get_sub_market(&sub_market_id)?;
+    require!(sub_market.resolution == ResolutionStatus::Finalized, TallyClobErrors::InDisputePeriod);
+    let voided = sub_market.voided;
+    // a proposal that was only partly right, like a winner that ends up sharing the pot, still lost
+    let proposal_won = sub_market.get_payouts() == proposal.payouts;
+
+    let bonds = match proposal.disputer {
+        Some(_) => proposal.bond * 2,
+        None => proposal.bond,
+    };
+    proposal.settled = true;
+    if bonds == 0 { return Ok(()) }
+
+    if voided {
+        ctx.accounts.proposer.add_to_balance(proposal.bond)?;
+        if let Some(disputer) = ctx.accounts.disputer.as_mut() {
+            disputer.add_to_balance(proposal.bond)?;
+        }
+        return Ok(())
+    }
+
+    match (proposal_won, ctx.accounts.disputer.as_mut()) {
+        (true, _) => {
+            ctx.accounts.proposer.add_to_balance(bonds)?;
+        }
+        (false, Some(disputer)) => {
+            disputer.add_to_balance(bonds)?;
+        }
+        (false, None) => {
+            ctx.accounts.fee_ledger.accrue(bonds)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(sub_market_id: u64)]
+pub struct SettleProposal<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"proposals".as_ref(), market.key().as_ref(), sub_market_id.to_le_bytes().as_ref()],
+        bump = proposal.bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(mut)]
+    pub proposer: Account<'info, User>,
+    #[account(mut)]
+    pub disputer: Option<Account<'info, User>>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        mut,
+        seeds = [b"fee_ledger".as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+}
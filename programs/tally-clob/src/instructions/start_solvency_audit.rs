@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::{utils::current_timestamp, ProgramConfig, SolvencyAudit};
+
+pub fn start_solvency_audit(
+    ctx: Context<StartSolvencyAudit>,
+    audit_id: u64
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let audit = &mut ctx.accounts.audit;
+
+    audit.bump = ctx.bumps.audit;
+    audit.audit_id = audit_id;
+    audit.auditor = ctx.accounts.signer.key();
+    audit.started_at = current_timestamp();
+    audit.user_count = config.user_count;
+    audit.market_count = config.market_count;
+    audit.order_book_count = config.order_book_count;
+    audit.proposal_count = config.proposal_count;
+    audit.users_audited = 0;
+    audit.markets_audited = 0;
+    audit.order_books_audited = 0;
+    audit.event_queues_audited = 0;
+    audit.proposals_audited = 0;
+    audit.last_user = Pubkey::default();
+    audit.last_market = Pubkey::default();
+    audit.last_order_book = Pubkey::default();
+    audit.last_event_queue = Pubkey::default();
+    audit.last_proposal = Pubkey::default();
+    audit.user_liabilities = 0;
+    audit.market_liabilities = 0;
+    audit.escrow_liabilities = 0;
+    audit.bond_liabilities = 0;
+    audit.fee_liabilities = 0;
+    audit.vault_balance = 0;
+    audit.finalized = false;
+    audit.solvent = false;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(audit_id: u64)]
+pub struct StartSolvencyAudit<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        init,
+        payer = signer,
+        space = SolvencyAudit::SIZE,
+        seeds = [b"solvency_audits".as_ref(), signer.key().as_ref(), audit_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub audit: Account<'info, SolvencyAudit>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
@@ -19,7 +19,10 @@ pub fn start_trading(
 pub struct StartTrading<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
-    #[account(mut)]
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
     pub authorized_user: Account<'info, AuthorizedUser>,
     #[account(mut)]
     pub market: Account<'info, Market>,
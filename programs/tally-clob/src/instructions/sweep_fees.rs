@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{errors::TallyClobErrors, events::FeesSwept, utils::{current_timestamp, to_balance_amount, to_token_amount, transfer_from_vault}, AuthorizedUser, FeeLedger, ProgramConfig};
+
+/// Sends every whole token unit of accrued fees from the vault to the fee
+/// owner. Whatever is below a token unit stays on the ledger for the next
+/// sweep.
+pub fn sweep_fees(ctx: Context<SweepFees>) -> Result<()> {
+    let mint = &ctx.accounts.mint;
+    let config = &ctx.accounts.config;
+    require!(mint.key() == config.usdc_mint, TallyClobErrors::NotUSDC);
+
+    let fee_account = &ctx.accounts.fee_usdc_account;
+    require!(fee_account.owner == config.fee_owner, TallyClobErrors::NotAuthorized);
+
+    let fee_ledger = &mut ctx.accounts.fee_ledger;
+    let token_amount = to_token_amount(fee_ledger.accrued, mint.decimals)?;
+    let amount = to_balance_amount(token_amount, mint.decimals)?;
+    fee_ledger.sweep(amount)?;
+
+    transfer_from_vault(
+        &ctx.accounts.token_program,
+        &ctx.accounts.vault,
+        fee_account,
+        config.vault_bump,
+        token_amount
+    )?;
+
+    emit!(FeesSwept {
+        amount,
+        remainder: fee_ledger.accrued,
+        timestamp: current_timestamp(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SweepFees<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
+    pub authorized_user: Account<'info, AuthorizedUser>,
+    #[account(
+        mut,
+        seeds = [b"fee_ledger".as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), mint.key().as_ref()],
+        bump = config.vault_bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub fee_usdc_account: Account<'info, TokenAccount>,
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+}
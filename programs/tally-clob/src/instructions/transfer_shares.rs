@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::TallyClobErrors, events::SharesTransferred, utils::current_timestamp, AuthorizedUser, Market, MarketPortfolio, ProgramConfig, User};
+
+/// Moves shares of one choice from the sender's portfolio to the
+/// recipient's, opening the recipient's portfolio if they don't have one.
+pub fn transfer_shares(
+    ctx: Context<TransferShares>,
+    sub_market_id: u64,
+    choice_id: u64,
+    shares: u128
+) -> Result<()> {
+    require!(shares > 0, TallyClobErrors::AmountToAddTooLow);
+    require!(ctx.accounts.sender.key() != ctx.accounts.recipient.key(), TallyClobErrors::NotAuthorized);
+
+    let sub_market = ctx.accounts.market.get_sub_market(&sub_market_id)?;
+    require!(!sub_market.resolved, TallyClobErrors::MarketAlreadyResolved);
+    // share tokens are transferred with the token program instead
+    require!(!sub_market.is_tokenized(), TallyClobErrors::SharesTokenized);
+    sub_market.get_choice(&choice_id)?;
+
+    // the cost basis and fair launch deposit move with the shares
+    let (cost_basis, fair_launch_deposit) = ctx.accounts.sender_portfolio
+        .get_sub_market_portfolio(&sub_market_id)?
+        .get_choice_market_portfolio(&choice_id)?
+        .get_cost_of(shares);
+
+    ctx.accounts.sender_portfolio.sell_from_portfolio(&sub_market_id, &choice_id, shares)?;
+    ctx.accounts.recipient_portfolio.add_to_portfolio(&sub_market_id, &choice_id, shares)?;
+    ctx.accounts.recipient_portfolio.add_cost(&sub_market_id, &choice_id, cost_basis, fair_launch_deposit)?;
+
+    emit!(SharesTransferred {
+        market: ctx.accounts.market.key(),
+        sub_market_id,
+        choice_id,
+        from: ctx.accounts.sender.key(),
+        to: ctx.accounts.recipient.key(),
+        shares,
+        authority: ctx.accounts.signer.key(),
+        timestamp: current_timestamp(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct TransferShares<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
+    pub authorized_user: Option<Account<'info, AuthorizedUser>>,
+    pub sender: Account<'info, User>,
+    pub recipient: Account<'info, User>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"market_portfolios".as_ref(), market.key().as_ref(), sender.key().as_ref(), ],
+        bump
+    )]
+    pub sender_portfolio: Account<'info, MarketPortfolio>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = MarketPortfolio::SIZE,
+        seeds = [b"market_portfolios".as_ref(), market.key().as_ref(), recipient.key().as_ref(), ],
+        bump
+    )]
+    pub recipient_portfolio: Account<'info, MarketPortfolio>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{ProgramConfig, ProgramConfigUpdate};
+
+pub fn update_config(
+    ctx: Context<UpdateConfig>,
+    update: ProgramConfigUpdate
+) -> Result<()> {
+    ctx.accounts.config.apply_update(&update)?;
+
+    Ok(())
+}
+
+pub fn transfer_ownership(
+    ctx: Context<UpdateConfig>,
+    new_owner: Pubkey
+) -> Result<()> {
+    ctx.accounts.config.owner = new_owner;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+
+use crate::{utils::{load_book_routes, split_fee, split_share_accounts, ShareTokenizer}, FeeLedger, Market, MarketPortfolio, Order, ProgramConfig, User};
+
+use super::process_bulk_sell_by_shares;
+
+pub fn user_bulk_sell_by_shares<'info>(
+    ctx: Context<'_, '_, '_, 'info, UserBulkSellByShares<'info>>,
+    orders: Vec<Order>
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+
+    let market_key = ctx.accounts.market.key();
+    let taker = ctx.accounts.user.key();
+    let (book_accounts, share_accounts) = split_share_accounts(ctx.remaining_accounts);
+    let share_tokenizer = ShareTokenizer {
+        token_program: &ctx.accounts.token_program,
+        owner: &ctx.accounts.signer,
+        market: market_key,
+        share_accounts
+    };
+    let choices = orders.iter().map(|order| (order.sub_market_id, order.choice_id)).collect::<Vec<(u64, u64)>>();
+
+    // tokenized positions are burned back into the portfolio to be sold
+    share_tokenizer.unwrap_positions(&mut ctx.accounts.market, &mut ctx.accounts.market_portfolio, &choices)?;
+
+    let trade_totals = process_bulk_sell_by_shares(
+        &mut ctx.accounts.user,
+        &mut ctx.accounts.market,
+        &mut ctx.accounts.market_portfolio,
+        orders,
+        taker,
+        &mut load_book_routes(book_accounts, &market_key)?,
+        &ctx.accounts.config
+    )?;
+
+    // and positions in tokenized sub markets leave the portfolio as tokens
+    share_tokenizer.wrap_positions(&mut ctx.accounts.market, &mut ctx.accounts.market_portfolio, &choices)?;
+
+    // the creator's and referrer's cut goes to their fee rewards, the rest accrues for the protocol
+    let protocol_fee_amount = split_fee(
+        trade_totals,
+        config,
+        ctx.accounts.market.creator,
+        Some(&mut ctx.accounts.user),
+        ctx.accounts.creator.iter_mut().chain(ctx.accounts.referrer.iter_mut()).collect()
+    )?;
+    ctx.accounts.fee_ledger.accrue(protocol_fee_amount)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UserBulkSellByShares<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [b"users".as_ref(), signer.key().as_ref()],
+        bump = user.bump
+    )]
+    pub user: Account<'info, User>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"market_portfolios".as_ref(), market.key().as_ref(), user.key().as_ref(), ],
+        bump
+    )]
+    pub market_portfolio: Account<'info, MarketPortfolio>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        mut,
+        seeds = [b"fee_ledger".as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+    pub token_program: Program<'info, Token>,
+    #[account(mut)]
+    pub creator: Option<Account<'info, User>>,
+    #[account(mut)]
+    pub referrer: Option<Account<'info, User>>,
+}
\ No newline at end of file
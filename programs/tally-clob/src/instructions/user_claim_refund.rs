@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+
+use crate::{utils::ShareTokenizer, Market, MarketPortfolio, ProgramConfig, User};
+
+use super::process_claim_refund;
+
+pub fn user_claim_refund<'info>(
+    ctx: Context<'_, '_, '_, 'info, UserClaimRefund<'info>>,
+    sub_market_id: u64
+) -> Result<()> {
+    // share tokens of every choice are burned back into the portfolio and refunded with it
+    let choices = ctx.accounts.market.get_sub_market(&sub_market_id)?.choices.iter()
+        .map(|choice| (sub_market_id, choice.id))
+        .collect::<Vec<(u64, u64)>>();
+    let share_tokenizer = ShareTokenizer {
+        token_program: &ctx.accounts.token_program,
+        owner: &ctx.accounts.signer,
+        market: ctx.accounts.market.key(),
+        share_accounts: ctx.remaining_accounts
+    };
+    share_tokenizer.unwrap_positions(&mut ctx.accounts.market, &mut ctx.accounts.market_portfolio, &choices)?;
+
+    process_claim_refund(
+        &mut ctx.accounts.user,
+        &mut ctx.accounts.market,
+        &mut ctx.accounts.market_portfolio,
+        sub_market_id
+    )
+}
+
+#[derive(Accounts)]
+pub struct UserClaimRefund<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [b"users".as_ref(), signer.key().as_ref()],
+        bump = user.bump
+    )]
+    pub user: Account<'info, User>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"market_portfolios".as_ref(), market.key().as_ref(), user.key().as_ref(), ],
+        bump
+    )]
+    pub market_portfolio: Account<'info, MarketPortfolio>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>
+}
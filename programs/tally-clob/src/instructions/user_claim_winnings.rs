@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+
+use crate::{utils::ShareTokenizer, Market, MarketPortfolio, ProgramConfig, User};
+
+use super::process_claim_winnings;
+
+pub fn user_claim_winnings<'info>(
+    ctx: Context<'_, '_, '_, 'info, UserClaimWinnings<'info>>,
+    sub_market_id: u64,
+    choice_id: u64
+) -> Result<()> {
+    // winning share tokens are burned into the portfolio and claimed with it
+    let share_tokenizer = ShareTokenizer {
+        token_program: &ctx.accounts.token_program,
+        owner: &ctx.accounts.signer,
+        market: ctx.accounts.market.key(),
+        share_accounts: ctx.remaining_accounts
+    };
+    share_tokenizer.unwrap_positions(&mut ctx.accounts.market, &mut ctx.accounts.market_portfolio, &[(sub_market_id, choice_id)])?;
+
+    process_claim_winnings(
+        &mut ctx.accounts.user,
+        &mut ctx.accounts.market,
+        &mut ctx.accounts.market_portfolio,
+        sub_market_id,
+        choice_id
+    )
+}
+
+#[derive(Accounts)]
+pub struct UserClaimWinnings<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [b"users".as_ref(), signer.key().as_ref()],
+        bump = user.bump
+    )]
+    pub user: Account<'info, User>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(
+        mut,
+        seeds = [b"market_portfolios".as_ref(), market.key().as_ref(), user.key().as_ref(), ],
+        bump
+    )]
+    pub market_portfolio: Account<'info, MarketPortfolio>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>
+}
\ No newline at end of file
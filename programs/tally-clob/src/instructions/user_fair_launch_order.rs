@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::{FeeLedger, Market, MarketPortfolio, Order, ProgramConfig, User};
+
+use super::process_fair_launch_order;
+
+pub fn user_fair_launch_order(
+    ctx: Context<UserFairLaunchOrder>,
+    orders: Vec<Order>
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+
+    let total_fee_amount = process_fair_launch_order(
+        &mut ctx.accounts.user,
+        &mut ctx.accounts.market,
+        &mut ctx.accounts.market_portfolio,
+        orders,
+        &config.fee_schedule
+    )?;
+
+    ctx.accounts.fee_ledger.accrue(total_fee_amount)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UserFairLaunchOrder<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [b"users".as_ref(), signer.key().as_ref()],
+        bump = user.bump
+    )]
+    pub user: Account<'info, User>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = MarketPortfolio::SIZE,
+        seeds = [b"market_portfolios".as_ref(), market.key().as_ref(), user.key().as_ref(), ],
+        bump
+    )]
+    pub market_portfolio: Account<'info, MarketPortfolio>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        mut,
+        seeds = [b"fee_ledger".as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+}
\ No newline at end of file
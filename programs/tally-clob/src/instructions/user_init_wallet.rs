@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{ProgramConfig, User, VOLUME_DAYS};
+
+pub fn user_init_wallet(ctx: Context<UserInitWallet>) -> Result<()> {
+    ctx.accounts.user.bump = ctx.bumps.user;
+    ctx.accounts.user.balance = 0;
+    ctx.accounts.user.unreedemable_balance = 0;
+    ctx.accounts.user.owner = ctx.accounts.signer.key();
+    ctx.accounts.user.fee_rewards = 0;
+    ctx.accounts.user.referral_rewards = 0;
+    ctx.accounts.user.referred_volume = 0;
+    ctx.accounts.user.daily_volume = [0; VOLUME_DAYS];
+    ctx.accounts.user.volume_day = 0;
+    ctx.accounts.user.custodial = false;
+    ctx.accounts.user.referrer = ctx.accounts.referrer.as_ref().map(|referrer| referrer.key());
+    ctx.accounts.config.user_count += 1;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UserInitWallet<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        init,
+        payer = signer,
+        space = User::SIZE, 
+        seeds = [b"users".as_ref(), signer.key().as_ref()], 
+        bump
+    )]
+    pub user: Account<'info, User>,
+    pub system_program: Program<'info, System>,
+    pub referrer: Option<Account<'info, User>>,
+}
\ No newline at end of file
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::{FeeLedger, ProgramConfig, User};
+
+use super::pay_out_of_vault;
+
+pub fn user_withdraw(ctx: Context<UserWithdraw>, amount: u128) -> Result<()> {
+    pay_out_of_vault(
+        &mut ctx.accounts.user,
+        &ctx.accounts.config,
+        &ctx.accounts.mint,
+        &ctx.accounts.vault,
+        &ctx.accounts.to_usdc_account,
+        &mut ctx.accounts.fee_ledger,
+        &ctx.accounts.token_program,
+        amount
+    )
+}
+
+#[derive(Accounts)]
+pub struct UserWithdraw<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [b"users".as_ref(), signer.key().as_ref()],
+        bump = user.bump
+    )]
+    pub user: Account<'info, User>,
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), mint.key().as_ref()],
+        bump = config.vault_bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+    #[account(mut)]
+    pub to_usdc_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"fee_ledger".as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::{AuthorizedUser, Market, RefundPolicy};
+
+pub fn void_sub_market(
+    ctx: Context<VoidSubMarket>,
+    sub_market_id: u64,
+    refund_policy: RefundPolicy
+) -> Result<()> {
+    ctx.accounts.market.get_sub_market(&sub_market_id)?.void(refund_policy)
+}
+
+#[derive(Accounts)]
+pub struct VoidSubMarket<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
+    pub authorized_user: Account<'info, AuthorizedUser>,
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+    pub system_program: Program<'info, System>,
+}
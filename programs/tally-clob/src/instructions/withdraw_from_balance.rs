@@ -1,56 +1,60 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token::{Mint, Token, TokenAccount};
 
-use crate::{errors::TallyClobErrors, User};
+use crate::{errors::TallyClobErrors, utils::{to_balance_amount, to_token_amount, transfer_from_vault}, AuthorizedUser, FeeLedger, ProgramConfig, User};
 
 pub fn withdraw_from_balance(ctx: Context<WithdrawFromBalance>, amount: u128) -> Result<()> {
-    let mint = &ctx.accounts.mint;
-    let mint_key = mint.key().to_string();
-    let usdc_key = "5DUWZLh3zPKAAJKu7ftMJJrkBrKnq3zHPPmguzVkhSes".to_string(); // not actually usdc address for development
-    require!(mint_key == usdc_key, TallyClobErrors::NotUSDC);
-    
-    require!(amount > 0, TallyClobErrors::AmountToWithdrawTooLow);
-    require!(amount <= ctx.accounts.user.balance, TallyClobErrors::AmountToWithdrawTooGreat);
-    
-    ctx.accounts.user.withdraw_real_balance(amount)?;
-
     let destination = &ctx.accounts.to_usdc_account;
-    let source = &ctx.accounts.from_usdc_account;
-    let fee_account = &ctx.accounts.fee_usdc_account;
-    let authority = &ctx.accounts.signer;
-
-    require!(source.owner.to_string() == "7rTBUSkc8PHPW3VwGiPB4EbwHWxoSvVpMmbnAqRiGwWx", TallyClobErrors::NotAuthorized);
-    require!(fee_account.owner.to_string() == "eQv1C2XUfsn1ynM65NghBikNsH4TDnTQn5aSZYZdH79",TallyClobErrors::NotAuthorized);
-    require!(source.owner.to_string() == authority.key().to_string(), TallyClobErrors::NotAuthorized);
+    require!(destination.owner == ctx.accounts.user.owner, TallyClobErrors::NotAuthorized);
 
-    let token_program = &ctx.accounts.token_program;
-    let cpi_program = &token_program.to_account_info();
+    pay_out_of_vault(
+        &mut ctx.accounts.user,
+        &ctx.accounts.config,
+        &ctx.accounts.mint,
+        &ctx.accounts.vault,
+        destination,
+        &mut ctx.accounts.fee_ledger,
+        &ctx.accounts.token_program,
+        amount
+    )
+}
 
-    let fee_amount = amount / 20;
-    let new_amount = (amount as i64 - fee_amount as i64) as u64;
+/// Sends `amount`, less the program's withdrawal fee, from the vault to
+/// `destination` in whole token units. Only the fee and what was sent are
+/// debited from the real balance, so anything below a token unit stays there.
+/// The fee accrues on the fee ledger.
+#[allow(clippy::too_many_arguments)]
+pub fn pay_out_of_vault<'info>(
+    user: &mut User,
+    config: &ProgramConfig,
+    mint: &Account<'info, Mint>,
+    vault: &Account<'info, TokenAccount>,
+    destination: &Account<'info, TokenAccount>,
+    fee_ledger: &mut Account<'info, FeeLedger>,
+    token_program: &Program<'info, Token>,
+    amount: u128
+) -> Result<()> {
+    require!(mint.key() == config.usdc_mint, TallyClobErrors::NotUSDC);
+    
+    require!(amount > 0, TallyClobErrors::AmountToWithdrawTooLow);
+    require!(amount <= user.balance, TallyClobErrors::AmountToWithdrawTooGreat);
+    
+    let fee_amount = config.fee_schedule.withdrawal_fee(amount);
+    let token_amount = to_token_amount(amount - fee_amount, mint.decimals)?;
+    require!(token_amount > 0, TallyClobErrors::AmountToWithdrawTooLow);
 
-    // transfer fees
-    let fee_cpi_accounts = Transfer {
-        from: source.to_account_info().clone(),
-        to: fee_account.to_account_info().clone(),
-        authority: authority.to_account_info().clone()
-    };
+    user.withdraw_real_balance(fee_amount + to_balance_amount(token_amount, mint.decimals)?)?;
 
-    transfer (
-        CpiContext::new(cpi_program.clone(), fee_cpi_accounts),
-        fee_amount as u64
-    )?;
+    // accrue fees
+    fee_ledger.accrue(fee_amount)?;
 
     // transfer amount
-    let cpi_accounts = Transfer {
-        from: source.to_account_info().clone(),
-        to: destination.to_account_info().clone(),
-        authority: authority.to_account_info().clone()
-    };
-
-    transfer (
-        CpiContext::new(cpi_program.clone(), cpi_accounts),
-        new_amount
+    transfer_from_vault(
+        token_program,
+        vault,
+        destination,
+        config.vault_bump,
+        token_amount
     )?;
 
     Ok(())
@@ -60,14 +64,32 @@ pub fn withdraw_from_balance(ctx: Context<WithdrawFromBalance>, amount: u128) ->
 pub struct WithdrawFromBalance<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
+    #[account(
+        seeds = [b"program_config".as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    #[account(
+        seeds = [b"authorized_users".as_ref(), signer.key().as_ref()],
+        bump = authorized_user.bump
+    )]
+    pub authorized_user: Option<Account<'info, AuthorizedUser>>,
     #[account(mut)]
     pub user: Account<'info, User>,
-    #[account(mut )]
-    pub from_usdc_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"vault".as_ref(), mint.key().as_ref()],
+        bump = config.vault_bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
     #[account(mut)]
     pub to_usdc_account: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub fee_usdc_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [b"fee_ledger".as_ref()],
+        bump = fee_ledger.bump
+    )]
+    pub fee_ledger: Account<'info, FeeLedger>,
     pub system_program: Program<'info, System>,
     pub mint: Account<'info, Mint>,
     pub token_program: Program<'info, Token>,
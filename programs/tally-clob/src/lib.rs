@@ -1,9 +1,12 @@
 use anchor_lang::prelude::*;
 use instructions::*;
 use state::*;
+use pricing::*;
 
 pub mod instructions;
 pub mod errors;
+pub mod events;
+pub mod pricing;
 pub mod state;
 pub mod utils;
 
@@ -11,20 +14,153 @@ declare_id!("DnPqvbGTENrvc5jiyjqdmhpn6K26cFexTgKiUxGxgcnN");
 
 #[program]
 pub mod tally_clob {
-    use crate::errors::TallyClobErrors;
-
-    use self::utils::{is_owner, is_wallet_manager};
+    use self::utils::{is_custodial, is_owner, is_upgrade_authority, is_user_owner, is_wallet_manager, not_paused};
 
     use super::*;
 
+    pub fn init_config(
+        ctx: Context<InitConfig>,
+        init_config: InitProgramConfig
+    ) -> Result<()> {
+        is_upgrade_authority(ctx.accounts.signer.key(), &ctx.accounts.program_data)?;
+
+        instructions::init_config(ctx, init_config)
+    }
+
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        update: ProgramConfigUpdate
+    ) -> Result<()> {
+        is_owner(ctx.accounts.signer.key(), &ctx.accounts.config)?;
+
+        instructions::update_config(ctx, update)
+    }
+
+    pub fn transfer_ownership(
+        ctx: Context<UpdateConfig>,
+        new_owner: Pubkey
+    ) -> Result<()> {
+        is_owner(ctx.accounts.signer.key(), &ctx.accounts.config)?;
+
+        instructions::transfer_ownership(ctx, new_owner)
+    }
+
+    pub fn init_vault(ctx: Context<InitVault>) -> Result<()> {
+        is_owner(ctx.accounts.signer.key(), &ctx.accounts.config)?;
+
+        instructions::init_vault(ctx)
+    }
+
+    pub fn init_fee_ledger(ctx: Context<InitFeeLedger>) -> Result<()> {
+        is_owner(ctx.accounts.signer.key(), &ctx.accounts.config)?;
+
+        instructions::init_fee_ledger(ctx)
+    }
+
+    pub fn set_fee_owner(
+        ctx: Context<SetFeeOwner>,
+        fee_owner: Pubkey
+    ) -> Result<()> {
+        ctx.accounts.authorized_user.has_role(ROLE_FEE_ADMIN, None)?;
+
+        instructions::set_fee_owner(ctx, fee_owner)
+    }
+
+    pub fn set_fee_schedule(
+        ctx: Context<SetFeeSchedule>,
+        fee_schedule: FeeSchedule
+    ) -> Result<()> {
+        ctx.accounts.authorized_user.has_role(ROLE_FEE_ADMIN, None)?;
+
+        instructions::set_fee_schedule(ctx, fee_schedule)
+    }
+
+    pub fn set_market_fee_schedule(
+        ctx: Context<SetMarketFeeSchedule>,
+        sub_market_id: Option<u64>,
+        fee_schedule: Option<FeeSchedule>
+    ) -> Result<()> {
+        ctx.accounts.authorized_user.has_role(ROLE_FEE_ADMIN, Some(ctx.accounts.market.key()))?;
+
+        instructions::set_market_fee_schedule(ctx, sub_market_id, fee_schedule)
+    }
+
+    pub fn set_fee_split(
+        ctx: Context<SetFeeSplit>,
+        fee_split: FeeSplit,
+        referral_tiers: Vec<ReferralTier>
+    ) -> Result<()> {
+        ctx.accounts.authorized_user.has_role(ROLE_FEE_ADMIN, None)?;
+
+        instructions::set_fee_split(ctx, fee_split, referral_tiers)
+    }
+
+    pub fn set_fee_tiers(
+        ctx: Context<SetFeeTiers>,
+        fee_tiers: Vec<FeeTier>
+    ) -> Result<()> {
+        ctx.accounts.authorized_user.has_role(ROLE_FEE_ADMIN, None)?;
+
+        instructions::set_fee_tiers(ctx, fee_tiers)
+    }
+
+    pub fn sweep_fees(ctx: Context<SweepFees>) -> Result<()> {
+        ctx.accounts.authorized_user.has_role(ROLE_FEE_ADMIN, None)?;
+
+        instructions::sweep_fees(ctx)
+    }
+
+    pub fn claim_fee_rewards(ctx: Context<ClaimFeeRewards>) -> Result<()> {
+        // the owner claims by signing, or through a wallet manager for custodial wallets
+        if ctx.accounts.signer.key() != ctx.accounts.user.owner {
+            is_wallet_manager(
+                ctx.accounts.signer.key(),
+                &ctx.accounts.config,
+                ctx.accounts.authorized_user.as_deref(),
+                None
+            )?;
+            is_custodial(&ctx.accounts.user)?;
+        }
+        not_paused(&ctx.accounts.config)?;
+
+        instructions::claim_fee_rewards(ctx)
+    }
+
+    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+        // the referrer claims by signing, or through a wallet manager for custodial wallets
+        if ctx.accounts.signer.key() != ctx.accounts.user.owner {
+            is_wallet_manager(
+                ctx.accounts.signer.key(),
+                &ctx.accounts.config,
+                ctx.accounts.authorized_user.as_deref(),
+                None
+            )?;
+            is_custodial(&ctx.accounts.user)?;
+        }
+        not_paused(&ctx.accounts.config)?;
+
+        instructions::claim_referral_rewards(ctx)
+    }
+
+    pub fn set_paused(
+        ctx: Context<SetPaused>,
+        paused: bool
+    ) -> Result<()> {
+        ctx.accounts.authorized_user.has_role(ROLE_PAUSER, None)?;
+
+        instructions::set_paused(ctx, paused)
+    }
+
     pub fn authorize_user(
         ctx: Context<AuthorizeUser>, 
-        authorized: bool,
+        roles: u8,
+        market: Option<Pubkey>,
+        expires_at: Option<i64>,
         user_key: Pubkey
     ) -> Result<()> {
-        is_owner(ctx.accounts.signer.key())?;
+        is_owner(ctx.accounts.signer.key(), &ctx.accounts.config)?;
 
-        instructions::authorize_user(ctx, authorized)
+        instructions::authorize_user(ctx, roles, market, expires_at)
 
     }
 
@@ -33,7 +169,7 @@ pub mod tally_clob {
         init_sub_markets: Vec<InitSubMarket>,
         market_key: Pubkey
     ) -> Result<()> {
-        require!(ctx.accounts.authorized_user.authorized, TallyClobErrors::NotAuthorized);
+        ctx.accounts.authorized_user.has_role(ROLE_MARKET_CREATOR, Some(ctx.accounts.market.key()))?;
 
         instructions::init_market(
             ctx,
@@ -45,25 +181,35 @@ pub mod tally_clob {
         ctx: Context<InitWallet>,
         user_key: Pubkey
     ) -> Result<()> {
-        is_wallet_manager(ctx.accounts.signer.key())?;
-        instructions::init_wallet(ctx)
+        is_wallet_manager(
+            ctx.accounts.signer.key(),
+            &ctx.accounts.config,
+            ctx.accounts.authorized_user.as_deref(),
+            None
+        )?;
+        instructions::init_wallet(ctx, user_key)
     }
 
-    pub fn add_to_balance(
-        ctx: Context<AddToBalance>,
+    pub fn deposit(
+        ctx: Context<Deposit>,
         amount: u128
     ) -> Result<()> {
-        is_wallet_manager(ctx.accounts.signer.key())?;
+        not_paused(&ctx.accounts.config)?;
 
-        instructions::add_to_balance(ctx, amount)
+        instructions::deposit(ctx, amount)
     }
 
-    
     pub fn add_to_unreedeemable(
         ctx: Context<AddToUnreedeemable>,
         amount: u128
     ) -> Result<()> {
-        is_wallet_manager(ctx.accounts.signer.key())?;
+        is_wallet_manager(
+            ctx.accounts.signer.key(),
+            &ctx.accounts.config,
+            ctx.accounts.authorized_user.as_deref(),
+            None
+        )?;
+        is_custodial(&ctx.accounts.user)?;
 
         instructions::add_to_unreedeemable(ctx, amount)
     }
@@ -72,7 +218,14 @@ pub mod tally_clob {
         ctx: Context<WithdrawFromBalance>,
         amount: u128
     ) -> Result<()> {
-        is_wallet_manager(ctx.accounts.signer.key())?;
+        is_wallet_manager(
+            ctx.accounts.signer.key(),
+            &ctx.accounts.config,
+            ctx.accounts.authorized_user.as_deref(),
+            None
+        )?;
+        is_custodial(&ctx.accounts.user)?;
+        not_paused(&ctx.accounts.config)?;
 
         instructions::withdraw_from_balance(ctx, amount)
     }
@@ -81,7 +234,14 @@ pub mod tally_clob {
         ctx: Context<FairLaunchOrder>,
         orders: Vec<Order>
     ) -> Result<()> {
-        is_wallet_manager(ctx.accounts.signer.key())?;
+        is_wallet_manager(
+            ctx.accounts.signer.key(),
+            &ctx.accounts.config,
+            ctx.accounts.authorized_user.as_deref(),
+            Some(ctx.accounts.market.key())
+        )?;
+        is_custodial(&ctx.accounts.user)?;
+        not_paused(&ctx.accounts.config)?;
         instructions::fair_launch_order(ctx, orders)
     }
 
@@ -90,7 +250,14 @@ pub mod tally_clob {
         ctx: Context<BulkBuyByPrice>,
         orders: Vec<Order>
     ) -> Result<()> {
-        is_wallet_manager(ctx.accounts.signer.key())?;
+        is_wallet_manager(
+            ctx.accounts.signer.key(),
+            &ctx.accounts.config,
+            ctx.accounts.authorized_user.as_deref(),
+            Some(ctx.accounts.market.key())
+        )?;
+        is_custodial(&ctx.accounts.user)?;
+        not_paused(&ctx.accounts.config)?;
         instructions::bulk_buy_by_price(ctx, orders)
     }
 
@@ -98,7 +265,14 @@ pub mod tally_clob {
         ctx: Context<BulkBuyByShares>,
         orders: Vec<Order>
     ) -> Result<()> {
-        is_wallet_manager(ctx.accounts.signer.key())?;
+        is_wallet_manager(
+            ctx.accounts.signer.key(),
+            &ctx.accounts.config,
+            ctx.accounts.authorized_user.as_deref(),
+            Some(ctx.accounts.market.key())
+        )?;
+        is_custodial(&ctx.accounts.user)?;
+        not_paused(&ctx.accounts.config)?;
         instructions::bulk_buy_by_shares(ctx, orders)
     }
 
@@ -106,7 +280,14 @@ pub mod tally_clob {
         ctx: Context<BulkSellByPrice>,
         orders: Vec<Order>
     ) -> Result<()> {
-        is_wallet_manager(ctx.accounts.signer.key())?;
+        is_wallet_manager(
+            ctx.accounts.signer.key(),
+            &ctx.accounts.config,
+            ctx.accounts.authorized_user.as_deref(),
+            Some(ctx.accounts.market.key())
+        )?;
+        is_custodial(&ctx.accounts.user)?;
+        not_paused(&ctx.accounts.config)?;
         instructions::bulk_sell_by_price(ctx, orders)
     }
 
@@ -114,7 +295,14 @@ pub mod tally_clob {
         ctx: Context<BulkSellByShares>,
         orders: Vec<Order>
     ) -> Result<()> {
-        is_wallet_manager(ctx.accounts.signer.key())?;
+        is_wallet_manager(
+            ctx.accounts.signer.key(),
+            &ctx.accounts.config,
+            ctx.accounts.authorized_user.as_deref(),
+            Some(ctx.accounts.market.key())
+        )?;
+        is_custodial(&ctx.accounts.user)?;
+        not_paused(&ctx.accounts.config)?;
         instructions::bulk_sell_by_shares(ctx, orders)
     }
 
@@ -123,16 +311,138 @@ pub mod tally_clob {
         sub_market_id: u64,
         choice_id: u64,
     ) -> Result<()> {
-        is_wallet_manager(ctx.accounts.signer.key())?;
+        ctx.accounts.authorized_user.has_role(ROLE_RESOLVER, Some(ctx.accounts.market.key()))?;
 
         instructions::resolve_market(ctx, sub_market_id, choice_id)
     }
 
+    pub fn dispute_resolution(
+        ctx: Context<DisputeResolution>,
+        sub_market_id: u64,
+    ) -> Result<()> {
+        ctx.accounts.authorized_user.has_role(ROLE_RESOLVER, Some(ctx.accounts.market.key()))?;
+
+        instructions::dispute_resolution(ctx, sub_market_id)
+    }
+
+    pub fn correct_resolution(
+        ctx: Context<CorrectResolution>,
+        sub_market_id: u64,
+        choice_id: u64,
+    ) -> Result<()> {
+        ctx.accounts.authorized_user.has_role(ROLE_ARBITER, Some(ctx.accounts.market.key()))?;
+
+        instructions::correct_resolution(ctx, sub_market_id, choice_id)
+    }
+
+    pub fn resolve_scalar(
+        ctx: Context<ResolveScalar>,
+        sub_market_id: u64,
+        value: i64,
+    ) -> Result<()> {
+        ctx.accounts.authorized_user.has_role(ROLE_RESOLVER, Some(ctx.accounts.market.key()))?;
+
+        instructions::resolve_scalar(ctx, sub_market_id, value)
+    }
+
+    pub fn correct_scalar_resolution(
+        ctx: Context<CorrectScalarResolution>,
+        sub_market_id: u64,
+        value: i64,
+    ) -> Result<()> {
+        ctx.accounts.authorized_user.has_role(ROLE_ARBITER, Some(ctx.accounts.market.key()))?;
+
+        instructions::correct_scalar_resolution(ctx, sub_market_id, value)
+    }
+
+    pub fn resolve_weighted(
+        ctx: Context<ResolveWeighted>,
+        sub_market_id: u64,
+        payout_numerators: Vec<u64>,
+    ) -> Result<()> {
+        ctx.accounts.authorized_user.has_role(ROLE_RESOLVER, Some(ctx.accounts.market.key()))?;
+
+        instructions::resolve_weighted(ctx, sub_market_id, payout_numerators)
+    }
+
+    pub fn correct_weighted_resolution(
+        ctx: Context<CorrectWeightedResolution>,
+        sub_market_id: u64,
+        payout_numerators: Vec<u64>,
+    ) -> Result<()> {
+        ctx.accounts.authorized_user.has_role(ROLE_ARBITER, Some(ctx.accounts.market.key()))?;
+
+        instructions::correct_weighted_resolution(ctx, sub_market_id, payout_numerators)
+    }
+
+    pub fn finalize_resolution(
+        ctx: Context<FinalizeResolution>,
+        sub_market_id: u64,
+    ) -> Result<()> {
+        instructions::finalize_resolution(ctx, sub_market_id)
+    }
+
+    pub fn set_oracle_config(
+        ctx: Context<SetOracleConfig>,
+        sub_market_id: u64,
+        oracle: Option<OracleConfig>,
+    ) -> Result<()> {
+        ctx.accounts.authorized_user.has_role(ROLE_MARKET_CREATOR, Some(ctx.accounts.market.key()))?;
+
+        instructions::set_oracle_config(ctx, sub_market_id, oracle)
+    }
+
+    pub fn resolve_from_oracle(
+        ctx: Context<ResolveFromOracle>,
+        sub_market_id: u64,
+    ) -> Result<()> {
+        instructions::resolve_from_oracle(ctx, sub_market_id)
+    }
+
+    pub fn set_mock_feed(
+        ctx: Context<SetMockFeed>,
+        _feed_key: Pubkey,
+        price: i64,
+        expo: i32,
+    ) -> Result<()> {
+        ctx.accounts.authorized_user.has_role(ROLE_RESOLVER, None)?;
+
+        instructions::set_mock_feed(ctx, price, expo)
+    }
+
+    pub fn propose_outcome(
+        ctx: Context<ProposeOutcome>,
+        sub_market_id: u64,
+        choice_id: u64,
+    ) -> Result<()> {
+        is_user_owner(ctx.accounts.signer.key(), &ctx.accounts.user)?;
+        not_paused(&ctx.accounts.config)?;
+
+        instructions::propose_outcome(ctx, sub_market_id, choice_id)
+    }
+
+    pub fn dispute_proposal(
+        ctx: Context<DisputeProposal>,
+        sub_market_id: u64,
+    ) -> Result<()> {
+        is_user_owner(ctx.accounts.signer.key(), &ctx.accounts.user)?;
+        not_paused(&ctx.accounts.config)?;
+
+        instructions::dispute_proposal(ctx, sub_market_id)
+    }
+
+    pub fn settle_proposal(
+        ctx: Context<SettleProposal>,
+        sub_market_id: u64,
+    ) -> Result<()> {
+        instructions::settle_proposal(ctx, sub_market_id)
+    }
+
     pub fn start_trading(
         ctx: Context<StartTrading>,
         sub_market_id: u64,
     ) -> Result<()> {
-        require!(ctx.accounts.authorized_user.authorized, TallyClobErrors::NotAuthorized);
+        ctx.accounts.authorized_user.has_role(ROLE_SCHEDULER, Some(ctx.accounts.market.key()))?;
 
         instructions::start_trading(ctx, sub_market_id)
     }
@@ -142,10 +452,233 @@ pub mod tally_clob {
         sub_market_id: u64,
         choice_id: u64
     ) -> Result<()> {
-        is_wallet_manager(ctx.accounts.signer.key())?;
+        is_wallet_manager(
+            ctx.accounts.signer.key(),
+            &ctx.accounts.config,
+            ctx.accounts.authorized_user.as_deref(),
+            Some(ctx.accounts.market.key())
+        )?;
+        is_custodial(&ctx.accounts.user)?;
+        not_paused(&ctx.accounts.config)?;
 
         instructions::claim_winnings(ctx, sub_market_id, choice_id)
     }
 
+    pub fn user_init_wallet(ctx: Context<UserInitWallet>) -> Result<()> {
+        instructions::user_init_wallet(ctx)
+    }
+
+    pub fn user_withdraw(
+        ctx: Context<UserWithdraw>,
+        amount: u128
+    ) -> Result<()> {
+        is_user_owner(ctx.accounts.signer.key(), &ctx.accounts.user)?;
+        not_paused(&ctx.accounts.config)?;
+
+        instructions::user_withdraw(ctx, amount)
+    }
+
+    pub fn user_fair_launch_order(
+        ctx: Context<UserFairLaunchOrder>,
+        orders: Vec<Order>
+    ) -> Result<()> {
+        is_user_owner(ctx.accounts.signer.key(), &ctx.accounts.user)?;
+        not_paused(&ctx.accounts.config)?;
+
+        instructions::user_fair_launch_order(ctx, orders)
+    }
+
+    pub fn user_bulk_buy_by_price<'info>(
+        ctx: Context<'_, '_, '_, 'info, UserBulkBuyByPrice<'info>>,
+        orders: Vec<Order>
+    ) -> Result<()> {
+        is_user_owner(ctx.accounts.signer.key(), &ctx.accounts.user)?;
+        not_paused(&ctx.accounts.config)?;
+
+        instructions::user_bulk_buy_by_price(ctx, orders)
+    }
+
+    pub fn user_bulk_buy_by_shares<'info>(
+        ctx: Context<'_, '_, '_, 'info, UserBulkBuyByShares<'info>>,
+        orders: Vec<Order>
+    ) -> Result<()> {
+        is_user_owner(ctx.accounts.signer.key(), &ctx.accounts.user)?;
+        not_paused(&ctx.accounts.config)?;
+
+        instructions::user_bulk_buy_by_shares(ctx, orders)
+    }
+
+    pub fn user_bulk_sell_by_price<'info>(
+        ctx: Context<'_, '_, '_, 'info, UserBulkSellByPrice<'info>>,
+        orders: Vec<Order>
+    ) -> Result<()> {
+        is_user_owner(ctx.accounts.signer.key(), &ctx.accounts.user)?;
+        not_paused(&ctx.accounts.config)?;
+
+        instructions::user_bulk_sell_by_price(ctx, orders)
+    }
+
+    pub fn user_bulk_sell_by_shares<'info>(
+        ctx: Context<'_, '_, '_, 'info, UserBulkSellByShares<'info>>,
+        orders: Vec<Order>
+    ) -> Result<()> {
+        is_user_owner(ctx.accounts.signer.key(), &ctx.accounts.user)?;
+        not_paused(&ctx.accounts.config)?;
+
+        instructions::user_bulk_sell_by_shares(ctx, orders)
+    }
+
+    pub fn user_claim_winnings<'info>(
+        ctx: Context<'_, '_, '_, 'info, UserClaimWinnings<'info>>,
+        sub_market_id: u64,
+        choice_id: u64
+    ) -> Result<()> {
+        is_user_owner(ctx.accounts.signer.key(), &ctx.accounts.user)?;
+        not_paused(&ctx.accounts.config)?;
+
+        instructions::user_claim_winnings(ctx, sub_market_id, choice_id)
+    }
+
+    pub fn init_order_book(
+        ctx: Context<InitOrderBook>,
+        sub_market_id: u64,
+        choice_id: u64
+    ) -> Result<()> {
+        ctx.accounts.authorized_user.has_role(ROLE_MARKET_CREATOR, Some(ctx.accounts.market.key()))?;
+
+        instructions::init_order_book(ctx, sub_market_id, choice_id)
+    }
+
+    pub fn place_order(
+        ctx: Context<PlaceOrder>,
+        sub_market_id: u64,
+        choice_id: u64,
+        side: Side,
+        price: u128,
+        shares: u128
+    ) -> Result<()> {
+        is_user_owner(ctx.accounts.signer.key(), &ctx.accounts.user)?;
+        not_paused(&ctx.accounts.config)?;
+
+        instructions::place_order(ctx, sub_market_id, choice_id, side, price, shares)
+    }
+
+    pub fn cancel_order(
+        ctx: Context<CancelOrder>,
+        side: Side,
+        order_id: u64
+    ) -> Result<()> {
+        is_user_owner(ctx.accounts.signer.key(), &ctx.accounts.user)?;
+
+        instructions::cancel_order(ctx, side, order_id)
+    }
+
+    pub fn consume_events(
+        ctx: Context<ConsumeEvents>,
+        limit: u16
+    ) -> Result<()> {
+        instructions::consume_events(ctx, limit)
+    }
+
+    pub fn init_share_mint(
+        ctx: Context<InitShareMint>,
+        sub_market_id: u64,
+        choice_id: u64
+    ) -> Result<()> {
+        ctx.accounts.authorized_user.has_role(ROLE_MARKET_CREATOR, Some(ctx.accounts.market.key()))?;
+
+        instructions::init_share_mint(ctx, sub_market_id, choice_id)
+    }
+
+    pub fn mint_complete_set(
+        ctx: Context<MintCompleteSet>,
+        sub_market_id: u64,
+        sets: u128
+    ) -> Result<()> {
+        is_user_owner(ctx.accounts.signer.key(), &ctx.accounts.user)?;
+        not_paused(&ctx.accounts.config)?;
+
+        instructions::mint_complete_set(ctx, sub_market_id, sets)
+    }
+
+    pub fn redeem_complete_set(
+        ctx: Context<RedeemCompleteSet>,
+        sub_market_id: u64,
+        sets: u128
+    ) -> Result<()> {
+        is_user_owner(ctx.accounts.signer.key(), &ctx.accounts.user)?;
+        not_paused(&ctx.accounts.config)?;
+
+        instructions::redeem_complete_set(ctx, sub_market_id, sets)
+    }
+
+    pub fn transfer_shares(
+        ctx: Context<TransferShares>,
+        sub_market_id: u64,
+        choice_id: u64,
+        shares: u128
+    ) -> Result<()> {
+        // the sender consents by signing, or through a wallet manager for custodial wallets
+        if ctx.accounts.signer.key() != ctx.accounts.sender.owner {
+            is_wallet_manager(
+                ctx.accounts.signer.key(),
+                &ctx.accounts.config,
+                ctx.accounts.authorized_user.as_deref(),
+                Some(ctx.accounts.market.key())
+            )?;
+            is_custodial(&ctx.accounts.sender)?;
+        }
+        not_paused(&ctx.accounts.config)?;
+
+        instructions::transfer_shares(ctx, sub_market_id, choice_id, shares)
+    }
+
+    pub fn void_sub_market(
+        ctx: Context<VoidSubMarket>,
+        sub_market_id: u64,
+        refund_policy: RefundPolicy
+    ) -> Result<()> {
+        ctx.accounts.authorized_user.has_role(ROLE_RESOLVER, Some(ctx.accounts.market.key()))?;
+
+        instructions::void_sub_market(ctx, sub_market_id, refund_policy)
+    }
+
+    pub fn claim_refund(
+        ctx: Context<ClaimRefund>,
+        sub_market_id: u64
+    ) -> Result<()> {
+        is_wallet_manager(
+            ctx.accounts.signer.key(),
+            &ctx.accounts.config,
+            ctx.accounts.authorized_user.as_deref(),
+            Some(ctx.accounts.market.key())
+        )?;
+        is_custodial(&ctx.accounts.user)?;
+        not_paused(&ctx.accounts.config)?;
+
+        instructions::claim_refund(ctx, sub_market_id)
+    }
+
+    pub fn user_claim_refund<'info>(
+        ctx: Context<'_, '_, '_, 'info, UserClaimRefund<'info>>,
+        sub_market_id: u64
+    ) -> Result<()> {
+        is_user_owner(ctx.accounts.signer.key(), &ctx.accounts.user)?;
+        not_paused(&ctx.accounts.config)?;
+
+        instructions::user_claim_refund(ctx, sub_market_id)
+    }
+
+    pub fn start_solvency_audit(
+        ctx: Context<StartSolvencyAudit>,
+        audit_id: u64
+    ) -> Result<()> {
+        instructions::start_solvency_audit(ctx, audit_id)
+    }
+
+    pub fn audit_solvency(ctx: Context<AuditSolvency>) -> Result<()> {
+        instructions::audit_solvency(ctx)
+    }
+
 }
 
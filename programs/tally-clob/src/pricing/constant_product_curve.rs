@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::{utils::{get_buy_price, get_fair_launch_pot_shares, get_sell_price, get_shares_to_buy, get_shares_to_sell, get_spot_price}, ChoiceMarket, InitSubMarket, PricingCurve, SHARE_PRICE_SCALE};
+
+/// N-outcome constant product market maker over every choice's pot_shares.
+pub struct ConstantProductCurve;
+
+fn get_pot_shares(choices: &[ChoiceMarket]) -> Vec<u128> {
+    choices.iter()
+        .map(|choice| choice.pot_shares)
+        .collect::<Vec<u128>>()
+}
+
+impl PricingCurve for ConstantProductCurve {
+    fn validate(&self, _init_sub_market: &InitSubMarket) -> Result<()> {
+        Ok(())
+    }
+
+    fn shares_to_buy(&self, choices: &[ChoiceMarket], index: usize, price: u128) -> Result<u128> {
+        get_shares_to_buy(&get_pot_shares(choices), index, price)
+    }
+
+    fn buy_price(&self, choices: &[ChoiceMarket], index: usize, shares: u128) -> Result<u128> {
+        get_buy_price(&get_pot_shares(choices), index, shares)
+    }
+
+    fn shares_to_sell(&self, choices: &[ChoiceMarket], index: usize, price: u128) -> Result<u128> {
+        get_shares_to_sell(&get_pot_shares(choices), index, price)
+    }
+
+    fn sell_price(&self, choices: &[ChoiceMarket], index: usize, shares: u128) -> Result<u128> {
+        get_sell_price(&get_pot_shares(choices), index, shares)
+    }
+
+    fn spot_price(&self, choices: &[ChoiceMarket], index: usize) -> Result<u128> {
+        get_spot_price(&get_pot_shares(choices), index, SHARE_PRICE_SCALE)
+    }
+
+    fn apply_buy(&self, choices: &mut [ChoiceMarket], index: usize, price: u128, shares: u128) -> Result<()> {
+        choices.iter_mut()
+            .for_each(|choice| choice.pot_shares += price);
+        choices[index].pot_shares -= shares;
+
+        Ok(())
+    }
+
+    fn apply_sell(&self, choices: &mut [ChoiceMarket], index: usize, price: u128, shares: u128) -> Result<()> {
+        choices.iter_mut()
+            .for_each(|choice| choice.pot_shares -= price);
+        choices[index].pot_shares += shares;
+
+        Ok(())
+    }
+
+    // reprice every choice by its share of the fair launch pot
+    fn seed_from_fair_launch(&self, choices: &mut [ChoiceMarket]) -> Result<()> {
+        let fair_launch_pots = choices.iter()
+            .map(|choice| choice.fair_launch_pot)
+            .collect::<Vec<u128>>();
+        let pot_shares = get_fair_launch_pot_shares(&fair_launch_pots)?;
+
+        choices.iter_mut()
+            .zip(pot_shares)
+            .for_each(|(choice, pot_shares)| choice.pot_shares = pot_shares);
+
+        Ok(())
+    }
+}
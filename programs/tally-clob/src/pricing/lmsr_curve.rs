@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::TallyClobErrors, utils::{get_lmsr_buy_price, get_lmsr_max_loss, get_lmsr_sell_price, get_lmsr_shares_to_buy, get_lmsr_shares_to_sell, get_lmsr_spot_price}, ChoiceMarket, InitSubMarket, PricingCurve, SHARE_PRICE_SCALE};
+
+/// Logarithmic market scoring rule over every choice's minted_shares, with
+/// liquidity parameter b.
+pub struct LmsrCurve {
+    pub liquidity_parameter: u128
+}
+
+fn get_minted_shares(choices: &[ChoiceMarket]) -> Vec<u128> {
+    choices.iter()
+        .map(|choice| choice.minted_shares)
+        .collect::<Vec<u128>>()
+}
+
+impl PricingCurve for LmsrCurve {
+    // the initial pot is the LMSR subsidy, so it has to cover b * ln(n)
+    fn validate(&self, init_sub_market: &InitSubMarket) -> Result<()> {
+        require!(self.liquidity_parameter > 0, TallyClobErrors::InvalidLiquidityParameter);
+
+        let max_loss = get_lmsr_max_loss(self.liquidity_parameter, init_sub_market.choice_ids.len())?;
+        require!(init_sub_market.init_pot >= max_loss, TallyClobErrors::InvalidLiquidityParameter);
+
+        Ok(())
+    }
+
+    fn shares_to_buy(&self, choices: &[ChoiceMarket], index: usize, price: u128) -> Result<u128> {
+        get_lmsr_shares_to_buy(&get_minted_shares(choices), index, price, self.liquidity_parameter)
+    }
+
+    fn buy_price(&self, choices: &[ChoiceMarket], index: usize, shares: u128) -> Result<u128> {
+        get_lmsr_buy_price(&get_minted_shares(choices), index, shares, self.liquidity_parameter)
+    }
+
+    fn shares_to_sell(&self, choices: &[ChoiceMarket], index: usize, price: u128) -> Result<u128> {
+        get_lmsr_shares_to_sell(&get_minted_shares(choices), index, price, self.liquidity_parameter)
+    }
+
+    fn sell_price(&self, choices: &[ChoiceMarket], index: usize, shares: u128) -> Result<u128> {
+        get_lmsr_sell_price(&get_minted_shares(choices), index, shares, self.liquidity_parameter)
+    }
+
+    fn spot_price(&self, choices: &[ChoiceMarket], index: usize) -> Result<u128> {
+        get_lmsr_spot_price(&get_minted_shares(choices), index, self.liquidity_parameter, SHARE_PRICE_SCALE)
+    }
+
+    // minted_shares is the whole state, and the sub market already moves it
+    fn apply_buy(&self, _choices: &mut [ChoiceMarket], _index: usize, _price: u128, _shares: u128) -> Result<()> {
+        Ok(())
+    }
+
+    fn apply_sell(&self, _choices: &mut [ChoiceMarket], _index: usize, _price: u128, _shares: u128) -> Result<()> {
+        Ok(())
+    }
+
+    // fair launch shares are minted_shares too, so they're already priced in
+    fn seed_from_fair_launch(&self, _choices: &mut [ChoiceMarket]) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+pub use pricing_curve::*;
+pub use constant_product_curve::*;
+pub use lmsr_curve::*;
+
+pub mod pricing_curve;
+pub mod constant_product_curve;
+pub mod lmsr_curve;
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{ChoiceMarket, ConstantProductCurve, InitSubMarket, LmsrCurve, U128_SIZE, U8_SIZE};
+
+/// Everything a sub market needs from its market maker. Curves only quote and
+/// move their own state; the sub market keeps usdc_pot and minted_shares in
+/// step with every fill itself.
+pub trait PricingCurve {
+    /// Rejects a sub market this curve can't price.
+    fn validate(&self, init_sub_market: &InitSubMarket) -> Result<()>;
+
+    /// Shares of the choice at `index` that `price` buys.
+    fn shares_to_buy(&self, choices: &[ChoiceMarket], index: usize, price: u128) -> Result<u128>;
+
+    /// Cheapest price that buys `shares` of the choice at `index`.
+    fn buy_price(&self, choices: &[ChoiceMarket], index: usize, shares: u128) -> Result<u128>;
+
+    /// Shares of the choice at `index` that have to be sold to be paid `price`.
+    fn shares_to_sell(&self, choices: &[ChoiceMarket], index: usize, price: u128) -> Result<u128>;
+
+    /// What selling `shares` of the choice at `index` pays.
+    fn sell_price(&self, choices: &[ChoiceMarket], index: usize, shares: u128) -> Result<u128>;
+
+    /// Marginal price of the choice at `index`, scaled by SHARE_PRICE_SCALE.
+    fn spot_price(&self, choices: &[ChoiceMarket], index: usize) -> Result<u128>;
+
+    fn apply_buy(&self, choices: &mut [ChoiceMarket], index: usize, price: u128, shares: u128) -> Result<()>;
+
+    fn apply_sell(&self, choices: &mut [ChoiceMarket], index: usize, price: u128, shares: u128) -> Result<()>;
+
+    /// Sets the curve up for trading once the fair launch pots change.
+    fn seed_from_fair_launch(&self, choices: &mut [ChoiceMarket]) -> Result<()>;
+}
+
+/// Which curve a sub market trades on, stored on the sub market.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PricingCurveKind {
+    ConstantProduct,
+    Lmsr { liquidity_parameter: u128 },
+}
+
+impl PricingCurveKind {
+    pub const SIZE: usize = U8_SIZE + U128_SIZE;
+
+    pub fn pricing_curve(&self) -> Box<dyn PricingCurve> {
+        match self {
+            PricingCurveKind::ConstantProduct => Box::new(ConstantProductCurve),
+            PricingCurveKind::Lmsr { liquidity_parameter } => Box::new(LmsrCurve {
+                liquidity_parameter: *liquidity_parameter
+            }),
+        }
+    }
+}
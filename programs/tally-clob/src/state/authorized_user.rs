@@ -1,16 +1,43 @@
 use anchor_lang::prelude::*;
 
-use crate::{BOOL_SIZE, DISCRIMINATOR_SIZE, U8_SIZE};
+use crate::{errors::TallyClobErrors, option_size, utils::current_timestamp, DISCRIMINATOR_SIZE, I64_SIZE, PUB_KEY_SIZE, U8_SIZE};
+
+pub const ROLE_MARKET_CREATOR: u8 = 1 << 0;
+pub const ROLE_RESOLVER: u8 = 1 << 1;
+pub const ROLE_SCHEDULER: u8 = 1 << 2;
+pub const ROLE_FEE_ADMIN: u8 = 1 << 3;
+pub const ROLE_WALLET_MANAGER: u8 = 1 << 4;
+pub const ROLE_PAUSER: u8 = 1 << 5;
+pub const ROLE_ARBITER: u8 = 1 << 6;
 
 #[account]
 pub struct AuthorizedUser {
     pub bump: u8,
-    pub authorized: bool
+    pub roles: u8,
+    pub market: Option<Pubkey>,
+    pub expires_at: Option<i64>,
 }
 
 impl AuthorizedUser {
     pub const SIZE: usize = DISCRIMINATOR_SIZE
     + U8_SIZE
-    + BOOL_SIZE;
+    + U8_SIZE
+    + option_size(PUB_KEY_SIZE)
+    + option_size(I64_SIZE);
+
+    /// Checks that every bit of `role` is granted, that the grant hasn't expired
+    /// and, for market scoped grants, that `market_key` is the scoped market.
+    pub fn has_role(&self, role: u8, market_key: Option<Pubkey>) -> Result<&Self> {
+        require!(role != 0 && self.roles & role == role, TallyClobErrors::NotAuthorized);
 
-}
\ No newline at end of file
+        if let Some(scoped_market) = self.market {
+            require!(market_key == Some(scoped_market), TallyClobErrors::NotAuthorized);
+        }
+
+        if let Some(expires_at) = self.expires_at {
+            require!(current_timestamp() < expires_at, TallyClobErrors::RoleExpired);
+        }
+
+        Ok(self)
+    }
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::{BOOL_SIZE, U128_SIZE, U64_SIZE};
+use crate::{U128_SIZE, U64_SIZE};
 
 use super::DISCRIMINATOR_SIZE;
 
@@ -18,6 +18,10 @@ pub struct SellOrderValues {
     pub fee_price: u128
 }
 
+/// A resolved choice is paid `payout_numerator / PAYOUT_DENOMINATOR` of a
+/// share's worth.
+pub const PAYOUT_DENOMINATOR: u64 = 1_000_000_000;
+
 #[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, PartialEq)]
 pub struct ChoiceMarket {
     pub id: u64,
@@ -25,7 +29,7 @@ pub struct ChoiceMarket {
     pub pot_shares: u128,
     pub minted_shares: u128,
     pub fair_launch_pot: u128,
-    pub winning_choice: bool
+    pub payout_numerator: u64
 }
 
 
@@ -34,16 +38,21 @@ impl ChoiceMarket {
     pub const SIZE: usize = DISCRIMINATOR_SIZE
     + U64_SIZE
     + (U128_SIZE * 4)
-    + BOOL_SIZE;
+    + U64_SIZE;
 
-    pub fn new(choice_id: &u64, init_pot: u128) -> Self {
+    pub fn new(choice_id: &u64, init_pot: u128, choice_count: usize) -> Self {
         ChoiceMarket {
             id: *choice_id,
             pot_shares: init_pot,
-            usdc_pot: init_pot / 2,
+            usdc_pot: init_pot / choice_count as u128,
             minted_shares: 0,
-            fair_launch_pot: init_pot / 2,
-            winning_choice: false
+            fair_launch_pot: init_pot / choice_count as u128,
+            payout_numerator: 0
         }
     }
+
+    /// Pays out something at resolution.
+    pub fn is_winning(&self) -> bool {
+        self.payout_numerator > 0
+    }
 }
\ No newline at end of file
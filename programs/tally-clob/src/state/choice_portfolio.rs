@@ -8,14 +8,17 @@ use crate::{errors::TallyClobErrors, BOOL_SIZE, DISCRIMINATOR_SIZE, U128_SIZE, U
 pub struct ChoicePortfolio {
     pub choice_id: u64,
     pub shares: u128,
-    pub claimed: bool
+    pub claimed: bool,
+    pub cost_basis: u128,
+    pub fair_launch_deposit: u128
 }
 
 impl ChoicePortfolio {
     pub const SIZE: usize = DISCRIMINATOR_SIZE
     + U64_SIZE
     + U128_SIZE
-    + BOOL_SIZE;
+    + BOOL_SIZE
+    + (U128_SIZE * 2);
 
     pub fn new(
         choice_id: u64
@@ -23,7 +26,9 @@ impl ChoicePortfolio {
         ChoicePortfolio {
             choice_id,
             shares: 0,
-            claimed: false
+            claimed: false,
+            cost_basis: 0,
+            fair_launch_deposit: 0
         }
     }
 
@@ -36,11 +41,41 @@ impl ChoicePortfolio {
         Ok(self)
     }
 
+    /// Records what was paid for shares already added. `cost_basis` includes
+    /// any fair launch deposit, which is also kept on its own.
+    pub fn add_cost(&mut self, cost_basis: u128, fair_launch_deposit: u128) -> Result<&Self> {
+        self.cost_basis
+            .add_assign(cost_basis);
+        self.fair_launch_deposit
+            .add_assign(fair_launch_deposit);
+
+        Ok(self)
+    }
+
+    /// The part of the cost basis and fair launch deposit that goes with
+    /// `shares` of this position.
+    pub fn get_cost_of(&self, shares: u128) -> (u128, u128) {
+        if self.shares == 0 || shares >= self.shares {
+            return (self.cost_basis, self.fair_launch_deposit)
+        }
+
+        (
+            self.cost_basis * shares / self.shares,
+            self.fair_launch_deposit * shares / self.shares
+        )
+    }
+
     pub fn withdraw_from_portfolio(&mut self, shares: u128) -> Result<&Self> {
         require!(self.shares >= shares, TallyClobErrors::NotEnoughSharesToSell);
 
+        let (cost_basis, fair_launch_deposit) = self.get_cost_of(shares);
+
         self.shares
             .sub_assign(shares);
+        self.cost_basis
+            .sub_assign(cost_basis);
+        self.fair_launch_deposit
+            .sub_assign(fair_launch_deposit);
         
         Ok(self)
     }
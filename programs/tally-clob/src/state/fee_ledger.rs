@@ -0,0 +1,36 @@
+use std::ops::AddAssign;
+
+use anchor_lang::prelude::*;
+
+use crate::{errors::TallyClobErrors, DISCRIMINATOR_SIZE, U128_SIZE, U8_SIZE};
+
+/// Protocol fees the vault holds for the fee owner, kept at balance
+/// precision. `sweep_fees` pays out what adds up to whole token units and
+/// leaves the rest to build up.
+#[account]
+pub struct FeeLedger {
+    pub bump: u8,
+    pub accrued: u128,
+    pub swept: u128,
+}
+
+impl FeeLedger {
+    pub const SIZE: usize = DISCRIMINATOR_SIZE
+    + U8_SIZE
+    + (U128_SIZE * 2);
+
+    pub fn accrue(&mut self, amount: u128) -> Result<&Self> {
+        self.accrued.add_assign(amount);
+
+        Ok(self)
+    }
+
+    pub fn sweep(&mut self, amount: u128) -> Result<&Self> {
+        require!(amount > 0 && amount <= self.accrued, TallyClobErrors::NoFeesToSweep);
+
+        self.accrued -= amount;
+        self.swept.add_assign(amount);
+
+        Ok(self)
+    }
+}
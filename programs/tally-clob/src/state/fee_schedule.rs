@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::TallyClobErrors, option_size, BPS_SCALE, U128_SIZE, U16_SIZE, U64_SIZE};
+
+/// Fee rates in basis points. The program config holds the default schedule,
+/// which a market and then a sub market can override. Every fee charged is
+/// kept between `min_fee` and `max_fee`, and never more than what it's taken
+/// from, except maker fees, which are a flat rate fixed when the order rests.
+///
+/// `trade_bps` is charged on curve trades, `taker_bps` and `maker_bps` on
+/// each side of an order book fill.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeSchedule {
+    pub trade_bps: u16,
+    pub taker_bps: u16,
+    pub maker_bps: u16,
+    pub withdrawal_bps: u16,
+    pub resolution_bps: u16,
+    pub fair_launch_bps: u16,
+    pub min_fee: u64,
+    pub max_fee: u64
+}
+
+impl FeeSchedule {
+    pub const SIZE: usize = (U16_SIZE * 6)
+    + (U64_SIZE * 2);
+
+    pub const OPTION_SIZE: usize = option_size(FeeSchedule::SIZE);
+
+    pub const DEFAULT: FeeSchedule = FeeSchedule {
+        trade_bps: 50,
+        taker_bps: 0,
+        maker_bps: 0,
+        withdrawal_bps: 500,
+        resolution_bps: 1_000,
+        fair_launch_bps: 0,
+        min_fee: 0,
+        max_fee: u64::MAX
+    };
+
+    pub fn validate(&self) -> Result<()> {
+        let rates = [
+            self.trade_bps,
+            self.taker_bps,
+            self.maker_bps,
+            self.withdrawal_bps,
+            self.resolution_bps,
+            self.fair_launch_bps
+        ];
+        require!(
+            rates.iter().all(|bps| *bps as u128 <= BPS_SCALE) && self.min_fee <= self.max_fee,
+            TallyClobErrors::InvalidFeeSchedule
+        );
+
+        Ok(())
+    }
+
+    fn get_fee(&self, amount: u128, bps: u16) -> u128 {
+        if bps == 0 { return 0 }
+
+        (amount * bps as u128 / BPS_SCALE)
+            .clamp(self.min_fee as u128, self.max_fee as u128)
+            .min(amount)
+    }
+
+    /// Takes `discount_bps` off every trade rate.
+    pub fn discounted(&self, discount_bps: u16) -> FeeSchedule {
+        let discount = |bps: u16| (bps as u128 * (BPS_SCALE - discount_bps as u128) / BPS_SCALE) as u16;
+
+        FeeSchedule {
+            trade_bps: discount(self.trade_bps),
+            taker_bps: discount(self.taker_bps),
+            maker_bps: discount(self.maker_bps),
+            ..*self
+        }
+    }
+
+    pub fn trade_fee(&self, amount: u128) -> u128 {
+        self.get_fee(amount, self.trade_bps)
+    }
+
+    pub fn taker_fee(&self, amount: u128) -> u128 {
+        self.get_fee(amount, self.taker_bps)
+    }
+
+    pub fn withdrawal_fee(&self, amount: u128) -> u128 {
+        self.get_fee(amount, self.withdrawal_bps)
+    }
+
+    pub fn resolution_fee(&self, amount: u128) -> u128 {
+        self.get_fee(amount, self.resolution_bps)
+    }
+
+    pub fn fair_launch_fee(&self, amount: u128) -> u128 {
+        self.get_fee(amount, self.fair_launch_bps)
+    }
+}
+
+/// How each trade and resolution fee is divided. The market creator gets
+/// their cut as claimable fee rewards, the trader's referrer as referral
+/// rewards, and the protocol keeps the rest. `referrer_bps` is the referral
+/// rate until the referrer reaches a `ReferralTier`.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeSplit {
+    pub creator_bps: u16,
+    pub referrer_bps: u16
+}
+
+impl FeeSplit {
+    pub const SIZE: usize = U16_SIZE * 2;
+
+    pub const DEFAULT: FeeSplit = FeeSplit {
+        creator_bps: 0,
+        referrer_bps: 0
+    };
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.creator_bps as u128 + self.referrer_bps as u128 <= BPS_SCALE,
+            TallyClobErrors::InvalidFeeSplit
+        );
+
+        Ok(())
+    }
+
+    pub fn creator_share(&self, fee: u128) -> u128 {
+        fee * self.creator_bps as u128 / BPS_SCALE
+    }
+}
+
+/// A referral rate for referrers whose referred users have traded at least
+/// `min_volume`.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReferralTier {
+    pub min_volume: u128,
+    pub referrer_bps: u16
+}
+
+impl ReferralTier {
+    pub const SIZE: usize = U128_SIZE + U16_SIZE;
+
+    pub const MAX_TIERS: usize = 4;
+}
+
+/// A discount off every trade rate for traders with at least `min_volume`
+/// over the last 30 days.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeTier {
+    pub min_volume: u128,
+    pub discount_bps: u16
+}
+
+impl FeeTier {
+    pub const SIZE: usize = U128_SIZE + U16_SIZE;
+
+    pub const MAX_TIERS: usize = 4;
+}
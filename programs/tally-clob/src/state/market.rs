@@ -1,13 +1,15 @@
 use anchor_lang::prelude::*;
 
-use crate::{errors::TallyClobErrors, BuyOrderValues, MarketStatus, SellOrderValues, SubMarket, U8_SIZE};
+use crate::{errors::TallyClobErrors, BuyOrderValues, FeeSchedule, MarketStatus, SellOrderValues, SubMarket, U8_SIZE};
 
-use super::{vec_size, DISCRIMINATOR_SIZE};
+use super::{option_size, vec_size, DISCRIMINATOR_SIZE, PUB_KEY_SIZE};
 
 #[account]
 pub struct Market {
     pub bump: u8,
-    pub sub_markets: Vec<SubMarket>
+    pub sub_markets: Vec<SubMarket>,
+    pub fee_schedule: Option<FeeSchedule>,
+    pub creator: Option<Pubkey>
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, PartialEq)]
@@ -15,7 +17,53 @@ pub struct Order {
     pub amount: u128,
     pub sub_market_id: u64,
     pub choice_id: u64,
-    pub requested_price_per_share: f64,
+    /// Buys: fewest shares, from the book and the curve together, to accept.
+    pub min_shares_out: Option<u128>,
+    /// Buys: most collateral to spend, fee included.
+    pub max_cost: Option<u128>,
+    /// Sells: least collateral to be credited, after fees.
+    pub min_proceeds: Option<u128>,
+    /// Sells: most shares to give up.
+    pub max_shares_in: Option<u128>,
+    /// Loosens each bound above by this many basis points.
+    pub tolerance_bps: Option<u16>,
+}
+
+pub const BPS_SCALE: u128 = 10_000;
+
+impl Order {
+    fn lower_bound(&self, bound: Option<u128>) -> u128 {
+        let bound = bound.unwrap_or(0);
+        let tolerance = bound * self.tolerance_bps.unwrap_or(0) as u128 / BPS_SCALE;
+
+        bound.saturating_sub(tolerance)
+    }
+
+    fn upper_bound(&self, bound: Option<u128>) -> u128 {
+        let bound = match bound {
+            Some(bound) => bound,
+            None => return u128::MAX,
+        };
+        let tolerance = bound * self.tolerance_bps.unwrap_or(0) as u128 / BPS_SCALE;
+
+        bound.saturating_add(tolerance)
+    }
+
+    pub fn check_buy_slippage(&self, shares_out: u128, cost: u128) -> Result<()> {
+        msg!("buy: shares_out: {}, cost: {}", shares_out, cost);
+        require!(shares_out >= self.lower_bound(self.min_shares_out), TallyClobErrors::SlippageExceeded);
+        require!(cost <= self.upper_bound(self.max_cost), TallyClobErrors::SlippageExceeded);
+
+        Ok(())
+    }
+
+    pub fn check_sell_slippage(&self, shares_in: u128, proceeds: u128) -> Result<()> {
+        msg!("sell: shares_in: {}, proceeds: {}", shares_in, proceeds);
+        require!(proceeds >= self.lower_bound(self.min_proceeds), TallyClobErrors::SlippageExceeded);
+        require!(shares_in <= self.upper_bound(self.max_shares_in), TallyClobErrors::SlippageExceeded);
+
+        Ok(())
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, PartialEq)]
@@ -27,12 +75,48 @@ pub struct FinalOrder {
     pub fee_price: u128
 }
 
+/// What a bulk trade adds up to: the fees the trader owes, the maker fees of
+/// the resting orders it filled and the collateral traded, fees included.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct TradeTotals {
+    pub fee: u128,
+    pub volume: u128,
+    pub maker_fee: u128
+}
+
 impl Market {
     pub const MARKET_MAX_LENGTH: usize = 10;
 
     pub const SIZE: usize = DISCRIMINATOR_SIZE
         + U8_SIZE
-        + vec_size(SubMarket::SIZE, Market::MARKET_MAX_LENGTH); //sub_markets
+        + vec_size(SubMarket::SIZE, Market::MARKET_MAX_LENGTH) //sub_markets
+        + FeeSchedule::OPTION_SIZE // fee_schedule
+        + option_size(PUB_KEY_SIZE); // creator
+
+    /// The sub market's own fee schedule, else the market's, else the
+    /// program's.
+    pub fn get_fee_schedule(&mut self, sub_market_id: &u64, program_fees: &FeeSchedule) -> Result<FeeSchedule> {
+        let market_fees = self.fee_schedule;
+        let sub_market_fees = self.get_sub_market(sub_market_id)?.fee_schedule;
+
+        Ok(sub_market_fees.or(market_fees).unwrap_or(*program_fees))
+    }
+
+    /// The fee schedule each order trades under, less the trader's volume
+    /// discount.
+    pub fn get_fee_schedules(&mut self, orders: &[Order], program_fees: &FeeSchedule, discount_bps: u16) -> Result<Vec<FeeSchedule>> {
+        orders.iter()
+            .map(|order| Ok(self.get_fee_schedule(&order.sub_market_id, program_fees)?.discounted(discount_bps)))
+            .collect()
+    }
+
+    /// Collateral the market still owes across its sub markets, including
+    /// what resolved and voided sub markets haven't paid out yet.
+    pub fn unclaimed_usdc_pots(&self) -> u128 {
+        self.sub_markets.iter()
+            .map(|sub_market| sub_market.unclaimed_collateral())
+            .sum()
+    }
 
     pub fn get_buying_periods(&mut self, orders: &Vec<Order>) -> Result<Vec<MarketStatus>> {
         let market_periods = orders.iter()
@@ -53,14 +137,17 @@ impl Market {
     pub fn bulk_buy_values_by_price(
         &mut self,
         orders: &Vec<Order>,
+        fee_schedules: &[FeeSchedule]
     ) -> Result<Vec<BuyOrderValues>> {
         let order_values = orders.iter()
-            .map(|order| self
+            .zip(fee_schedules.iter())
+            .map(|(order, fee_schedule)| {
+                self
                     .get_sub_market(&order.sub_market_id)
                     .unwrap()
-                    .get_buy_values_by_price(&order.choice_id, order.amount)
+                    .get_buy_values_by_price(&order.choice_id, order.amount, fee_schedule)
                     .unwrap()
-            ).collect::<Vec<BuyOrderValues>>();
+            }).collect::<Vec<BuyOrderValues>>();
 
         Ok(order_values)
     }
@@ -68,47 +155,55 @@ impl Market {
     pub fn bulk_buy_values_by_shares(
         &mut self,
         orders: &Vec<Order>,
+        fee_schedules: &[FeeSchedule]
     ) -> Result<Vec<BuyOrderValues>> {
         let order_values = orders.iter()
-            .map(|order| 
-            self
-                .get_sub_market(&order.sub_market_id)
-                .unwrap()
-                .get_buy_values_by_shares(&order.choice_id, order.amount)
-                .unwrap()
-        ).collect::<Vec<BuyOrderValues>>();
+            .zip(fee_schedules.iter())
+            .map(|(order, fee_schedule)| {
+                self
+                    .get_sub_market(&order.sub_market_id)
+                    .unwrap()
+                    .get_buy_values_by_shares(&order.choice_id, order.amount, fee_schedule)
+                    .unwrap()
+            }).collect::<Vec<BuyOrderValues>>();
 
-    Ok(order_values)
+        Ok(order_values)
     }
 
     pub fn bulk_sell_values_by_price(
         &mut self,
-        orders: &Vec<Order>
+        orders: &Vec<Order>,
+        fee_schedules: &[FeeSchedule]
     ) -> Result<Vec<SellOrderValues>> {
         let order_values = orders.iter()
-            .map(|order| self
-                .get_sub_market(&order.sub_market_id)
-                .unwrap()
-                .get_sell_values_by_price(&order.choice_id, order.amount)
-                .unwrap()
-            ).collect::<Vec<SellOrderValues>>();
+            .zip(fee_schedules.iter())
+            .map(|(order, fee_schedule)| {
+                self
+                    .get_sub_market(&order.sub_market_id)
+                    .unwrap()
+                    .get_sell_values_by_price(&order.choice_id, order.amount, fee_schedule)
+                    .unwrap()
+            }).collect::<Vec<SellOrderValues>>();
 
         Ok(order_values)
     }
 
     pub fn bulk_sell_values_by_shares(
         &mut self,
-        orders: &Vec<Order>
+        orders: &Vec<Order>,
+        fee_schedules: &[FeeSchedule]
     ) -> Result<Vec<SellOrderValues>> {
         let order_values = orders.iter()
-        .map(|order| self
-            .get_sub_market(&order.sub_market_id)
-            .unwrap()
-            .get_sell_values_by_shares(&order.choice_id, order.amount)
-            .unwrap()
-        ).collect::<Vec<SellOrderValues>>();
-
-    Ok(order_values)
+            .zip(fee_schedules.iter())
+            .map(|(order, fee_schedule)| {
+                self
+                    .get_sub_market(&order.sub_market_id)
+                    .unwrap()
+                    .get_sell_values_by_shares(&order.choice_id, order.amount, fee_schedule)
+                    .unwrap()
+            }).collect::<Vec<SellOrderValues>>();
+
+        Ok(order_values)
     }
 
     pub fn adjust_markets_after_buy(&mut self, final_orders: &Vec<FinalOrder>,) -> Result<()> {
@@ -36,6 +36,12 @@ impl MarketPortfolio {
                     &order.choice_id, 
                     order.shares
                 ).unwrap();
+                self.add_cost(
+                    &order.sub_market_id,
+                    &order.choice_id,
+                    order.price,
+                    0
+                ).unwrap();
             });
 
         Ok(self)
@@ -81,6 +87,15 @@ impl MarketPortfolio {
         Ok(self)
     }
 
+    pub fn add_cost(&mut self, sub_market_id: &u64, choice_id: &u64, cost_basis: u128, fair_launch_deposit: u128) -> Result<&Self> {
+        self
+            .get_sub_market_portfolio(sub_market_id)?
+            .get_choice_market_portfolio(choice_id)?
+            .add_cost(cost_basis, fair_launch_deposit)?;
+
+        Ok(self)
+    }
+
     pub fn get_choice_shares(&mut self, sub_market_id: &u64, choice_id: &u64) -> Result<u128> {
         let choice_shares = self
             .get_sub_market_portfolio(sub_market_id)?
@@ -93,10 +108,9 @@ impl MarketPortfolio {
     pub fn get_sub_market_portfolio(&mut self, sub_market_id: &u64) -> Result<&mut SubMarketPortfolio> {
         match self.sub_market_portfolio.binary_search_by_key(sub_market_id, |sub_market| sub_market.sub_market_id) {
             Ok(index) => Ok(&mut self.sub_market_portfolio[index]),
-            Err(_) =>  {
-             let new_sub_portfolio = &mut SubMarketPortfolio::new(*sub_market_id);
-                self.sub_market_portfolio.push(new_sub_portfolio.clone()); 
-                Ok(self.sub_market_portfolio.last_mut().unwrap())
+            Err(index) =>  {
+                self.sub_market_portfolio.insert(index, SubMarketPortfolio::new(*sub_market_id));
+                Ok(&mut self.sub_market_portfolio[index])
             }
         }
     }
@@ -7,6 +7,13 @@ pub use market_portfolio::*;
 pub use sub_market_portfolio::*;
 pub use choice_portfolio::*;
 pub use authorized_user::*;
+pub use program_config::*;
+pub use solvency_audit::*;
+pub use order_book::*;
+pub use oracle::*;
+pub use proposal::*;
+pub use fee_schedule::*;
+pub use fee_ledger::*;
 
 
 pub mod choice_market;
@@ -17,4 +24,11 @@ pub mod user;
 pub mod market_portfolio;
 pub mod sub_market_portfolio;
 pub mod choice_portfolio;
-pub mod authorized_user;
\ No newline at end of file
+pub mod authorized_user;
+pub mod program_config;
+pub mod solvency_audit;
+pub mod order_book;
+pub mod oracle;
+pub mod proposal;
+pub mod fee_schedule;
+pub mod fee_ledger;
\ No newline at end of file
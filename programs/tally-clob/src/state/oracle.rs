@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use crate::{option_size, DISCRIMINATOR_SIZE, ENUM_SIZE, I32_SIZE, I64_SIZE, PUB_KEY_SIZE, U64_SIZE, U8_SIZE};
+
+/// Layout of the price feed account an oracle config reads.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OracleKind {
+    Pyth,
+    Switchboard,
+    /// A `MockFeed` owned by this program, only in builds with the `test`
+    /// feature.
+    Mock
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    GreaterThan,
+    GreaterThanOrEqual,
+    LessThan,
+    LessThanOrEqual
+}
+
+impl Comparison {
+    pub fn holds(&self, left: i128, right: i128) -> bool {
+        match self {
+            Comparison::GreaterThan => left > right,
+            Comparison::GreaterThanOrEqual => left >= right,
+            Comparison::LessThan => left < right,
+            Comparison::LessThanOrEqual => left <= right,
+        }
+    }
+}
+
+/// Resolves a sub market from a price feed: once `resolve_at` has passed,
+/// the first price published in the `max_delay` seconds after it is
+/// compared against `threshold * 10^threshold_expo`.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OracleConfig {
+    pub kind: OracleKind,
+    pub feed: Pubkey,
+    pub threshold: i64,
+    pub threshold_expo: i32,
+    pub comparison: Comparison,
+    pub resolve_at: i64,
+    pub max_delay: i64,
+    pub choice_if_true: u64,
+    pub choice_if_false: u64
+}
+
+impl OracleConfig {
+    pub const SIZE: usize = option_size(
+        ENUM_SIZE // kind
+        + PUB_KEY_SIZE // feed
+        + I64_SIZE // threshold
+        + I32_SIZE // threshold_expo
+        + ENUM_SIZE // comparison
+        + (I64_SIZE * 2) // resolve_at, max_delay
+        + (U64_SIZE * 2) // choices
+    );
+}
+
+/// A feed price of `price * 10^expo`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OraclePrice {
+    pub price: i128,
+    pub expo: i32,
+    pub publish_time: i64
+}
+
+/// A price feed the program owns, so tests can resolve oracle markets
+/// without a live Pyth or Switchboard feed.
+#[account]
+pub struct MockFeed {
+    pub bump: u8,
+    pub authority: Pubkey,
+    pub price: i64,
+    pub expo: i32,
+    pub publish_time: i64
+}
+
+impl MockFeed {
+    pub const SIZE: usize = DISCRIMINATOR_SIZE
+    + U8_SIZE
+    + PUB_KEY_SIZE
+    + I64_SIZE
+    + I32_SIZE
+    + I64_SIZE;
+}
@@ -0,0 +1,261 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::TallyClobErrors, vec_size, BPS_SCALE, DISCRIMINATOR_SIZE, ENUM_SIZE, I64_SIZE, PUB_KEY_SIZE, U128_SIZE, U16_SIZE, U64_SIZE, U8_SIZE};
+
+/// Prices are quoted in collateral per whole share, with the same 9 decimals
+/// as balances and shares.
+pub const SHARE_PRICE_SCALE: u128 = 1_000_000_000;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Bid,
+    Ask
+}
+
+/// A limit order waiting on the book. Bids escrow the collateral for their
+/// remaining shares, asks escrow the shares themselves, so a fill never has to
+/// touch the maker's accounts. The maker rate is fixed when the order rests;
+/// bids escrow their maker fee in `fee_escrow`, asks pay it out of what they
+/// sell for.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, PartialEq)]
+pub struct RestingOrder {
+    pub order_id: u64,
+    pub owner: Pubkey,
+    pub owner_portfolio: Pubkey,
+    pub price: u128,
+    pub shares: u128,
+    pub escrow: u128,
+    pub placed_at: i64,
+    pub maker_bps: u16,
+    pub fee_escrow: u128
+}
+
+impl RestingOrder {
+    pub const SIZE: usize = U64_SIZE
+    + (PUB_KEY_SIZE * 2)
+    + (U128_SIZE * 3)
+    + I64_SIZE
+    + U16_SIZE
+    + U128_SIZE;
+}
+
+pub fn get_maker_fee(quote: u128, maker_bps: u16) -> u128 {
+    quote * maker_bps as u128 / BPS_SCALE
+}
+
+/// One match between an incoming order and a resting one. `quote` is the
+/// collateral that changes hands, `maker_fee` what the maker pays on it.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, PartialEq)]
+pub struct Fill {
+    pub maker: Pubkey,
+    pub maker_portfolio: Pubkey,
+    pub maker_order_id: u64,
+    pub price: u128,
+    pub shares: u128,
+    pub quote: u128,
+    pub maker_fee: u128
+}
+
+/// Bids and asks for one choice of a sub market. Each side is kept sorted
+/// best price first and, within a price, oldest order first.
+#[account]
+pub struct OrderBook {
+    pub bump: u8,
+    pub market: Pubkey,
+    pub sub_market_id: u64,
+    pub choice_id: u64,
+    pub next_order_id: u64,
+    pub bids: Vec<RestingOrder>,
+    pub asks: Vec<RestingOrder>
+}
+
+pub fn get_order_cost(price: u128, shares: u128) -> Result<u128> {
+    let cost = shares.checked_mul(price).ok_or(TallyClobErrors::InvalidOrderPrice)?;
+
+    Ok(cost / SHARE_PRICE_SCALE)
+}
+
+impl OrderBook {
+    pub const MAX_ORDERS: usize = 32;
+
+    pub const SIZE: usize = DISCRIMINATOR_SIZE
+    + U8_SIZE
+    + PUB_KEY_SIZE
+    + (U64_SIZE * 3)
+    + (vec_size(RestingOrder::SIZE, OrderBook::MAX_ORDERS) * 2);
+
+    fn get_side(&mut self, side: Side) -> &mut Vec<RestingOrder> {
+        match side {
+            Side::Bid => &mut self.bids,
+            Side::Ask => &mut self.asks,
+        }
+    }
+
+    /// Matches an incoming order against the other side of the book for as
+    /// long as the best resting price is at least as good as `limit_price`.
+    /// Fills happen at the resting order's price.
+    pub fn match_order(&mut self, taker: Pubkey, side: Side, limit_price: u128, shares: u128) -> Result<Vec<Fill>> {
+        self.match_order_up_to(taker, side, limit_price, shares, u128::MAX)
+    }
+
+    /// Same as `match_order`, but also stops once the fills have traded
+    /// `max_quote` worth of collateral.
+    pub fn match_order_up_to(&mut self, taker: Pubkey, side: Side, limit_price: u128, shares: u128, max_quote: u128) -> Result<Vec<Fill>> {
+        let resting_side = match side {
+            Side::Bid => Side::Ask,
+            Side::Ask => Side::Bid,
+        };
+        let book = self.get_side(resting_side);
+
+        let mut fills: Vec<Fill> = Vec::new();
+        let mut remaining = shares;
+        let mut remaining_quote = max_quote;
+        while remaining > 0 && remaining_quote > 0 && !book.is_empty() {
+            let best = &mut book[0];
+            let crosses = match side {
+                Side::Bid => best.price <= limit_price,
+                Side::Ask => best.price >= limit_price,
+            };
+            if !crosses { break }
+
+            require!(best.owner != taker, TallyClobErrors::SelfTrade);
+
+            let affordable_shares = remaining_quote
+                .saturating_mul(SHARE_PRICE_SCALE)
+                .checked_div(best.price)
+                .ok_or(TallyClobErrors::InvalidOrderPrice)?;
+            let fill_shares = remaining.min(best.shares).min(affordable_shares);
+            if fill_shares == 0 { break }
+            let quote = match resting_side {
+                // the last fill of a bid takes whatever rounding left in its escrow
+                Side::Bid if fill_shares == best.shares => best.escrow,
+                Side::Bid => get_order_cost(best.price, fill_shares)?,
+                Side::Ask => get_order_cost(best.price, fill_shares)?,
+            };
+            let maker_fee = match resting_side {
+                Side::Bid if fill_shares == best.shares => best.fee_escrow,
+                Side::Bid => best.fee_escrow * fill_shares / best.shares,
+                Side::Ask => get_maker_fee(quote, best.maker_bps),
+            };
+
+            fills.push(Fill {
+                maker: best.owner,
+                maker_portfolio: best.owner_portfolio,
+                maker_order_id: best.order_id,
+                price: best.price,
+                shares: fill_shares,
+                quote,
+                maker_fee
+            });
+
+            best.shares -= fill_shares;
+            if resting_side == Side::Bid {
+                best.escrow -= quote;
+                best.fee_escrow -= maker_fee;
+            }
+            remaining -= fill_shares;
+            remaining_quote = remaining_quote.saturating_sub(quote);
+
+            if best.shares == 0 {
+                book.remove(0);
+            }
+        }
+
+        Ok(fills)
+    }
+
+    /// Rests what's left of an order behind every order at the same or a
+    /// better price, giving it the next order id.
+    pub fn insert_order(&mut self, side: Side, mut order: RestingOrder) -> Result<u64> {
+        order.order_id = self.next_order_id;
+        self.next_order_id += 1;
+
+        let book = self.get_side(side);
+        require!(book.len() < OrderBook::MAX_ORDERS, TallyClobErrors::OrderBookFull);
+
+        let index = match side {
+            Side::Bid => book.partition_point(|resting| resting.price >= order.price),
+            Side::Ask => book.partition_point(|resting| resting.price <= order.price),
+        };
+        let order_id = order.order_id;
+        book.insert(index, order);
+
+        Ok(order_id)
+    }
+
+    pub fn remove_order(&mut self, side: Side, order_id: u64, owner: Pubkey) -> Result<RestingOrder> {
+        let book = self.get_side(side);
+
+        let index = book.iter()
+            .position(|order| order.order_id == order_id)
+            .ok_or(TallyClobErrors::OrderNotFound)?;
+        require!(book[index].owner == owner, TallyClobErrors::NotAuthorized);
+
+        Ok(book.remove(index))
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, PartialEq)]
+pub struct FillEvent {
+    pub seq_num: u64,
+    pub maker: Pubkey,
+    pub maker_portfolio: Pubkey,
+    pub maker_order_id: u64,
+    pub maker_side: Side,
+    pub taker: Pubkey,
+    pub price: u128,
+    pub shares: u128,
+    pub quote: u128,
+    pub timestamp: i64,
+    pub maker_fee: u128
+}
+
+impl FillEvent {
+    pub const SIZE: usize = (U64_SIZE * 2)
+    + (PUB_KEY_SIZE * 3)
+    + ENUM_SIZE
+    + (U128_SIZE * 3)
+    + I64_SIZE
+    + U128_SIZE;
+}
+
+/// Fills waiting to be settled on the maker's side. Takers settle as they
+/// trade; makers are paid out by `consume_events`, oldest fill first.
+#[account]
+pub struct EventQueue {
+    pub bump: u8,
+    pub order_book: Pubkey,
+    pub seq_num: u64,
+    pub events: Vec<FillEvent>
+}
+
+impl EventQueue {
+    pub const MAX_EVENTS: usize = 48;
+
+    pub const SIZE: usize = DISCRIMINATOR_SIZE
+    + U8_SIZE
+    + PUB_KEY_SIZE
+    + U64_SIZE
+    + vec_size(FillEvent::SIZE, EventQueue::MAX_EVENTS);
+
+    pub fn push_fill(&mut self, fill: &Fill, maker_side: Side, taker: Pubkey, timestamp: i64) -> Result<()> {
+        require!(self.events.len() < EventQueue::MAX_EVENTS, TallyClobErrors::EventQueueFull);
+
+        self.events.push(FillEvent {
+            seq_num: self.seq_num,
+            maker: fill.maker,
+            maker_portfolio: fill.maker_portfolio,
+            maker_order_id: fill.maker_order_id,
+            maker_side,
+            taker,
+            price: fill.price,
+            shares: fill.shares,
+            quote: fill.quote,
+            timestamp,
+            maker_fee: fill.maker_fee
+        });
+        self.seq_num += 1;
+
+        Ok(())
+    }
+}
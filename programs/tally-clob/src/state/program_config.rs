@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::TallyClobErrors, vec_size, FeeSchedule, FeeSplit, FeeTier, ReferralTier, BOOL_SIZE, BPS_SCALE, DISCRIMINATOR_SIZE, I64_SIZE, PUB_KEY_SIZE, U128_SIZE, U64_SIZE, U8_SIZE};
+
+#[account]
+pub struct ProgramConfig {
+    pub bump: u8,
+    pub owner: Pubkey,
+    pub wallet_manager: Pubkey,
+    pub clob_manager: Pubkey,
+    pub usdc_mint: Pubkey,
+    pub fee_owner: Pubkey,
+    pub paused: bool,
+    pub vault_bump: u8,
+    pub vault_initialized: bool,
+    pub user_count: u64,
+    pub market_count: u64,
+    pub order_book_count: u64,
+    pub proposal_count: u64,
+    pub dispute_period: i64,
+    pub proposal_bond: u128,
+    pub fee_schedule: FeeSchedule,
+    pub fee_split: FeeSplit,
+    pub referral_tiers: Vec<ReferralTier>,
+    pub fee_tiers: Vec<FeeTier>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct InitProgramConfig {
+    pub owner: Pubkey,
+    pub wallet_manager: Pubkey,
+    pub clob_manager: Pubkey,
+    pub fee_owner: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
+pub struct ProgramConfigUpdate {
+    pub wallet_manager: Option<Pubkey>,
+    pub clob_manager: Option<Pubkey>,
+    pub usdc_mint: Option<Pubkey>,
+    pub dispute_period: Option<i64>,
+    pub proposal_bond: Option<u128>,
+    pub fee_schedule: Option<FeeSchedule>,
+}
+
+impl ProgramConfig {
+    pub const SIZE: usize = DISCRIMINATOR_SIZE
+    + (U8_SIZE * 2)
+    + (PUB_KEY_SIZE * 5)
+    + (BOOL_SIZE * 2)
+    + (U64_SIZE * 4)
+    + I64_SIZE
+    + U128_SIZE
+    + FeeSchedule::SIZE
+    + FeeSplit::SIZE
+    + vec_size(ReferralTier::SIZE, ReferralTier::MAX_TIERS)
+    + vec_size(FeeTier::SIZE, FeeTier::MAX_TIERS);
+
+    pub const DEFAULT_DISPUTE_PERIOD: i64 = 60 * 60 * 24;
+    pub const DEFAULT_PROPOSAL_BOND: u128 = 100_000_000_000;
+
+    /// The referral rate of a referrer whose referred users have traded
+    /// `referred_volume`.
+    pub fn get_referrer_bps(&self, referred_volume: u128) -> u16 {
+        self.referral_tiers.iter()
+            .rev()
+            .find(|tier| tier.min_volume <= referred_volume)
+            .map_or(self.fee_split.referrer_bps, |tier| tier.referrer_bps)
+    }
+
+    /// Referrers are only paid, and their volume only counted, while there's
+    /// a referral rate to earn.
+    pub fn has_referral_program(&self) -> bool {
+        self.fee_split.referrer_bps > 0 || !self.referral_tiers.is_empty()
+    }
+
+    /// Tiers go from the lowest volume to the highest, and no referral rate
+    /// can leave the creator's cut without room.
+    pub fn set_fee_split(&mut self, fee_split: FeeSplit, referral_tiers: Vec<ReferralTier>) -> Result<()> {
+        require!(referral_tiers.len() <= ReferralTier::MAX_TIERS, TallyClobErrors::InvalidReferralTiers);
+        require!(
+            referral_tiers.windows(2).all(|tiers| tiers[0].min_volume < tiers[1].min_volume),
+            TallyClobErrors::InvalidReferralTiers
+        );
+
+        let referrer_bps = referral_tiers.iter()
+            .map(|tier| tier.referrer_bps)
+            .chain([fee_split.referrer_bps])
+            .max()
+            .unwrap_or(0);
+        FeeSplit { referrer_bps, ..fee_split }.validate()?;
+
+        self.fee_split = fee_split;
+        self.referral_tiers = referral_tiers;
+
+        Ok(())
+    }
+
+    /// The discount off trade fees for a trader with `trailing_volume` over
+    /// the last 30 days.
+    pub fn get_fee_discount(&self, trailing_volume: u128) -> u16 {
+        self.fee_tiers.iter()
+            .rev()
+            .find(|tier| tier.min_volume <= trailing_volume)
+            .map_or(0, |tier| tier.discount_bps)
+    }
+
+    /// Tiers go from the lowest volume to the highest.
+    pub fn set_fee_tiers(&mut self, fee_tiers: Vec<FeeTier>) -> Result<()> {
+        require!(fee_tiers.len() <= FeeTier::MAX_TIERS, TallyClobErrors::InvalidFeeTiers);
+        require!(
+            fee_tiers.windows(2).all(|tiers| tiers[0].min_volume < tiers[1].min_volume),
+            TallyClobErrors::InvalidFeeTiers
+        );
+        require!(
+            fee_tiers.iter().all(|tier| tier.discount_bps as u128 <= BPS_SCALE),
+            TallyClobErrors::InvalidFeeTiers
+        );
+
+        self.fee_tiers = fee_tiers;
+
+        Ok(())
+    }
+
+    pub fn apply_update(&mut self, update: &ProgramConfigUpdate) -> Result<&Self> {
+        if let Some(wallet_manager) = update.wallet_manager {
+            self.wallet_manager = wallet_manager;
+        }
+        if let Some(clob_manager) = update.clob_manager {
+            self.clob_manager = clob_manager;
+        }
+        if let Some(usdc_mint) = update.usdc_mint {
+            // the vault holds every balance in the current mint
+            require!(!self.vault_initialized, TallyClobErrors::CollateralMintLocked);
+            self.usdc_mint = usdc_mint;
+        }
+        if let Some(dispute_period) = update.dispute_period {
+            require!(dispute_period >= 0, TallyClobErrors::InvalidDisputePeriod);
+            self.dispute_period = dispute_period;
+        }
+        if let Some(proposal_bond) = update.proposal_bond {
+            self.proposal_bond = proposal_bond;
+        }
+        if let Some(fee_schedule) = update.fee_schedule {
+            fee_schedule.validate()?;
+            self.fee_schedule = fee_schedule;
+        }
+
+        Ok(self)
+    }
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use crate::{option_size, vec_size, SubMarket, BOOL_SIZE, DISCRIMINATOR_SIZE, PUB_KEY_SIZE, U128_SIZE, U64_SIZE, U8_SIZE};
+
+/// A bonded outcome proposal for one sub market. The proposer and any
+/// disputer each escrow `bond` out of their balance; whoever the final
+/// outcome sides with gets both back. `payouts` are the payouts proposed,
+/// which the final ones have to match exactly for the proposal to stand.
+#[account]
+pub struct Proposal {
+    pub bump: u8,
+    pub market: Pubkey,
+    pub sub_market_id: u64,
+    pub choice_id: u64,
+    pub payouts: Vec<u64>,
+    pub bond: u128,
+    pub proposer: Pubkey,
+    pub disputer: Option<Pubkey>,
+    pub settled: bool
+}
+
+impl Proposal {
+    pub const SIZE: usize = DISCRIMINATOR_SIZE
+    + U8_SIZE
+    + PUB_KEY_SIZE
+    + (U64_SIZE * 2)
+    + vec_size(U64_SIZE, SubMarket::MAX_CHOICES)
+    + U128_SIZE
+    + PUB_KEY_SIZE
+    + option_size(PUB_KEY_SIZE)
+    + BOOL_SIZE;
+}
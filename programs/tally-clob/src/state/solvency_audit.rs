@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::TallyClobErrors, EventQueue, Market, OrderBook, Proposal, Side, User, BOOL_SIZE, DISCRIMINATOR_SIZE, I64_SIZE, PUB_KEY_SIZE, U128_SIZE, U64_SIZE, U8_SIZE};
+
+/// Running totals for a solvency audit. `User`, `Market`, `OrderBook`,
+/// `EventQueue` and `Proposal` accounts are fed in over as many transactions
+/// as needed, each kind in strictly increasing key order so nothing can be
+/// counted twice. Fees accrued but not yet swept are owed to the fee owner and
+/// count as liabilities too.
+#[account]
+pub struct SolvencyAudit {
+    pub bump: u8,
+    pub audit_id: u64,
+    pub auditor: Pubkey,
+    pub started_at: i64,
+    pub user_count: u64,
+    pub market_count: u64,
+    pub order_book_count: u64,
+    pub proposal_count: u64,
+    pub users_audited: u64,
+    pub markets_audited: u64,
+    pub order_books_audited: u64,
+    pub event_queues_audited: u64,
+    pub proposals_audited: u64,
+    pub last_user: Pubkey,
+    pub last_market: Pubkey,
+    pub last_order_book: Pubkey,
+    pub last_event_queue: Pubkey,
+    pub last_proposal: Pubkey,
+    pub user_liabilities: u128,
+    pub market_liabilities: u128,
+    pub escrow_liabilities: u128,
+    pub bond_liabilities: u128,
+    pub fee_liabilities: u128,
+    pub vault_balance: u128,
+    pub finalized: bool,
+    pub solvent: bool,
+}
+
+impl SolvencyAudit {
+    pub const SIZE: usize = DISCRIMINATOR_SIZE
+    + U8_SIZE
+    + (U64_SIZE * 10)
+    + (PUB_KEY_SIZE * 6)
+    + I64_SIZE
+    + (U128_SIZE * 6)
+    + (BOOL_SIZE * 2);
+
+    pub fn add_user(&mut self, key: Pubkey, user: &User) -> Result<&Self> {
+        require!(key > self.last_user, TallyClobErrors::InvalidAuditAccount);
+        require!(self.users_audited < self.user_count, TallyClobErrors::InvalidAuditAccount);
+
+        self.last_user = key;
+        self.users_audited += 1;
+        self.user_liabilities += user.balance + user.unreedemable_balance + user.fee_rewards + user.referral_rewards;
+
+        Ok(self)
+    }
+
+    pub fn add_market(&mut self, key: Pubkey, market: &Market) -> Result<&Self> {
+        require!(key > self.last_market, TallyClobErrors::InvalidAuditAccount);
+        require!(self.markets_audited < self.market_count, TallyClobErrors::InvalidAuditAccount);
+
+        self.last_market = key;
+        self.markets_audited += 1;
+        self.market_liabilities += market.unclaimed_usdc_pots();
+
+        Ok(self)
+    }
+
+    /// Resting bids hold their collateral and maker fee in escrow.
+    pub fn add_order_book(&mut self, key: Pubkey, order_book: &OrderBook) -> Result<&Self> {
+        require!(key > self.last_order_book, TallyClobErrors::InvalidAuditAccount);
+        require!(self.order_books_audited < self.order_book_count, TallyClobErrors::InvalidAuditAccount);
+
+        self.last_order_book = key;
+        self.order_books_audited += 1;
+        self.escrow_liabilities += order_book.bids.iter()
+            .map(|order| order.escrow + order.fee_escrow)
+            .sum::<u128>();
+
+        Ok(self)
+    }
+
+    /// Ask makers are owed what their fills sold for until the fills are
+    /// consumed; bid makers are owed shares, not collateral.
+    pub fn add_event_queue(&mut self, key: Pubkey, event_queue: &EventQueue) -> Result<&Self> {
+        require!(key > self.last_event_queue, TallyClobErrors::InvalidAuditAccount);
+        require!(self.event_queues_audited < self.order_book_count, TallyClobErrors::InvalidAuditAccount);
+
+        self.last_event_queue = key;
+        self.event_queues_audited += 1;
+        self.escrow_liabilities += event_queue.events.iter()
+            .filter(|event| event.maker_side == Side::Ask)
+            .map(|event| event.quote - event.maker_fee)
+            .sum::<u128>();
+
+        Ok(self)
+    }
+
+    /// Bonds stay escrowed until the proposal is settled.
+    pub fn add_proposal(&mut self, key: Pubkey, proposal: &Proposal) -> Result<&Self> {
+        require!(key > self.last_proposal, TallyClobErrors::InvalidAuditAccount);
+        require!(self.proposals_audited < self.proposal_count, TallyClobErrors::InvalidAuditAccount);
+
+        self.last_proposal = key;
+        self.proposals_audited += 1;
+        if !proposal.settled {
+            self.bond_liabilities += match proposal.disputer {
+                Some(_) => proposal.bond * 2,
+                None => proposal.bond,
+            };
+        }
+
+        Ok(self)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.users_audited == self.user_count
+            && self.markets_audited == self.market_count
+            && self.order_books_audited == self.order_book_count
+            && self.event_queues_audited == self.order_book_count
+            && self.proposals_audited == self.proposal_count
+    }
+
+    pub fn total_liabilities(&self) -> u128 {
+        self.user_liabilities
+            + self.market_liabilities
+            + self.escrow_liabilities
+            + self.bond_liabilities
+            + self.fee_liabilities
+    }
+}
\ No newline at end of file
@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
 
-use crate::{errors::TallyClobErrors, utils::{clock, get_buy_price, get_sell_price}, BuyOrderValues, FinalOrder, SellOrderValues, U128_SIZE};
+use spl_math::uint::U256;
 
-use super::{vec_size, ChoiceMarket, DISCRIMINATOR_SIZE, U64_SIZE, I64_SIZE ,BOOL_SIZE};
+use crate::{errors::TallyClobErrors, utils::clock, BuyOrderValues, ChoicePortfolio, FeeSchedule, FinalOrder, OracleConfig, OracleKind, PricingCurve, PricingCurveKind, SellOrderValues, PAYOUT_DENOMINATOR, SHARE_PRICE_SCALE};
+
+use super::{option_size, vec_size, ChoiceMarket, DISCRIMINATOR_SIZE, ENUM_SIZE, U8_SIZE, U64_SIZE, U128_SIZE, I64_SIZE ,BOOL_SIZE};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
 pub struct InitSubMarket {
@@ -12,45 +14,94 @@ pub struct InitSubMarket {
     pub fair_launch_end: i64,
     pub trading_start: i64,
     pub trading_end: i64,
-    pub init_pot: u128
+    pub init_pot: u128,
+    pub curve: PricingCurveKind,
+    pub scalar: Option<ScalarRange>
+}
+
+/// Bounds of a scalar sub market. Its first choice is LONG and its second
+/// SHORT.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScalarRange {
+    pub lower: i64,
+    pub upper: i64
+}
+
+impl ScalarRange {
+    pub const SIZE: usize = option_size(I64_SIZE * 2);
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
 pub struct SubMarket {
     pub id: u64,
-    pub invariant: u128,
     pub choices: Vec<ChoiceMarket>,
     pub fair_launch_start: i64,
     pub fair_launch_end: i64,
     pub trading_start: i64,
     pub trading_end: i64,
-    pub resolved: bool
+    pub resolved: bool,
+    pub curve: PricingCurveKind,
+    pub complete_set_collateral: u128,
+    pub claimed_collateral: u128,
+    pub share_mint_count: u8,
+    pub init_pot: u128,
+    pub voided: bool,
+    pub refund_policy: RefundPolicy,
+    pub resolution: ResolutionStatus,
+    pub dispute_ends_at: i64,
+    pub oracle: Option<OracleConfig>,
+    pub scalar: Option<ScalarRange>,
+    pub fee_schedule: Option<FeeSchedule>
 }
 
 impl SubMarket {
 
+    pub const MAX_CHOICES: usize = 8;
+
     pub const SIZE: usize = DISCRIMINATOR_SIZE
         + U64_SIZE // id
-        + U128_SIZE // invaraint
-        + vec_size(ChoiceMarket::SIZE, 2) // choices
+        + vec_size(ChoiceMarket::SIZE, SubMarket::MAX_CHOICES) // choices
         + (I64_SIZE * 4) // timestamps
-        + BOOL_SIZE; //resolved
+        + BOOL_SIZE //resolved
+        + PricingCurveKind::SIZE // curve
+        + U128_SIZE // complete_set_collateral
+        + U128_SIZE // claimed_collateral
+        + U8_SIZE // share_mint_count
+        + U128_SIZE // init_pot
+        + BOOL_SIZE // voided
+        + ENUM_SIZE // refund_policy
+        + ENUM_SIZE // resolution
+        + I64_SIZE // dispute_ends_at
+        + OracleConfig::SIZE // oracle
+        + ScalarRange::SIZE // scalar
+        + FeeSchedule::OPTION_SIZE; // fee_schedule
 
 
     pub fn new(init_sub_market: &InitSubMarket) -> Self {
         let choices = init_sub_market.choice_ids.iter()
             .map(|choice_id| ChoiceMarket::new(
-                choice_id, init_sub_market.init_pot))
+                choice_id, init_sub_market.init_pot, init_sub_market.choice_ids.len()))
             .collect::<Vec<ChoiceMarket>>();
         SubMarket {
             id: init_sub_market.id,
-            invariant: init_sub_market.init_pot.pow(2),
             choices,
             fair_launch_start: init_sub_market.fair_launch_start,
             fair_launch_end: init_sub_market.fair_launch_end,
             trading_start: init_sub_market.trading_start,
             trading_end: init_sub_market.trading_end,
-            resolved: false
+            resolved: false,
+            curve: init_sub_market.curve,
+            complete_set_collateral: 0,
+            claimed_collateral: 0,
+            share_mint_count: 0,
+            init_pot: init_sub_market.init_pot,
+            voided: false,
+            refund_policy: RefundPolicy::CostBasis,
+            resolution: ResolutionStatus::Unresolved,
+            dispute_ends_at: 0,
+            oracle: None,
+            scalar: init_sub_market.scalar,
+            fee_schedule: None
         }
     }
 
@@ -71,40 +122,40 @@ impl SubMarket {
         Ok(MarketStatus::Closed)
     }
 
-    pub fn calculate_shares_to_buy(&mut self, mut choices: Vec<ChoiceMarket>, choice_id: &u64, price: u128,) -> Result<u128> {
-        let choice = match choices.binary_search_by_key(choice_id, |choice_market| choice_market.id) {
-            Ok(index) => Ok(&mut self.choices[index]),
+    /// Every choice has its own share mint.
+    pub fn is_tokenized(&self) -> bool {
+        self.share_mint_count as usize == self.choices.len()
+    }
+
+    pub fn pricing_curve(&self) -> Box<dyn PricingCurve> {
+        self.curve.pricing_curve()
+    }
+
+    pub fn get_choice_index(&self, choice_id: &u64) -> Result<usize> {
+        match self.choices.binary_search_by_key(choice_id, |choice_market| choice_market.id) {
+            Ok(index) => Ok(index),
             Err(_) => err!(TallyClobErrors::ChoiceNotFound),
-        }?;
-        
-        let losing_choices = choices.iter_mut()
-            .filter(|choice| &choice.id != choice_id)
-            .collect::<Vec<&mut ChoiceMarket>>();
+        }
+    }
 
-        
-        let old_pot_shares = choice.pot_shares + price;
-        let invariant = self.invariant;
-        let new_pot_shares = invariant / losing_choices[0].pot_shares;
-        msg!(&old_pot_shares.to_string());
-        msg!(&new_pot_shares.to_string());
-        Ok(old_pot_shares - new_pot_shares)
+    pub fn get_spot_price(&self, choice_id: &u64) -> Result<u128> {
+        let index = self.get_choice_index(choice_id)?;
+
+        self.pricing_curve().spot_price(&self.choices, index)
     }
 
-    pub fn get_buy_values_by_price(&mut self, choice_id: &u64, buy_price: u128) -> Result<BuyOrderValues> {
+    pub fn calculate_shares_to_buy(&self, choice_id: &u64, price: u128) -> Result<u128> {
+        let index = self.get_choice_index(choice_id)?;
 
-        let fee_price = buy_price / 200;
-        let new_buy_price = buy_price - fee_price;
+        self.pricing_curve().shares_to_buy(&self.choices, index, price)
+    }
 
-        // yes: 104.975, no: 104.975
-        let new_choices = self.choices
-            .iter()
-            .map(|choice|ChoiceMarket {
-                pot_shares: choice.pot_shares + new_buy_price,
-                ..choice.clone()
-            })
-            .collect::<Vec<ChoiceMarket>>();
+    pub fn get_buy_values_by_price(&mut self, choice_id: &u64, buy_price: u128, fee_schedule: &FeeSchedule) -> Result<BuyOrderValues> {
+
+        let fee_price = fee_schedule.trade_fee(buy_price);
+        let new_buy_price = buy_price - fee_price;
 
-        let shares_to_buy = self.calculate_shares_to_buy(new_choices, choice_id, new_buy_price)?;
+        let shares_to_buy = self.calculate_shares_to_buy(choice_id, new_buy_price)?;
 
         Ok(BuyOrderValues {
             fee_price,
@@ -113,21 +164,11 @@ impl SubMarket {
         })
     }
 
-    pub fn get_buy_values_by_shares(&self, choice_id: &u64, shares_to_buy: u128) -> Result<BuyOrderValues> {
-        let pot_shares = self.choices
-            .iter()
-            .map(|choice|{
-                if choice.id == *choice_id {
-                    choice.pot_shares - shares_to_buy
-                } else {
-                    choice.pot_shares
-                }
-            })
-            .collect::<Vec<u128>>();
-
+    pub fn get_buy_values_by_shares(&self, choice_id: &u64, shares_to_buy: u128, fee_schedule: &FeeSchedule) -> Result<BuyOrderValues> {
+        let index = self.get_choice_index(choice_id)?;
 
-        let buy_price = get_buy_price(pot_shares, self.invariant)?;
-        let fee_price = buy_price / 200; 
+        let buy_price = self.pricing_curve().buy_price(&self.choices, index, shares_to_buy)?;
+        let fee_price = fee_schedule.trade_fee(buy_price);
 
         Ok(BuyOrderValues{
             shares_to_buy,
@@ -136,51 +177,33 @@ impl SubMarket {
         })
     }
 
-    pub fn calculate_shares_to_sell(&mut self, choices: Vec<ChoiceMarket>, choice_id: &u64) -> Result<u128> {
-        let choice = match choices.binary_search_by_key(choice_id, |choice_market| choice_market.id) {
-            Ok(index) => Ok(&mut self.choices[index]),
-            Err(_) => err!(TallyClobErrors::ChoiceNotFound),
-        }?;
-
-        let total_pot_shares = choices.iter()
-            .map(|choice|choice.pot_shares)
-            .sum::<u128>();
-        let old_pot_shares = choice.pot_shares;
-        let invariant = self.invariant;
-        let new_pot_shares = invariant / (total_pot_shares  - choice.pot_shares);
-        Ok(new_pot_shares - old_pot_shares)
-     }
-
-    pub fn get_sell_values_by_price(&mut self, choice_id: &u64, sell_price: u128) -> Result<SellOrderValues> {
-        let fee_price = sell_price / 200;
-
-        let new_choices = self.choices
-            .iter()
-            .map(|choice|ChoiceMarket {
-                pot_shares: choice.pot_shares - sell_price,
-                ..choice.clone()
-            })
-            .collect::<Vec<ChoiceMarket>>();
+    pub fn calculate_shares_to_sell(&self, choice_id: &u64, price: u128) -> Result<u128> {
+        let index = self.get_choice_index(choice_id)?;
 
-        let shares_to_sell = self.calculate_shares_to_sell(new_choices, choice_id)?;
+        self.pricing_curve().shares_to_sell(&self.choices, index, price)
+    }
+
+    /// `sell_price` is what the curve pays out; the fee comes out of it when
+    /// the order settles, same as selling by shares.
+    pub fn get_sell_values_by_price(&mut self, choice_id: &u64, sell_price: u128, fee_schedule: &FeeSchedule) -> Result<SellOrderValues> {
+        let fee_price = fee_schedule.trade_fee(sell_price);
+
+        let shares_to_sell = self.calculate_shares_to_sell(choice_id, sell_price)?;
 
         Ok(SellOrderValues {
             shares_to_sell,
-            sell_price: sell_price - fee_price,
+            sell_price,
             fee_price
         })
 
     }
 
-    pub fn get_sell_values_by_shares(&self, choice_id: &u64, shares_to_sell: u128) -> Result<SellOrderValues> {
-        let pot_shares = self.choices
-            .iter()
-            .map(|choice|{if choice.id == *choice_id {choice.pot_shares + shares_to_sell} else {choice.pot_shares}})
-            .collect::<Vec<u128>>();
+    pub fn get_sell_values_by_shares(&self, choice_id: &u64, shares_to_sell: u128, fee_schedule: &FeeSchedule) -> Result<SellOrderValues> {
+        let index = self.get_choice_index(choice_id)?;
 
-        let sell_price = get_sell_price(pot_shares, self.invariant)?;
+        let sell_price = self.pricing_curve().sell_price(&self.choices, index, shares_to_sell)?;
         msg!(&sell_price.to_string());
-        let fee_price = sell_price / 200;
+        let fee_price = fee_schedule.trade_fee(sell_price);
 
         Ok(SellOrderValues {
             shares_to_sell,
@@ -191,65 +214,290 @@ impl SubMarket {
 
 
     pub fn adjust_markets_after_buy(&mut self, final_order: &FinalOrder) -> Result<()> {
-        
-        let new_choices = self.choices
-            .iter()
-            .map(| choice|{
-                msg!(&choice.pot_shares.to_string());
-                ChoiceMarket {
-                pot_shares: choice.pot_shares + final_order.price,
-                ..choice.clone()
-                }
-            }
-            )
-            .collect::<Vec<ChoiceMarket>>();
 
-        let shares_to_buy = self.calculate_shares_to_buy(new_choices, &final_order.choice_id, final_order.price)?;
+        let shares_to_buy = self.calculate_shares_to_buy(&final_order.choice_id, final_order.price)?;
         msg!(&shares_to_buy.to_string());
 
-        require!(shares_to_buy == final_order.shares, TallyClobErrors::SharesNotEqual);
-        
+        // buying by shares rounds the price up, so the curve may give a little more than asked
+        require!(shares_to_buy >= final_order.shares, TallyClobErrors::SharesNotEqual);
 
-        self.choices
-            .iter_mut()
-            .for_each(|choice|{
-                choice.pot_shares += final_order.price;
-            });
+        let index = self.get_choice_index(&final_order.choice_id)?;
+        self.pricing_curve().apply_buy(&mut self.choices, index, final_order.price, final_order.shares)?;
 
-        self.get_choice(&final_order.choice_id)?.usdc_pot += final_order.price;
-        self.get_choice(&final_order.choice_id)?.pot_shares -= shares_to_buy;
-        self.get_choice(&final_order.choice_id)?.minted_shares += shares_to_buy;
+        self.choices[index].usdc_pot += final_order.price;
+        self.choices[index].minted_shares += final_order.shares;
 
         Ok(())
     }
 
     pub fn adjust_markets_after_sell(&mut self, final_order: &FinalOrder) -> Result<()> {
-        
-        // let new_choices = self.choices
-        //     .iter()
-        //     .map(|choice|ChoiceMarket {
-        //         pot_shares: choice.pot_shares - final_order.price + final_order.fee_price,
-        //         ..choice.clone()
-        //     })
-        //     .collect::<Vec<ChoiceMarket>>();
 
-        // let shares_to_sell = self.calculate_shares_to_sell(new_choices, &final_order.choice_id)?;
+        let index = self.get_choice_index(&final_order.choice_id)?;
+        self.pricing_curve().apply_sell(&mut self.choices, index, final_order.price, final_order.shares)?;
+
+        self.choices[index].usdc_pot -= final_order.price;
+        self.choices[index].minted_shares -= final_order.shares;
+
+        Ok(())
+    }
+
+    /// Mints `sets` shares of every choice for the same amount of collateral,
+    /// which is held next to the pots rather than in them.
+    pub fn mint_complete_sets(&mut self, sets: u128) -> Result<()> {
+        for choice in self.choices.iter_mut() {
+            choice.minted_shares += sets;
+        }
+        self.complete_set_collateral += sets;
 
-        // require!(shares_to_sell == final_order.shares, TallyClobErrors::SharesNotEqual);
+        Ok(())
+    }
 
-        self.choices
-            .iter_mut()
-            .for_each(|choice|{
-                choice.pot_shares -= final_order.price;
-            });
+    /// Burns `sets` shares of every choice and releases their collateral.
+    pub fn redeem_complete_sets(&mut self, sets: u128) -> Result<()> {
+        require!(sets <= self.complete_set_collateral, TallyClobErrors::NotEnoughSetCollateral);
 
-        self.get_choice(&final_order.choice_id)?.usdc_pot -= final_order.price;
-        self.get_choice(&final_order.choice_id)?.pot_shares += final_order.shares;
-        self.get_choice(&final_order.choice_id)?.minted_shares -= final_order.shares;
+        for choice in self.choices.iter_mut() {
+            choice.minted_shares = choice.minted_shares
+                .checked_sub(sets)
+                .ok_or(TallyClobErrors::NotEnoughSetCollateral)?;
+        }
+        self.complete_set_collateral -= sets;
 
         Ok(())
     }
 
+    /// Marks the sub market void. It counts as resolved from here on so
+    /// trading stops, but no choice wins and holders claim refunds instead.
+    /// Voiding is final straight away, there is no outcome to dispute, and it
+    /// also throws out an outcome that was proposed but isn't final yet.
+    pub fn void(&mut self, refund_policy: RefundPolicy) -> Result<()> {
+        require!(self.resolution != ResolutionStatus::Finalized, TallyClobErrors::MarketAlreadyResolved);
+
+        for choice in self.choices.iter_mut() {
+            choice.payout_numerator = 0;
+        }
+        self.resolved = true;
+        self.voided = true;
+        self.refund_policy = refund_policy;
+        self.resolution = ResolutionStatus::Finalized;
+
+        Ok(())
+    }
+
+    /// Sets or clears the feed the sub market resolves from. Both outcomes
+    /// have to be different choices of this sub market, and mock feeds are
+    /// only accepted in test builds.
+    pub fn set_oracle(&mut self, oracle: Option<OracleConfig>) -> Result<()> {
+        require!(!self.resolved, TallyClobErrors::MarketAlreadyResolved);
+
+        if let Some(oracle) = oracle {
+            self.get_choice_index(&oracle.choice_if_true)?;
+            self.get_choice_index(&oracle.choice_if_false)?;
+            require!(oracle.choice_if_true != oracle.choice_if_false, TallyClobErrors::InvalidOracleConfig);
+            require!(oracle.max_delay >= 0, TallyClobErrors::InvalidOracleConfig);
+            require!(oracle.kind != OracleKind::Mock || cfg!(feature = "test"), TallyClobErrors::MockOracleDisabled);
+        }
+        self.oracle = oracle;
+
+        Ok(())
+    }
+
+    /// Pays the whole pot out to `choice_id`.
+    pub fn get_categorical_payouts(&self, choice_id: &u64) -> Result<Vec<u64>> {
+        require!(self.scalar.is_none(), TallyClobErrors::ScalarSubMarket);
+        let index = self.get_choice_index(choice_id)?;
+
+        Ok((0..self.choices.len())
+            .map(|choice_index| if choice_index == index { PAYOUT_DENOMINATOR } else { 0 })
+            .collect())
+    }
+
+    /// LONG is paid (value - lower) / (upper - lower) and SHORT the rest,
+    /// with `value` clamped to the range.
+    pub fn get_scalar_payouts(&self, value: i64) -> Result<Vec<u64>> {
+        let range = self.scalar.ok_or(TallyClobErrors::NotScalarSubMarket)?;
+        let value = value.clamp(range.lower, range.upper);
+
+        let long = (value as i128 - range.lower as i128) * PAYOUT_DENOMINATOR as i128
+            / (range.upper as i128 - range.lower as i128);
+        let long = long as u64;
+
+        Ok(vec![long, PAYOUT_DENOMINATOR - long])
+    }
+
+    /// Payouts split a whole share's worth across every choice, so a dead
+    /// heat between two choices is half of `PAYOUT_DENOMINATOR` each.
+    pub fn get_weighted_payouts(&self, payouts: &[u64]) -> Result<Vec<u64>> {
+        require!(self.scalar.is_none(), TallyClobErrors::ScalarSubMarket);
+
+        Ok(payouts.to_vec())
+    }
+
+    fn set_payouts(&mut self, payouts: &[u64]) -> Result<()> {
+        require!(payouts.len() == self.choices.len(), TallyClobErrors::InvalidPayouts);
+        require!(
+            payouts.iter().map(|payout| *payout as u128).sum::<u128>() == PAYOUT_DENOMINATOR as u128,
+            TallyClobErrors::InvalidPayouts
+        );
+
+        for (choice, payout) in self.choices.iter_mut().zip(payouts.iter()) {
+            choice.payout_numerator = *payout;
+        }
+
+        Ok(())
+    }
+
+    /// Stops trading and proposes `payouts` as the outcome. Nothing can be
+    /// claimed until the proposal is finalized.
+    pub fn propose_payouts(&mut self, payouts: &[u64], dispute_ends_at: i64) -> Result<()> {
+        require!(!self.resolved, TallyClobErrors::MarketAlreadyResolved);
+
+        self.set_payouts(payouts)?;
+        self.resolved = true;
+        self.resolution = ResolutionStatus::Proposed;
+        self.dispute_ends_at = dispute_ends_at;
+
+        Ok(())
+    }
+
+    pub fn propose_resolution(&mut self, choice_id: &u64, dispute_ends_at: i64) -> Result<()> {
+        let payouts = self.get_categorical_payouts(choice_id)?;
+
+        self.propose_payouts(&payouts, dispute_ends_at)
+    }
+
+    /// Holds a proposed outcome until an arbiter corrects or confirms it.
+    pub fn dispute_resolution(&mut self) -> Result<()> {
+        require!(self.resolution == ResolutionStatus::Proposed, TallyClobErrors::InvalidResolutionStatus);
+        require!(clock::current_timestamp() < self.dispute_ends_at, TallyClobErrors::DisputePeriodOver);
+
+        self.resolution = ResolutionStatus::Disputed;
+
+        Ok(())
+    }
+
+    /// Finalizes a proposal nobody disputed once its dispute period is over.
+    pub fn finalize_resolution(&mut self) -> Result<()> {
+        require!(self.resolution == ResolutionStatus::Proposed, TallyClobErrors::InvalidResolutionStatus);
+        require!(clock::current_timestamp() >= self.dispute_ends_at, TallyClobErrors::InDisputePeriod);
+
+        self.resolution = ResolutionStatus::Finalized;
+
+        Ok(())
+    }
+
+    /// Settles a proposed or disputed outcome on `payouts`, which may be the
+    /// proposed ones, without waiting out the dispute period.
+    pub fn correct_payouts(&mut self, payouts: &[u64]) -> Result<()> {
+        require!(
+            [ResolutionStatus::Proposed, ResolutionStatus::Disputed].contains(&self.resolution),
+            TallyClobErrors::InvalidResolutionStatus
+        );
+
+        self.set_payouts(payouts)?;
+        self.resolution = ResolutionStatus::Finalized;
+
+        Ok(())
+    }
+
+    pub fn correct_resolution(&mut self, choice_id: &u64) -> Result<()> {
+        let payouts = self.get_categorical_payouts(choice_id)?;
+
+        self.correct_payouts(&payouts)
+    }
+
+    pub fn get_payouts(&self) -> Vec<u64> {
+        self.choices.iter().map(|choice| choice.payout_numerator).collect()
+    }
+
+    /// Takes the resolution fee on what every pot loses at resolution once
+    /// the outcome is final. In a categorical sub market that's every losing
+    /// pot.
+    pub fn take_resolution_fee(&mut self, fee_schedule: &FeeSchedule) -> Result<u128> {
+        require!(self.resolution == ResolutionStatus::Finalized, TallyClobErrors::InvalidResolutionStatus);
+
+        Ok(self.choices.iter_mut()
+            .map(|choice| {
+                let losing_share = (PAYOUT_DENOMINATOR - choice.payout_numerator) as u128;
+                let fee = fee_schedule.resolution_fee(choice.usdc_pot * losing_share / PAYOUT_DENOMINATOR as u128);
+                choice.usdc_pot -= fee;
+                fee
+            })
+            .sum::<u128>())
+    }
+
+    /// What `shares` of `choice_id` are paid out of the whole pot, complete
+    /// set collateral included. Each outstanding share is weighted by its
+    /// choice's payout numerator.
+    pub fn get_payout(&self, choice_id: &u64, shares: u128) -> Result<u128> {
+        let choice = &self.choices[self.get_choice_index(choice_id)?];
+
+        let total_pot = self.choices.iter().map(|choice| choice.usdc_pot).sum::<u128>()
+            + self.complete_set_collateral;
+        let total_weight = self.choices.iter()
+            .map(|choice| U256::from(choice.minted_shares) * U256::from(choice.payout_numerator))
+            .fold(U256::zero(), |sum, weight| sum + weight);
+        require!(!total_weight.is_zero(), TallyClobErrors::NotWinningChoice);
+
+        let payout = U256::from(total_pot) * U256::from(shares) * U256::from(choice.payout_numerator) / total_weight;
+
+        Ok(payout.as_u128())
+    }
+
+    /// Price per share that `RefundPolicy::ProRata` pays for the shares of a
+    /// choice that didn't come from the fair launch: what was traded into
+    /// the choice's pot after the fair launch, plus its slice of the complete
+    /// set collateral, over those shares. Never more than a whole share.
+    fn get_pro_rata_price(&self, choice_id: &u64) -> Result<u128> {
+        let choice = &self.choices[self.get_choice_index(choice_id)?];
+        let choice_count = self.choices.len() as u128;
+
+        // the fair launch pot is the initial pot's share plus every deposit
+        let fair_launch_deposits = choice.fair_launch_pot.saturating_sub(self.init_pot / choice_count);
+        let pot = (choice.usdc_pot + self.complete_set_collateral / choice_count)
+            .saturating_sub(choice.fair_launch_pot);
+        let shares = choice.minted_shares.saturating_sub(fair_launch_deposits);
+        if shares == 0 { return Ok(0) }
+
+        Ok((pot * SHARE_PRICE_SCALE / shares).min(SHARE_PRICE_SCALE))
+    }
+
+    /// What a position in a voided sub market gets back. Fair launch
+    /// deposits always come back exactly; the rest follows the refund policy.
+    pub fn get_refund(&self, choice_portfolio: &ChoicePortfolio) -> Result<u128> {
+        require!(self.voided, TallyClobErrors::MarketNotVoided);
+
+        let fair_launch_deposit = choice_portfolio.fair_launch_deposit.min(choice_portfolio.shares);
+        let trading_refund = match self.refund_policy {
+            RefundPolicy::CostBasis => choice_portfolio.cost_basis.saturating_sub(fair_launch_deposit),
+            RefundPolicy::ProRata => {
+                let trading_shares = choice_portfolio.shares - fair_launch_deposit;
+                trading_shares * self.get_pro_rata_price(&choice_portfolio.choice_id)? / SHARE_PRICE_SCALE
+            }
+        };
+
+        Ok(fair_launch_deposit + trading_refund)
+    }
+
+    /// Pots aren't drawn down by claims, since every payout is worked out
+    /// against the whole pot, so what has been paid out is kept here.
+    pub fn record_claim(&mut self, amount: u128) -> Result<()> {
+        self.claimed_collateral += amount;
+
+        Ok(())
+    }
+
+    /// Collateral the sub market still owes its holders: every pot and the
+    /// complete set collateral, less what has been claimed out of them.
+    pub fn unclaimed_collateral(&self) -> u128 {
+        (self.complete_set_collateral + self.choices.iter().map(|choice| choice.usdc_pot).sum::<u128>())
+            .saturating_sub(self.claimed_collateral)
+    }
+
+    pub fn seed_from_fair_launch(&mut self) -> Result<()> {
+        self.pricing_curve().seed_from_fair_launch(&mut self.choices)
+    }
+
     pub fn get_choice(&mut self, choice_id: &u64) -> Result<&mut ChoiceMarket> {
         match self.choices.binary_search_by_key(choice_id, |choice_market| choice_market.id) {
             Ok(index) => Ok(&mut self.choices[index]),
@@ -259,6 +507,25 @@ impl SubMarket {
 
 }
 
+/// Where a sub market is in its resolution. Outcomes are proposed first and
+/// only become claimable once finalized.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolutionStatus {
+    Unresolved,
+    Proposed,
+    Disputed,
+    Finalized
+}
+
+/// How holders of a voided sub market are paid back.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefundPolicy {
+    /// What each holder paid for their position, fees aside.
+    CostBasis,
+    /// An even price per share out of each choice's pot.
+    ProRata
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone, PartialEq, Eq)]
 pub enum MarketStatus {
     Initializing,
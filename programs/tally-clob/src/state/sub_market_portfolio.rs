@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::{vec_size, ChoicePortfolio, DISCRIMINATOR_SIZE, U64_SIZE};
+use crate::{vec_size, ChoicePortfolio, SubMarket, DISCRIMINATOR_SIZE, U64_SIZE};
 
 
 #[derive(AnchorSerialize, AnchorDeserialize, Debug, Clone)]
@@ -14,7 +14,7 @@ impl SubMarketPortfolio {
 
     pub const SIZE: usize = DISCRIMINATOR_SIZE
     + U64_SIZE
-    + vec_size(ChoicePortfolio::SIZE, 2);
+    + vec_size(ChoicePortfolio::SIZE, SubMarket::MAX_CHOICES);
 
     pub fn new(id: u64) -> SubMarketPortfolio {
         let choice_portfolio: Vec<ChoicePortfolio> = Vec::new();
@@ -54,10 +54,10 @@ impl SubMarketPortfolio {
     pub fn get_choice_market_portfolio(&mut self, choice_id: &u64) -> Result<&mut ChoicePortfolio> {
         match self.choice_portfolio.binary_search_by_key(choice_id, |choice_portfolio| choice_portfolio.choice_id) {
             Ok(index) => Ok(&mut self.choice_portfolio[index]),
-            Err(_) => {
-                let new_choice_portfolio = &mut ChoicePortfolio::new(*choice_id);
-                self.choice_portfolio.push(new_choice_portfolio.clone());
-                Ok(self.choice_portfolio.last_mut().unwrap())
+            Err(index) => {
+                // keep the portfolio sorted so the binary search keeps working
+                self.choice_portfolio.insert(index, ChoicePortfolio::new(*choice_id));
+                Ok(&mut self.choice_portfolio[index])
             },
         }
     }
@@ -4,19 +4,104 @@ use anchor_lang::prelude::*;
 
 use crate::{errors::TallyClobErrors, U128_SIZE};
 
-use super::{DISCRIMINATOR_SIZE, U8_SIZE};
+use super::{option_size, BOOL_SIZE, DISCRIMINATOR_SIZE, I64_SIZE, PUB_KEY_SIZE, U8_SIZE};
+
+pub const VOLUME_DAYS: usize = 30;
+
+const SECONDS_PER_DAY: i64 = 60 * 60 * 24;
 
 #[account]
 pub struct User {
     pub bump: u8,
     pub balance: u128,
     pub unreedemable_balance: u128,
+    pub owner: Pubkey,
+    pub fee_rewards: u128,
+    pub referrer: Option<Pubkey>,
+    pub referral_rewards: u128,
+    pub referred_volume: u128,
+    pub daily_volume: [u128; VOLUME_DAYS],
+    pub volume_day: i64,
+    pub custodial: bool,
 }
 
 impl User {
     pub const SIZE: usize = DISCRIMINATOR_SIZE
     + U8_SIZE
-    + (U128_SIZE * 2);
+    + (U128_SIZE * 2)
+    + PUB_KEY_SIZE
+    + U128_SIZE
+    + option_size(PUB_KEY_SIZE)
+    + (U128_SIZE * 2)
+    + (U128_SIZE * VOLUME_DAYS)
+    + I64_SIZE
+    + BOOL_SIZE;
+
+    /// What the user traded over the last 30 days, today included.
+    pub fn get_trailing_volume(&self, now: i64) -> u128 {
+        let today = now / SECONDS_PER_DAY;
+        let stale_days = (today - self.volume_day).clamp(0, VOLUME_DAYS as i64);
+
+        (0..VOLUME_DAYS as i64 - stale_days)
+            .map(|age| self.daily_volume[(self.volume_day - age).rem_euclid(VOLUME_DAYS as i64) as usize])
+            .sum()
+    }
+
+    /// Adds to today's volume, first clearing the days that have fallen out
+    /// of the 30 day window.
+    pub fn record_volume(&mut self, volume: u128, now: i64) -> Result<&Self> {
+        let today = now / SECONDS_PER_DAY;
+        let elapsed_days = (today - self.volume_day).clamp(0, VOLUME_DAYS as i64);
+        for day in 1..=elapsed_days {
+            self.daily_volume[(self.volume_day + day).rem_euclid(VOLUME_DAYS as i64) as usize] = 0;
+        }
+        self.volume_day = self.volume_day.max(today);
+
+        self.daily_volume[self.volume_day.rem_euclid(VOLUME_DAYS as i64) as usize]
+            .add_assign(volume);
+
+        Ok(self)
+    }
+
+    pub fn add_fee_rewards(&mut self, amount: u128) -> Result<&Self> {
+        require!(amount > 0, TallyClobErrors::AmountToAddTooLow);
+
+        self.fee_rewards
+            .add_assign(amount);
+
+        Ok(self)
+    }
+
+    /// Counts a referred user's trade towards the referrer's tier and pays
+    /// them their cut of its fee.
+    pub fn add_referral(&mut self, volume: u128, reward: u128) -> Result<&Self> {
+        self.referred_volume.add_assign(volume);
+        self.referral_rewards.add_assign(reward);
+
+        Ok(self)
+    }
+
+    pub fn claim_referral_rewards(&mut self) -> Result<u128> {
+        let amount = self.referral_rewards;
+        require!(amount > 0, TallyClobErrors::NoReferralRewards);
+
+        self.referral_rewards = 0;
+        self.balance.add_assign(amount);
+
+        Ok(amount)
+    }
+
+    /// Moves every fee reward into the balance, where it can be traded or
+    /// withdrawn.
+    pub fn claim_fee_rewards(&mut self) -> Result<u128> {
+        let amount = self.fee_rewards;
+        require!(amount > 0, TallyClobErrors::NoFeeRewards);
+
+        self.fee_rewards = 0;
+        self.balance.add_assign(amount);
+
+        Ok(amount)
+    }
 
     pub fn add_to_balance(&mut self, amount: u128) -> Result<&Self> {
         require!(amount > 0, TallyClobErrors::AmountToAddTooLow);
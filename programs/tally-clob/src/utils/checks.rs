@@ -4,26 +4,58 @@ use std::hash::Hash;
 
 use anchor_lang::prelude::*;
 
-use crate::errors::TallyClobErrors;
+use crate::{errors::TallyClobErrors, AuthorizedUser, ProgramConfig, User, ROLE_WALLET_MANAGER};
 
-pub fn is_wallet_manager(signer_key: Pubkey) -> Result<()> {
-    let address = signer_key.to_string();
-    let manager_address = String::from("7rTBUSkc8PHPW3VwGiPB4EbwHWxoSvVpMmbnAqRiGwWx");
-    require!(address == manager_address, TallyClobErrors::NotAuthorized);
+/// The configured wallet manager always passes, anyone else needs a
+/// `ROLE_WALLET_MANAGER` grant (optionally scoped to `market_key`).
+pub fn is_wallet_manager(
+    signer_key: Pubkey,
+    config: &ProgramConfig,
+    authorized_user: Option<&AuthorizedUser>,
+    market_key: Option<Pubkey>
+) -> Result<()> {
+    if signer_key == config.wallet_manager {
+        return Ok(())
+    }
+
+    match authorized_user {
+        Some(authorized_user) => {
+            authorized_user.has_role(ROLE_WALLET_MANAGER, market_key)?;
+            Ok(())
+        }
+        None => err!(TallyClobErrors::NotAuthorized)
+    }
+}
+
+pub fn is_owner(signer_key: Pubkey, config: &ProgramConfig) -> Result<()> {
+    require!(signer_key == config.owner, TallyClobErrors::NotAuthorized);
+    Ok(())
+}
+
+pub fn is_clob_manager(signer_key: Pubkey, config: &ProgramConfig) -> Result<()> {
+    require!(signer_key == config.clob_manager, TallyClobErrors::NotAuthorized);
+    Ok(())
+}
+
+pub fn is_user_owner(signer_key: Pubkey, user: &User) -> Result<()> {
+    require!(signer_key == user.owner, TallyClobErrors::NotAuthorized);
+    Ok(())
+}
+
+/// Only wallets created by the wallet manager can be acted on through it;
+/// self-custodial wallets are signed for by their owner.
+pub fn is_custodial(user: &User) -> Result<()> {
+    require!(user.custodial, TallyClobErrors::NotCustodial);
     Ok(())
 }
 
-pub fn is_owner(signer_key: Pubkey) -> Result<()> {
-    let address = signer_key.to_string();
-    let owner_address = String::from("JDXABA58QsRJnGX4EvNDbMWx76shqYrRi72t8cW3ow3P");
-    require!(address == owner_address, TallyClobErrors::NotAuthorized);
+pub fn not_paused(config: &ProgramConfig) -> Result<()> {
+    require!(!config.paused, TallyClobErrors::ProgramPaused);
     Ok(())
 }
 
-pub fn is_clob_manager(signer_key: Pubkey) -> Result<()> {
-    let address = signer_key.to_string();
-    let owner_address = String::from("FvX9MgvZuaASkJyoVispyK2R6eDdc5tgtJmjXiqPuMKC");
-    require!(address == owner_address, TallyClobErrors::NotAuthorized);
+pub fn is_upgrade_authority(signer_key: Pubkey, program_data: &ProgramData) -> Result<()> {
+    require!(program_data.upgrade_authority_address == Some(signer_key), TallyClobErrors::NotAuthorized);
     Ok(())
 }
 
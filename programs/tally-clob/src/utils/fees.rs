@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::TallyClobErrors, utils::has_unique_elements, ProgramConfig, TradeTotals, User, BPS_SCALE};
+
+/// Credits the market creator's cut of the fee to their fee rewards and the
+/// trader's referrer's cut to their referral rewards, then returns what's
+/// left for the protocol. A cut with nobody to receive it stays with the
+/// protocol. The trade's volume counts towards the referrer's tier. Maker
+/// fees aren't split and all go to the protocol.
+///
+/// `recipients` are the creator and referrer accounts passed in. A recipient
+/// who is also the trader is paid through `trader`, so no account is ever
+/// loaded twice.
+pub fn split_fee<'info>(
+    totals: TradeTotals,
+    config: &ProgramConfig,
+    market_creator: Option<Pubkey>,
+    mut trader: Option<&mut Account<'info, User>>,
+    mut recipients: Vec<&mut Account<'info, User>>
+) -> Result<u128> {
+    let trader_key = trader.as_ref().map(|trader| trader.key());
+    require!(
+        has_unique_elements(recipients.iter().map(|recipient| recipient.key()).chain(trader_key)),
+        TallyClobErrors::InvalidFeeRecipient
+    );
+
+    let mut paid = 0;
+
+    let creator_share = config.fee_split.creator_share(totals.fee);
+    if let Some(creator) = market_creator.filter(|_| creator_share > 0) {
+        let account = match trader.as_deref_mut() {
+            Some(trader) if trader.key() == creator => trader,
+            _ => recipients.iter_mut()
+                .find(|account| account.key() == creator)
+                .ok_or(TallyClobErrors::InvalidFeeRecipient)?,
+        };
+        account.add_fee_rewards(creator_share)?;
+        paid += creator_share;
+    }
+
+    let referrer = trader.as_ref().and_then(|trader| trader.referrer);
+    if let Some(referrer) = referrer.filter(|_| config.has_referral_program()) {
+        let account = recipients.iter_mut()
+            .find(|account| account.key() == referrer)
+            .ok_or(TallyClobErrors::InvalidFeeRecipient)?;
+        let referrer_bps = config.get_referrer_bps(account.referred_volume);
+        let referrer_share = totals.fee * referrer_bps as u128 / BPS_SCALE;
+        account.add_referral(totals.volume, referrer_share)?;
+        paid += referrer_share;
+    }
+
+    Ok(totals.fee - paid + totals.maker_fee)
+}
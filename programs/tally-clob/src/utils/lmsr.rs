@@ -0,0 +1,230 @@
+use anchor_lang::prelude::*;
+
+use spl_math::{precise_number::PreciseNumber, uint::U256};
+
+use crate::errors::TallyClobErrors;
+
+// Logarithmic market scoring rule. The cost of the outstanding shares q is
+// C(q) = b * ln(sum(e^(q_j / b))) and a trade costs the change in C. Every
+// exponent is taken relative to the traded choice, so its own weight is 1
+// and the rest only lose precision once they are far enough behind that they
+// no longer matter. Choices can be at most MAX_EXPONENT * b apart.
+
+const MAX_EXPONENT: u128 = 80;
+const MAX_SERIES_TERMS: usize = 64;
+const MAX_PRICE_NUDGES: usize = 8;
+
+// ln(2) scaled by spl_math's ONE
+const LN_2: u128 = 693_147_180_560;
+
+fn precise(value: u128) -> Result<PreciseNumber> {
+    Ok(PreciseNumber::new(value).ok_or(TallyClobErrors::NotAValidOrder)?)
+}
+
+fn ln_2() -> PreciseNumber {
+    PreciseNumber { value: U256::from(LN_2) }
+}
+
+fn floor(value: &PreciseNumber) -> Result<u128> {
+    Ok(value.floor().and_then(|value| value.to_imprecise()).ok_or(TallyClobErrors::NotAValidOrder)?)
+}
+
+fn ceiling(value: &PreciseNumber) -> Result<u128> {
+    Ok(value.ceiling().and_then(|value| value.to_imprecise()).ok_or(TallyClobErrors::NotAValidOrder)?)
+}
+
+/// e^x for 0 <= x <= MAX_EXPONENT. x is split into k * ln(2) + r so the
+/// Taylor series only ever runs on r < ln(2).
+pub fn exp(x: &PreciseNumber) -> Result<PreciseNumber> {
+    require!(x.less_than_or_equal(&precise(MAX_EXPONENT)?), TallyClobErrors::NotAValidOrder);
+
+    let ln_2 = ln_2();
+    let halvings = floor(&x.checked_div(&ln_2).ok_or(TallyClobErrors::NotAValidOrder)?)?;
+    let remainder = x
+        .checked_sub(&precise(halvings)?.checked_mul(&ln_2).ok_or(TallyClobErrors::NotAValidOrder)?)
+        .unwrap_or(precise(0)?);
+
+    let mut sum = precise(1)?;
+    let mut term = precise(1)?;
+    for n in 1..=MAX_SERIES_TERMS {
+        term = term
+            .checked_mul(&remainder).ok_or(TallyClobErrors::NotAValidOrder)?
+            .checked_div(&precise(n as u128)?).ok_or(TallyClobErrors::NotAValidOrder)?;
+        if term.value.is_zero() { break }
+        sum = sum.checked_add(&term).ok_or(TallyClobErrors::NotAValidOrder)?;
+    }
+
+    Ok(sum.checked_mul(&precise(1u128 << halvings)?).ok_or(TallyClobErrors::NotAValidOrder)?)
+}
+
+/// ln(x) for x >= 1. x is halved down to m in [1, 2) and
+/// ln(m) = 2 * atanh((m - 1) / (m + 1)), which converges quickly since the
+/// atanh argument stays under 1/3.
+pub fn ln(x: &PreciseNumber) -> Result<PreciseNumber> {
+    let one = precise(1)?;
+    let two = precise(2)?;
+    require!(x.greater_than_or_equal(&one), TallyClobErrors::NotAValidOrder);
+
+    let mut mantissa = x.clone();
+    let mut halvings = 0;
+    while mantissa.greater_than_or_equal(&two) {
+        mantissa = mantissa.checked_div(&two).ok_or(TallyClobErrors::NotAValidOrder)?;
+        halvings += 1;
+    }
+
+    let z = mantissa
+        .checked_sub(&one).ok_or(TallyClobErrors::NotAValidOrder)?
+        .checked_div(&mantissa.checked_add(&one).ok_or(TallyClobErrors::NotAValidOrder)?)
+        .ok_or(TallyClobErrors::NotAValidOrder)?;
+    let z_squared = z.checked_mul(&z).ok_or(TallyClobErrors::NotAValidOrder)?;
+
+    let mut sum = precise(0)?;
+    let mut power = z;
+    for n in 0..MAX_SERIES_TERMS {
+        let term = power.checked_div(&precise(2 * n as u128 + 1)?).ok_or(TallyClobErrors::NotAValidOrder)?;
+        if term.value.is_zero() { break }
+        sum = sum.checked_add(&term).ok_or(TallyClobErrors::NotAValidOrder)?;
+        power = power.checked_mul(&z_squared).ok_or(TallyClobErrors::NotAValidOrder)?;
+    }
+
+    Ok(ln_2()
+        .checked_mul(&precise(halvings)?).ok_or(TallyClobErrors::NotAValidOrder)?
+        .checked_add(&sum.checked_mul(&two).ok_or(TallyClobErrors::NotAValidOrder)?)
+        .ok_or(TallyClobErrors::NotAValidOrder)?)
+}
+
+/// e^(-x), flushed to zero past MAX_EXPONENT.
+fn exp_neg(x: &PreciseNumber) -> Result<PreciseNumber> {
+    if x.greater_than(&precise(MAX_EXPONENT)?) {
+        return precise(0)
+    }
+
+    Ok(precise(1)?.checked_div(&exp(x)?).ok_or(TallyClobErrors::NotAValidOrder)?)
+}
+
+fn divide_by_liquidity(amount: u128, liquidity_parameter: u128) -> Result<PreciseNumber> {
+    require!(liquidity_parameter > 0, TallyClobErrors::InvalidLiquidityParameter);
+
+    Ok(precise(amount)?
+        .checked_div(&precise(liquidity_parameter)?)
+        .ok_or(TallyClobErrors::NotAValidOrder)?)
+}
+
+fn multiply_by_liquidity(value: &PreciseNumber, liquidity_parameter: u128) -> Result<PreciseNumber> {
+    Ok(value.checked_mul(&precise(liquidity_parameter)?).ok_or(TallyClobErrors::NotAValidOrder)?)
+}
+
+/// Sum of e^((q_j - q_i) / b) over every choice, so the choice at `index`
+/// itself has a weight of exactly 1.
+fn get_total_weight(shares: &[u128], index: usize, liquidity_parameter: u128) -> Result<PreciseNumber> {
+    require!(index < shares.len(), TallyClobErrors::ChoiceNotFound);
+
+    let mut total_weight = precise(0)?;
+    for other_shares in shares.iter() {
+        let weight = if *other_shares >= shares[index] {
+            exp(&divide_by_liquidity(other_shares - shares[index], liquidity_parameter)?)?
+        } else {
+            exp_neg(&divide_by_liquidity(shares[index] - other_shares, liquidity_parameter)?)?
+        };
+        total_weight = total_weight.checked_add(&weight).ok_or(TallyClobErrors::NotAValidOrder)?;
+    }
+
+    Ok(total_weight)
+}
+
+/// Most the operator can lose on a sub market, b * ln(n).
+pub fn get_lmsr_max_loss(liquidity_parameter: u128, choice_count: usize) -> Result<u128> {
+    let ln_n = ln(&precise(choice_count as u128)?)?;
+
+    ceiling(&multiply_by_liquidity(&ln_n, liquidity_parameter)?)
+}
+
+/// Shares of the choice at `index` that `price` buys, rounded down.
+pub fn get_lmsr_shares_to_buy(shares: &[u128], index: usize, price: u128, liquidity_parameter: u128) -> Result<u128> {
+    let total_weight = get_total_weight(shares, index, liquidity_parameter)?;
+
+    // the choice's weight grows to 1 + (e^(p / b) - 1) * S
+    let new_weight = exp(&divide_by_liquidity(price, liquidity_parameter)?)?
+        .checked_sub(&precise(1)?).ok_or(TallyClobErrors::NotAValidOrder)?
+        .checked_mul(&total_weight).ok_or(TallyClobErrors::NotAValidOrder)?
+        .checked_add(&precise(1)?).ok_or(TallyClobErrors::NotAValidOrder)?;
+
+    floor(&multiply_by_liquidity(&ln(&new_weight)?, liquidity_parameter)?)
+}
+
+/// Cost of buying `shares_to_buy` of the choice at `index`, rounded up.
+pub fn get_lmsr_buy_price(shares: &[u128], index: usize, shares_to_buy: u128, liquidity_parameter: u128) -> Result<u128> {
+    let total_weight = get_total_weight(shares, index, liquidity_parameter)?;
+
+    // b * ln(1 + (e^(d / b) - 1) / S)
+    let cost_ratio = exp(&divide_by_liquidity(shares_to_buy, liquidity_parameter)?)?
+        .checked_sub(&precise(1)?).ok_or(TallyClobErrors::NotAValidOrder)?
+        .checked_div(&total_weight).ok_or(TallyClobErrors::NotAValidOrder)?
+        .checked_add(&precise(1)?).ok_or(TallyClobErrors::NotAValidOrder)?;
+    let mut buy_price = ceiling(&multiply_by_liquidity(&ln(&cost_ratio)?, liquidity_parameter)?)?;
+
+    // exp and ln each round a little, so nudge the price until it buys back
+    // the shares it was quoted for. Shares never cost more than 1 each, so
+    // the shortfall is always enough extra price.
+    for _ in 0..MAX_PRICE_NUDGES {
+        let shares_bought = get_lmsr_shares_to_buy(shares, index, buy_price, liquidity_parameter)?;
+        if shares_bought >= shares_to_buy {
+            return Ok(buy_price)
+        }
+        buy_price += shares_to_buy - shares_bought;
+    }
+
+    err!(TallyClobErrors::NotAValidOrder)
+}
+
+/// Shares of the choice at `index` that have to be sold to be paid `price`,
+/// rounded up.
+pub fn get_lmsr_shares_to_sell(shares: &[u128], index: usize, price: u128, liquidity_parameter: u128) -> Result<u128> {
+    let total_weight = get_total_weight(shares, index, liquidity_parameter)?;
+
+    // the choice's weight shrinks to S * e^(-p / b) - (S - 1)
+    let other_weight = total_weight
+        .checked_sub(&precise(1)?).ok_or(TallyClobErrors::NotAValidOrder)?;
+    let new_weight = total_weight
+        .checked_mul(&exp_neg(&divide_by_liquidity(price, liquidity_parameter)?)?)
+        .ok_or(TallyClobErrors::NotAValidOrder)?
+        .checked_sub(&other_weight).ok_or(TallyClobErrors::NotAValidOrder)?;
+    require!(!new_weight.value.is_zero(), TallyClobErrors::NotAValidOrder);
+
+    // so b * ln(1 / new weight) shares come out
+    let shrink_ratio = precise(1)?
+        .checked_div(&new_weight).ok_or(TallyClobErrors::NotAValidOrder)?;
+    let shares_to_sell = ceiling(&multiply_by_liquidity(&ln(&shrink_ratio)?, liquidity_parameter)?)?;
+
+    require!(shares_to_sell <= shares[index], TallyClobErrors::NotAValidOrder);
+
+    Ok(shares_to_sell)
+}
+
+/// What selling `shares_to_sell` of the choice at `index` pays, rounded down.
+pub fn get_lmsr_sell_price(shares: &[u128], index: usize, shares_to_sell: u128, liquidity_parameter: u128) -> Result<u128> {
+    require!(index < shares.len() && shares_to_sell <= shares[index], TallyClobErrors::NotAValidOrder);
+
+    let total_weight = get_total_weight(shares, index, liquidity_parameter)?;
+
+    // b * ln(S / (S - 1 + e^(-d / b)))
+    let new_total_weight = total_weight
+        .checked_sub(&precise(1)?).ok_or(TallyClobErrors::NotAValidOrder)?
+        .checked_add(&exp_neg(&divide_by_liquidity(shares_to_sell, liquidity_parameter)?)?)
+        .ok_or(TallyClobErrors::NotAValidOrder)?;
+    let proceeds_ratio = total_weight
+        .checked_div(&new_total_weight).ok_or(TallyClobErrors::NotAValidOrder)?;
+
+    floor(&multiply_by_liquidity(&ln(&proceeds_ratio)?, liquidity_parameter)?)
+}
+
+/// Marginal price of the choice at `index`, e^(q_i / b) / sum(e^(q_j / b)),
+/// scaled by `price_scale`.
+pub fn get_lmsr_spot_price(shares: &[u128], index: usize, liquidity_parameter: u128, price_scale: u128) -> Result<u128> {
+    let total_weight = get_total_weight(shares, index, liquidity_parameter)?;
+
+    let spot_price = precise(price_scale)?
+        .checked_div(&total_weight).ok_or(TallyClobErrors::NotAValidOrder)?;
+
+    floor(&spot_price)
+}
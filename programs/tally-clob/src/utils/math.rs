@@ -4,178 +4,196 @@ use spl_math::precise_number::PreciseNumber;
 
 use crate::errors::TallyClobErrors;
 
-pub fn get_buy_price(pot_shares: Vec<u128>, invariant: u128) -> Result<u128> {
-
-    require!(pot_shares.len() == 2, TallyClobErrors::NotAValidOrder);
-
-
-    let pot_shares1 = PreciseNumber::new(pot_shares[0]).ok_or(TallyClobErrors::NotAValidOrder)?;
-    msg!(&pot_shares1.value.to_string());
-    let pot_shares2 = PreciseNumber::new(pot_shares[1]).ok_or(TallyClobErrors::NotAValidOrder)?;
-    msg!(&pot_shares2.value.to_string());
-    let invariant  = PreciseNumber::new(invariant).ok_or(TallyClobErrors::NotAValidOrder)?;
-    msg!(&invariant.value.to_string());
-
-
-    let a = PreciseNumber::new(1).ok_or(TallyClobErrors::NotAValidOrder)?; // The coefficient of x^2 in the quadratic equation is always 1.
-    msg!(&a.value.to_string());
-    let b= pot_shares1
-                            .checked_add(&pot_shares2)
-                            .ok_or(TallyClobErrors::NotAValidOrder)?; // Sum of pot_shares, with a negative sign for the coefficient of x.
-    msg!(&b.value.to_string());
-    let c: PreciseNumber;
-    if invariant.greater_than(&pot_shares1.checked_mul(&pot_shares2).ok_or(TallyClobErrors::NotAValidOrder)?) {
-        c = invariant.checked_sub(&pot_shares1.checked_mul(&pot_shares2).ok_or(TallyClobErrors::NotAValidOrder)?).ok_or(TallyClobErrors::NotAValidOrder)?;
-    } else {
-        c = pot_shares1.checked_mul(&pot_shares2).ok_or(TallyClobErrors::NotAValidOrder)?.checked_sub(&invariant).ok_or(TallyClobErrors::NotAValidOrder)?;
-    }
-    msg!(&c.value.to_string());
-    let b_squared = b.checked_pow(2)
-                                    .ok_or(TallyClobErrors::NotAValidOrder)?;
-    msg!(&b_squared.value.to_string());
-    let four_a_c= a.checked_mul(&PreciseNumber::new(4).ok_or(TallyClobErrors::NotAValidOrder)?)
-                                    .ok_or(TallyClobErrors::NotAValidOrder)?
-                                    .checked_mul(&c)
-                                    .ok_or(TallyClobErrors::NotAValidOrder)?;
-
-    msg!(&four_a_c.value.to_string()); 
-    let discriminant: PreciseNumber;
-
-    if invariant.greater_than(&pot_shares1.checked_mul(&pot_shares2).ok_or(TallyClobErrors::NotAValidOrder)?) {
-        discriminant = b_squared.checked_add(&four_a_c).ok_or(TallyClobErrors::NotAValidOrder)?;
-    } else {
-        discriminant = b_squared.checked_sub(&four_a_c).ok_or(TallyClobErrors::NotAValidOrder)?;
+// The sub market is a constant product market maker over every choice's
+// pot_shares. Buying adds the price to every pot and takes the shares out of
+// the bought choice's pot, selling does the reverse, and in both cases the
+// product of all the pots stays the same. The product itself is never stored,
+// since with a handful of choices it doesn't fit in a u128, so each pot is
+// rescaled one ratio at a time instead.
+
+fn checked_mul_div(value: u128, numerator: u128, denominator: u128) -> Result<u128> {
+    require!(denominator > 0, TallyClobErrors::NotAValidOrder);
+
+    let product = value.checked_mul(numerator).ok_or(TallyClobErrors::NotAValidOrder)?;
+
+    Ok(product / denominator)
+}
+
+/// What's left in the pot at `index` after `price` is added to every pot.
+pub fn get_pot_shares_after_buy(pot_shares: &[u128], index: usize, price: u128) -> Result<u128> {
+    require!(index < pot_shares.len(), TallyClobErrors::ChoiceNotFound);
+
+    let mut new_pot_shares = pot_shares[index];
+    for (other_index, other_pot_shares) in pot_shares.iter().enumerate() {
+        if other_index == index { continue }
+
+        let grown_pot_shares = other_pot_shares.checked_add(price).ok_or(TallyClobErrors::NotAValidOrder)?;
+        new_pot_shares = checked_mul_div(new_pot_shares, *other_pot_shares, grown_pot_shares)?;
     }
-    msg!(&discriminant.value.to_string()); 
-    // Calculate the square root of the discriminant for the quadratic formula.
-    let sqrt_discriminant = discriminant.sqrt().ok_or(TallyClobErrors::NotAValidOrder)?;
-    msg!(&sqrt_discriminant.value.to_string()); 
-    let two_a = PreciseNumber::new(2)
-                                .ok_or(TallyClobErrors::NotAValidOrder)?
-                                .checked_mul(&a)
-                                .ok_or(TallyClobErrors::NotAValidOrder)?;
-    msg!(&two_a.value.to_string()); 
-    // let mut root1: PreciseNumber;
-    // let mut root2: PreciseNumber;
-
-    let valid_root = sqrt_discriminant
-                                    .checked_sub(&b)
-                                    .ok_or(TallyClobErrors::NotAValidOrder)?
-                                    .checked_div(&two_a)
-                                    .ok_or(TallyClobErrors::NotAValidOrder)?;
-    msg!(&(valid_root.value / 10_u128.pow(12)).to_string());    
-    Ok(valid_root.value.as_u128() / 10_u128.pow(12))
 
+    Ok(new_pot_shares)
 }
 
-pub fn get_sell_price(pot_shares: Vec<u128>, invariant: u128) -> Result<u128> {
-
-    require!(pot_shares.len() == 2, TallyClobErrors::NotAValidOrder);
-
-    // let usdc_decimal_factor = PreciseNumber::new(10_u128.pow(12)).ok_or(TallyClobErrors::NotAValidOrder).unwrap();
-
-    let pot_shares1 = PreciseNumber::new(pot_shares[0]).ok_or(TallyClobErrors::NotAValidOrder)?;
-    msg!(&pot_shares1.value.to_string());
-    let pot_shares2 = PreciseNumber::new(pot_shares[1]).ok_or(TallyClobErrors::NotAValidOrder)?;
-    msg!(&pot_shares2.value.to_string());
-    let invariant  = PreciseNumber::new(invariant).ok_or(TallyClobErrors::NotAValidOrder)?;
-    msg!(&invariant.value.to_string());
-
-    let a = PreciseNumber::new(1).ok_or(TallyClobErrors::NotAValidOrder)?;// The coefficient of x^2 in the quadratic equation is always 1.
-    msg!(&a.value.to_string());
-    let b= pot_shares1
-                            .checked_add(&pot_shares2)
-                            .ok_or(TallyClobErrors::NotAValidOrder)?; // Sum of pot_shares, with a negative sign for the coefficient of x.
-    msg!(&b.value.to_string());
-    let c = pot_shares1
-                            .checked_mul(&pot_shares2)
-                            .ok_or(TallyClobErrors::NotAValidOrder)?
-                            .checked_sub(&invariant)
-                            .ok_or(TallyClobErrors::NotAValidOrder)?; // Product of pot_shares minus the invariant for the constant term.
-    msg!(&c.value.to_string());
-    let b_squared =  b.checked_pow(2)
-                                    .ok_or(TallyClobErrors::NotAValidOrder)?;
-    msg!(&b_squared.value.to_string());
-    let four_a_c= a.checked_mul(&PreciseNumber::new(4).ok_or(TallyClobErrors::NotAValidOrder)?)
-                                        .ok_or(TallyClobErrors::NotAValidOrder)?
-                                        .checked_mul(&c)
-                                        .ok_or(TallyClobErrors::NotAValidOrder)?;
-    msg!(&four_a_c.value.to_string()); 
-    let discriminant = b_squared.checked_sub(&four_a_c).ok_or(TallyClobErrors::NotAValidOrder)?;
-    msg!(&discriminant.value.to_string()); 
-    // Calculate the square root of the discriminant for the quadratic formula.
-    let sqrt_discriminant = discriminant.sqrt().ok_or(TallyClobErrors::NotAValidOrder)?;
-    msg!(&sqrt_discriminant.value.to_string()); 
-    let two_a = PreciseNumber::new(2)
-                                .ok_or(TallyClobErrors::NotAValidOrder)?
-                                .checked_mul(&a)
-                                .ok_or(TallyClobErrors::NotAValidOrder)?;
-    msg!(&two_a.value.to_string()); 
-    let b_minus_discriminant_sqrt = b
-                                                            .checked_sub(&sqrt_discriminant);
-
-    if b_minus_discriminant_sqrt.is_some() {
-        let root = b_minus_discriminant_sqrt
-                                            .ok_or(TallyClobErrors::NotAValidOrder)?
-                                            .checked_div(&two_a)
-                                            .ok_or(TallyClobErrors::NotAValidOrder)?;
-        msg!(&root.value.to_string());     
-        return Ok(root.value.as_u128() / 10_u128.pow(12))
+/// What the pot at `index` has to grow to after `price` is taken out of every
+/// pot.
+pub fn get_pot_shares_after_sell(pot_shares: &[u128], index: usize, price: u128) -> Result<u128> {
+    require!(index < pot_shares.len(), TallyClobErrors::ChoiceNotFound);
+
+    let mut new_pot_shares = pot_shares[index];
+    for (other_index, other_pot_shares) in pot_shares.iter().enumerate() {
+        if other_index == index { continue }
+
+        require!(*other_pot_shares > price, TallyClobErrors::NotAValidOrder);
+        new_pot_shares = checked_mul_div(new_pot_shares, *other_pot_shares, other_pot_shares - price)?;
     }
 
-    let b_plus_discriminant_sqrt = b
-                                                    .checked_sub(&sqrt_discriminant)
-                                                    .ok_or(TallyClobErrors::NotAValidOrder)?;
+    Ok(new_pot_shares)
+}
 
-    let root = b_plus_discriminant_sqrt.checked_div(&two_a)
-                                .ok_or(TallyClobErrors::NotAValidOrder)?;
+pub fn get_shares_to_buy(pot_shares: &[u128], index: usize, price: u128) -> Result<u128> {
+    let new_pot_shares = get_pot_shares_after_buy(pot_shares, index, price)?;
 
-    let root_value = root.value.as_u128() / 10_u128.pow(12);
+    Ok(pot_shares[index] + price - new_pot_shares)
+}
+
+pub fn get_shares_to_sell(pot_shares: &[u128], index: usize, price: u128) -> Result<u128> {
+    require!(pot_shares[index] >= price, TallyClobErrors::NotAValidOrder);
+    let new_pot_shares = get_pot_shares_after_sell(pot_shares, index, price)?;
 
-    if root_value < pot_shares[0] && root_value < pot_shares[1] {
-        msg!(&root.value.to_string());
-        return Ok(root_value)
+    Ok(new_pot_shares - (pot_shares[index] - price))
+}
+
+/// Cheapest price that buys at least `shares`. Shares bought never drop
+/// below the price paid, so the answer is somewhere in `0..=shares`.
+pub fn get_buy_price(pot_shares: &[u128], index: usize, shares: u128) -> Result<u128> {
+    require!(index < pot_shares.len(), TallyClobErrors::ChoiceNotFound);
+
+    let mut low = 0;
+    let mut high = shares;
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if get_shares_to_buy(pot_shares, index, mid)? >= shares {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
     }
 
-    err!(TallyClobErrors::NotAValidOrder) // Return an error if neither root is valid.
+    Ok(low)
 }
 
-// pub fn get_sell_price(pot_shares: Vec<f64>, invariant: f64) -> Result<f64> {
+/// Most a seller can be paid for `shares`, capped by the smallest pot it has
+/// to come out of.
+pub fn get_sell_price(pot_shares: &[u128], index: usize, shares: u128) -> Result<u128> {
+    require!(index < pot_shares.len(), TallyClobErrors::ChoiceNotFound);
+
+    let smallest_other_pot = pot_shares.iter()
+        .enumerate()
+        .filter(|(other_index, _)| *other_index != index)
+        .map(|(_, pot_shares)| *pot_shares)
+        .min()
+        .ok_or(TallyClobErrors::NotAValidOrder)?;
+
+    let mut low = 0;
+    let mut high = shares
+        .min(smallest_other_pot.saturating_sub(1))
+        .min(pot_shares[index]);
+    while low < high {
+        let mid = high - (high - low) / 2;
+        if get_shares_to_sell(pot_shares, index, mid)? <= shares {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
 
-//     let mut pot_shares1 = Float::new(128);
-//     pot_shares1.assign(pot_shares[0]);
-//     let mut pot_shares2 = Float::new(128);
-//     pot_shares2.assign(pot_shares[1]);
-//     let mut invariant_prec = Float::new(128);
-//     invariant_prec.assign(invariant);
+    Ok(low)
+}
 
+/// n-th root of a number between 0 and 1 by Newton's method, starting from 1
+/// so every step approaches the root from above.
+pub fn nth_root(value: &PreciseNumber, n: u128) -> Result<PreciseNumber> {
+    let one = PreciseNumber::new(1).ok_or(TallyClobErrors::NotAValidOrder)?;
+    require!(value.less_than_or_equal(&one), TallyClobErrors::NotAValidOrder);
+    require!(n > 0, TallyClobErrors::NotAValidOrder);
 
-//     // (1-x)(2-x) = 3
-//     // c = 1 * 2 - 3 = -1
-//     let a: Float = Float::with_val(128, 1.0); // Since we're working directly with f64, no scaling is needed.
-//     let b = -(pot_shares1 + pot_shares[1]); // Sum of pot_shares.
-//     let c = pot_shares1 * pot_shares2 - invariant_prec; // Product of pot_shares minus the invariant.
+    if n == 1 {
+        return Ok(value.clone())
+    }
 
+    let n_prec = PreciseNumber::new(n).ok_or(TallyClobErrors::NotAValidOrder)?;
+    let n_minus_one = PreciseNumber::new(n - 1).ok_or(TallyClobErrors::NotAValidOrder)?;
+
+    let mut root = one;
+    for _ in 0..100 {
+        let power = root.checked_pow(n - 1).ok_or(TallyClobErrors::NotAValidOrder)?;
+        let next = n_minus_one
+            .checked_mul(&root).ok_or(TallyClobErrors::NotAValidOrder)?
+            .checked_add(&value.checked_div(&power).ok_or(TallyClobErrors::NotAValidOrder)?)
+            .ok_or(TallyClobErrors::NotAValidOrder)?
+            .checked_div(&n_prec).ok_or(TallyClobErrors::NotAValidOrder)?;
+
+        if !next.less_than(&root) {
+            break
+        }
+        root = next;
+    }
 
-//     let discriminant: Float = b.pow(2) - 4.0 * a * c; // Calculate the discriminant of the quadratic equation.
+    Ok(root)
+}
 
-//     require!(discriminant >= 0.0, TallyClobErrors::NotAValidOrder); // Ensure discriminant is non-negative for real roots.
+/// Seeds pot_shares from the fair launch pots so every choice's price is its
+/// share of the total pot. For two choices this is the old `k = total^2`
+/// seeding; in general the pots are scaled so their geometric mean is the
+/// total pot.
+pub fn get_fair_launch_pot_shares(fair_launch_pots: &[u128]) -> Result<Vec<u128>> {
+    require!(fair_launch_pots.iter().all(|pot| *pot > 0), TallyClobErrors::NotAValidOrder);
+
+    let total_pot = fair_launch_pots.iter().sum::<u128>();
+    let largest_pot = *fair_launch_pots.iter().max().ok_or(TallyClobErrors::NotAValidOrder)?;
+    let largest_pot_prec = PreciseNumber::new(largest_pot).ok_or(TallyClobErrors::NotAValidOrder)?;
+
+    // geometric mean of the pots relative to the largest one, so the product stays <= 1
+    let mut ratio_product = PreciseNumber::new(1).ok_or(TallyClobErrors::NotAValidOrder)?;
+    for pot in fair_launch_pots.iter() {
+        let ratio = PreciseNumber::new(*pot).ok_or(TallyClobErrors::NotAValidOrder)?
+            .checked_div(&largest_pot_prec).ok_or(TallyClobErrors::NotAValidOrder)?;
+        ratio_product = ratio_product.checked_mul(&ratio).ok_or(TallyClobErrors::NotAValidOrder)?;
+    }
+    require!(ratio_product.value > 0.into(), TallyClobErrors::NotAValidOrder);
 
-//     // Calculate the square root of the discriminant for the quadratic formula.
-//     let sqrt_discriminant = discriminant.sqrt();
-//     let root1: Float = (-b + sqrt_discriminant) / (2.0 * a); // Calculate the first root.
-//     let root2: Float = (-b - sqrt_discriminant) / (2.0 * a); // Calculate the second root.
+    let geometric_mean = nth_root(&ratio_product, fair_launch_pots.len() as u128)?
+        .checked_mul(&largest_pot_prec).ok_or(TallyClobErrors::NotAValidOrder)?;
 
-//     // Ensure the roots are valid based on the application's logic.
-//     // Adjust the conditions to reflect the specifics of a sell operation.
-//     // msg!(&(pot_shares[0] - root1).to_string());
-//     if root1 > 0.0 && pot_shares[0] - root1 > 0.0 {
-//         return Ok(root1.to_f64());
-//     }
-//     // msg!(&(pot_shares[1] - root2).to_string());
-//     if root2 > 0.0 && pot_shares[1] - root2 > 0.0{
-//         return Ok(root1.to_f64());
-//     }
+    let total_pot_prec = PreciseNumber::new(total_pot).ok_or(TallyClobErrors::NotAValidOrder)?;
 
-//     err!(TallyClobErrors::NotAValidOrder) // Return an error if neither root is valid.
-// }
\ No newline at end of file
+    fair_launch_pots.iter()
+        .map(|pot| {
+            let pot_prec = PreciseNumber::new(*pot).ok_or(TallyClobErrors::NotAValidOrder)?;
+            let pot_shares = total_pot_prec
+                .checked_mul(&geometric_mean).ok_or(TallyClobErrors::NotAValidOrder)?
+                .checked_div(&pot_prec).ok_or(TallyClobErrors::NotAValidOrder)?;
+
+            Ok(pot_shares.to_imprecise().ok_or(TallyClobErrors::NotAValidOrder)?)
+        })
+        .collect()
+}
+/// Marginal price of the choice at `index`, scaled by `price_scale`.
+/// With the product held constant it's 1 / sum(x_i / x_j).
+pub fn get_spot_price(pot_shares: &[u128], index: usize, price_scale: u128) -> Result<u128> {
+    require!(index < pot_shares.len(), TallyClobErrors::ChoiceNotFound);
+
+    let choice_pot_shares = PreciseNumber::new(pot_shares[index]).ok_or(TallyClobErrors::NotAValidOrder)?;
+    let mut ratio_sum = PreciseNumber::new(0).ok_or(TallyClobErrors::NotAValidOrder)?;
+    for other_pot_shares in pot_shares.iter() {
+        let ratio = choice_pot_shares
+            .checked_div(&PreciseNumber::new(*other_pot_shares).ok_or(TallyClobErrors::NotAValidOrder)?)
+            .ok_or(TallyClobErrors::NotAValidOrder)?;
+        ratio_sum = ratio_sum.checked_add(&ratio).ok_or(TallyClobErrors::NotAValidOrder)?;
+    }
+
+    let spot_price = PreciseNumber::new(price_scale).ok_or(TallyClobErrors::NotAValidOrder)?
+        .checked_div(&ratio_sum).ok_or(TallyClobErrors::NotAValidOrder)?;
+
+    Ok(spot_price.to_imprecise().ok_or(TallyClobErrors::NotAValidOrder)?)
+}
@@ -1,7 +1,19 @@
 pub use checks::*;
 pub use clock::*;
+pub use fees::*;
+pub use lmsr::*;
 pub use math::*;
+pub use oracle::*;
+pub use order_routing::*;
+pub use share_tokens::*;
+pub use vault::*;
 
 pub mod checks;
 pub mod clock;
-pub mod math;
\ No newline at end of file
+pub mod fees;
+pub mod lmsr;
+pub mod math;
+pub mod oracle;
+pub mod order_routing;
+pub mod share_tokens;
+pub mod vault;
\ No newline at end of file
@@ -0,0 +1,120 @@
+use anchor_lang::{prelude::*, solana_program::pubkey};
+
+use crate::{errors::TallyClobErrors, MockFeed, OracleConfig, OracleKind, OraclePrice};
+
+const PYTH_PROGRAM_IDS: [Pubkey; 2] = [
+    pubkey!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi9epH"), // mainnet
+    pubkey!("gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s"), // devnet
+];
+const SWITCHBOARD_PROGRAM_ID: Pubkey = pubkey!("SW1TCH7qEPTdLsDHRgPuMQjbQxKdH2aBStViMFnt64f");
+
+// Pyth v2 price account: magic, version and account type up front, the
+// aggregate's publish time at 96 and the aggregate price info at 208.
+const PYTH_MAGIC: u32 = 0xa1b2c3d4;
+const PYTH_VERSION: u32 = 2;
+const PYTH_PRICE_ACCOUNT: u32 = 3;
+const PYTH_STATUS_TRADING: u32 = 1;
+const PYTH_EXPO_OFFSET: usize = 20;
+const PYTH_TIMESTAMP_OFFSET: usize = 96;
+const PYTH_AGG_PRICE_OFFSET: usize = 208;
+const PYTH_AGG_STATUS_OFFSET: usize = 224;
+
+// Switchboard v2 aggregator: the packed latest confirmed round starts at 341,
+// its open timestamp at 358 and its result (i128 mantissa, u32 scale) at 366.
+const SWITCHBOARD_AGGREGATOR_DISCRIMINATOR: [u8; 8] = [217, 230, 65, 101, 201, 162, 27, 125];
+const SWITCHBOARD_NUM_SUCCESS_OFFSET: usize = 341;
+const SWITCHBOARD_TIMESTAMP_OFFSET: usize = 358;
+const SWITCHBOARD_MANTISSA_OFFSET: usize = 366;
+const SWITCHBOARD_SCALE_OFFSET: usize = 382;
+
+fn read_bytes<const N: usize>(data: &[u8], offset: usize) -> Result<[u8; N]> {
+    let bytes = data.get(offset..offset + N).ok_or(TallyClobErrors::InvalidOracleFeed)?;
+
+    Ok(bytes.try_into().map_err(|_| TallyClobErrors::InvalidOracleFeed)?)
+}
+
+fn read_pyth_price(data: &[u8]) -> Result<OraclePrice> {
+    require!(u32::from_le_bytes(read_bytes(data, 0)?) == PYTH_MAGIC, TallyClobErrors::InvalidOracleFeed);
+    require!(u32::from_le_bytes(read_bytes(data, 4)?) == PYTH_VERSION, TallyClobErrors::InvalidOracleFeed);
+    require!(u32::from_le_bytes(read_bytes(data, 8)?) == PYTH_PRICE_ACCOUNT, TallyClobErrors::InvalidOracleFeed);
+    require!(
+        u32::from_le_bytes(read_bytes(data, PYTH_AGG_STATUS_OFFSET)?) == PYTH_STATUS_TRADING,
+        TallyClobErrors::OraclePriceUnavailable
+    );
+
+    Ok(OraclePrice {
+        price: i64::from_le_bytes(read_bytes(data, PYTH_AGG_PRICE_OFFSET)?) as i128,
+        expo: i32::from_le_bytes(read_bytes(data, PYTH_EXPO_OFFSET)?),
+        publish_time: i64::from_le_bytes(read_bytes(data, PYTH_TIMESTAMP_OFFSET)?)
+    })
+}
+
+fn read_switchboard_price(data: &[u8]) -> Result<OraclePrice> {
+    require!(read_bytes::<8>(data, 0)? == SWITCHBOARD_AGGREGATOR_DISCRIMINATOR, TallyClobErrors::InvalidOracleFeed);
+    require!(
+        u32::from_le_bytes(read_bytes(data, SWITCHBOARD_NUM_SUCCESS_OFFSET)?) > 0,
+        TallyClobErrors::OraclePriceUnavailable
+    );
+
+    let scale = u32::from_le_bytes(read_bytes(data, SWITCHBOARD_SCALE_OFFSET)?);
+
+    Ok(OraclePrice {
+        price: i128::from_le_bytes(read_bytes(data, SWITCHBOARD_MANTISSA_OFFSET)?),
+        expo: -i32::try_from(scale).map_err(|_| TallyClobErrors::InvalidOracleFeed)?,
+        publish_time: i64::from_le_bytes(read_bytes(data, SWITCHBOARD_TIMESTAMP_OFFSET)?)
+    })
+}
+
+fn read_mock_price(data: &[u8]) -> Result<OraclePrice> {
+    let mock_feed = MockFeed::try_deserialize(&mut &data[..])?;
+
+    Ok(OraclePrice {
+        price: mock_feed.price as i128,
+        expo: mock_feed.expo,
+        publish_time: mock_feed.publish_time
+    })
+}
+
+/// Reads the latest price out of `feed` after checking it's the configured
+/// feed and is owned by the program its layout belongs to.
+pub fn read_oracle_price(oracle: &OracleConfig, feed: &AccountInfo) -> Result<OraclePrice> {
+    require!(feed.key() == oracle.feed, TallyClobErrors::InvalidOracleFeed);
+
+    let owned_by_layout = match oracle.kind {
+        OracleKind::Pyth => PYTH_PROGRAM_IDS.contains(feed.owner),
+        OracleKind::Switchboard => feed.owner == &SWITCHBOARD_PROGRAM_ID,
+        OracleKind::Mock => cfg!(feature = "test") && feed.owner == &crate::ID,
+    };
+    require!(owned_by_layout, TallyClobErrors::InvalidOracleFeed);
+
+    let data = feed.try_borrow_data()?;
+    match oracle.kind {
+        OracleKind::Pyth => read_pyth_price(&data),
+        OracleKind::Switchboard => read_switchboard_price(&data),
+        OracleKind::Mock => read_mock_price(&data),
+    }
+}
+
+fn scale_to_expo(value: i128, expo: i32, target_expo: i32) -> Result<i128> {
+    let factor = 10i128
+        .checked_pow((expo - target_expo) as u32)
+        .ok_or(TallyClobErrors::InvalidOracleFeed)?;
+
+    Ok(value.checked_mul(factor).ok_or(TallyClobErrors::InvalidOracleFeed)?)
+}
+
+/// Picks the winning choice for `price`. The price has to be published
+/// within `max_delay` seconds of `resolve_at`.
+pub fn get_oracle_outcome(oracle: &OracleConfig, price: &OraclePrice) -> Result<u64> {
+    require!(price.publish_time >= oracle.resolve_at, TallyClobErrors::OraclePriceUnavailable);
+    require!(price.publish_time <= oracle.resolve_at + oracle.max_delay, TallyClobErrors::OraclePriceStale);
+
+    let expo = price.expo.min(oracle.threshold_expo);
+    let feed_price = scale_to_expo(price.price, price.expo, expo)?;
+    let threshold = scale_to_expo(oracle.threshold as i128, oracle.threshold_expo, expo)?;
+
+    Ok(match oracle.comparison.holds(feed_price, threshold) {
+        true => oracle.choice_if_true,
+        false => oracle.choice_if_false,
+    })
+}
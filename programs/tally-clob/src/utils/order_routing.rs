@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::TallyClobErrors, utils::{current_timestamp, has_unique_elements}, EventQueue, FeeSchedule, Market, Order, OrderBook, Side};
+
+/// An order book and its event queue passed in the remaining accounts of a
+/// bulk order. Both are deserialized once, matched against in memory and
+/// written back by `save_book_routes`.
+pub struct BookRoute<'a, 'info> {
+    order_book_info: &'a AccountInfo<'info>,
+    event_queue_info: &'a AccountInfo<'info>,
+    pub order_book: OrderBook,
+    pub event_queue: EventQueue,
+}
+
+/// What an order filled against resting orders before reaching the curve,
+/// with the taker fee owed on it and the makers' fees taken out of it.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct BookFill {
+    pub shares: u128,
+    pub quote: u128,
+    pub fee: u128,
+    pub maker_fee: u128,
+}
+
+/// Reads `[order_book, event_queue]` pairs out of the remaining accounts.
+/// Every book has to belong to `market` and each book can only be passed once.
+pub fn load_book_routes<'a, 'info>(accounts: &'a [AccountInfo<'info>], market: &Pubkey) -> Result<Vec<BookRoute<'a, 'info>>> {
+    let pairs = accounts.chunks_exact(2);
+    require!(pairs.remainder().is_empty(), TallyClobErrors::InvalidOrderBookAccount);
+    require!(has_unique_elements(accounts.iter().map(|account| account.key())), TallyClobErrors::InvalidOrderBookAccount);
+
+    pairs.map(|pair| {
+        let order_book_info = &pair[0];
+        let event_queue_info = &pair[1];
+        for account in pair.iter() {
+            require!(account.owner == &crate::ID && account.is_writable, TallyClobErrors::InvalidOrderBookAccount);
+        }
+
+        let order_book = OrderBook::try_deserialize(&mut &order_book_info.try_borrow_data()?[..])?;
+        let event_queue = EventQueue::try_deserialize(&mut &event_queue_info.try_borrow_data()?[..])?;
+        require!(order_book.market == *market, TallyClobErrors::InvalidOrderBookAccount);
+        require!(event_queue.order_book == order_book_info.key(), TallyClobErrors::InvalidOrderBookAccount);
+
+        Ok(BookRoute { order_book_info, event_queue_info, order_book, event_queue })
+    }).collect()
+}
+
+pub fn save_book_routes(routes: &[BookRoute]) -> Result<()> {
+    for route in routes.iter() {
+        route.order_book.try_serialize(&mut &mut route.order_book_info.try_borrow_mut_data()?[..])?;
+        route.event_queue.try_serialize(&mut &mut route.event_queue_info.try_borrow_mut_data()?[..])?;
+    }
+
+    Ok(())
+}
+
+/// Fills each order against resting orders priced better than its sub
+/// market's current curve price and takes what filled off the order's amount,
+/// leaving the rest for the curve. `by_price` orders spend (or raise) their
+/// amount in collateral, the others trade their amount in shares; buys spend
+/// their taker fee out of the amount too. Makers are settled later through
+/// the event queue.
+pub fn route_orders(
+    market: &mut Market,
+    routes: &mut [BookRoute],
+    orders: &mut [Order],
+    fee_schedules: &[FeeSchedule],
+    taker: Pubkey,
+    side: Side,
+    by_price: bool
+) -> Result<Vec<BookFill>> {
+    let maker_side = match side {
+        Side::Bid => Side::Ask,
+        Side::Ask => Side::Bid,
+    };
+
+    orders.iter_mut().zip(fee_schedules.iter()).map(|(order, fee_schedule)| {
+        let route = match routes.iter_mut().find(|route| {
+            route.order_book.sub_market_id == order.sub_market_id && route.order_book.choice_id == order.choice_id
+        }) {
+            Some(route) => route,
+            None => return Ok(BookFill::default()),
+        };
+
+        let spot_price = market.get_sub_market(&order.sub_market_id)?.get_spot_price(&order.choice_id)?;
+        let fills = match (by_price, side) {
+            (true, Side::Bid) => {
+                let max_quote = order.amount - fee_schedule.taker_fee(order.amount);
+                route.order_book.match_order_up_to(taker, side, spot_price, u128::MAX, max_quote)?
+            }
+            (true, Side::Ask) => route.order_book.match_order_up_to(taker, side, spot_price, u128::MAX, order.amount)?,
+            (false, _) => route.order_book.match_order(taker, side, spot_price, order.amount)?,
+        };
+
+        let now = current_timestamp();
+        let mut book_fill = BookFill::default();
+        for fill in fills.iter() {
+            route.event_queue.push_fill(fill, maker_side, taker, now)?;
+            book_fill.shares += fill.shares;
+            book_fill.quote += fill.quote;
+            book_fill.maker_fee += fill.maker_fee;
+        }
+        if book_fill.quote > 0 {
+            book_fill.fee = fee_schedule.taker_fee(book_fill.quote);
+        }
+
+        order.amount -= match (by_price, side) {
+            (true, Side::Bid) => (book_fill.quote + book_fill.fee).min(order.amount),
+            (true, Side::Ask) => book_fill.quote.min(order.amount),
+            (false, _) => book_fill.shares,
+        };
+        msg!("book_fill: shares: {}, quote: {}, fee: {}", book_fill.shares, book_fill.quote, book_fill.fee);
+
+        Ok(book_fill)
+    }).collect()
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, burn, mint_to, Burn, MintTo, Token, TokenAccount};
+
+use crate::{errors::TallyClobErrors, Market, MarketPortfolio};
+
+pub fn get_share_mint_address(market: &Pubkey, sub_market_id: u64, choice_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"share_mints".as_ref(), market.as_ref(), sub_market_id.to_le_bytes().as_ref(), choice_id.to_le_bytes().as_ref()],
+        &crate::ID
+    )
+}
+
+/// Order book pairs come first in the remaining accounts and
+/// `[share_mint, share_account]` pairs after them, so the first account the
+/// token program owns is where the share accounts start.
+pub fn split_share_accounts<'a, 'info>(accounts: &'a [AccountInfo<'info>]) -> (&'a [AccountInfo<'info>], &'a [AccountInfo<'info>]) {
+    let index = accounts.iter()
+        .position(|account| account.owner == &token::ID)
+        .unwrap_or(accounts.len());
+
+    accounts.split_at(index)
+}
+
+/// Moves positions in tokenized sub markets between a `MarketPortfolio` and
+/// the owner's share token accounts. Each share is one base unit of its
+/// choice's mint, so amounts carry over as is.
+pub struct ShareTokenizer<'a, 'info> {
+    pub token_program: &'a Program<'info, Token>,
+    pub owner: &'a AccountInfo<'info>,
+    pub market: Pubkey,
+    pub share_accounts: &'a [AccountInfo<'info>],
+}
+
+impl<'a, 'info> ShareTokenizer<'a, 'info> {
+    fn find_share_accounts(&self, sub_market_id: u64, choice_id: u64) -> Result<(&'a AccountInfo<'info>, &'a AccountInfo<'info>, u8)> {
+        let (mint_key, mint_bump) = get_share_mint_address(&self.market, sub_market_id, choice_id);
+
+        let index = self.share_accounts.iter()
+            .position(|account| account.key() == mint_key)
+            .ok_or(TallyClobErrors::InvalidShareAccount)?;
+        let share_mint = &self.share_accounts[index];
+        let share_account = self.share_accounts.get(index + 1).ok_or(TallyClobErrors::InvalidShareAccount)?;
+        require!(share_mint.is_writable && share_account.is_writable, TallyClobErrors::InvalidShareAccount);
+        require!(share_account.owner == &token::ID, TallyClobErrors::InvalidShareAccount);
+
+        let token_account = TokenAccount::try_deserialize(&mut &share_account.try_borrow_data()?[..])?;
+        require!(token_account.mint == mint_key && token_account.owner == self.owner.key(), TallyClobErrors::InvalidShareAccount);
+
+        Ok((share_mint, share_account, mint_bump))
+    }
+
+    /// Mints the owner's whole position in each tokenized choice out of the
+    /// portfolio. Choices in other sub markets are left alone.
+    pub fn wrap_positions(&self, market: &mut Market, market_portfolio: &mut MarketPortfolio, choices: &[(u64, u64)]) -> Result<()> {
+        for (sub_market_id, choice_id) in choices.iter() {
+            if !market.get_sub_market(sub_market_id)?.is_tokenized() { continue }
+
+            let shares = market_portfolio.get_choice_shares(sub_market_id, choice_id)?;
+            if shares == 0 { continue }
+
+            let (share_mint, share_account, mint_bump) = self.find_share_accounts(*sub_market_id, *choice_id)?;
+            market_portfolio.sell_from_portfolio(sub_market_id, choice_id, shares)?;
+
+            let sub_market_seed = sub_market_id.to_le_bytes();
+            let choice_seed = choice_id.to_le_bytes();
+            let seeds: &[&[u8]] = &[b"share_mints".as_ref(), self.market.as_ref(), sub_market_seed.as_ref(), choice_seed.as_ref(), &[mint_bump]];
+            let signer_seeds = &[seeds];
+
+            let cpi_accounts = MintTo {
+                mint: share_mint.clone(),
+                to: share_account.clone(),
+                authority: share_mint.clone()
+            };
+            mint_to(
+                CpiContext::new_with_signer(self.token_program.to_account_info(), cpi_accounts, signer_seeds),
+                u64::try_from(shares).map_err(|_| error!(TallyClobErrors::NotAValidOrder))?
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Burns the owner's share tokens for each tokenized choice back into the
+    /// portfolio so they can be sold or claimed.
+    pub fn unwrap_positions(&self, market: &mut Market, market_portfolio: &mut MarketPortfolio, choices: &[(u64, u64)]) -> Result<()> {
+        for (sub_market_id, choice_id) in choices.iter() {
+            if !market.get_sub_market(sub_market_id)?.is_tokenized() { continue }
+
+            let (share_mint, share_account, _) = self.find_share_accounts(*sub_market_id, *choice_id)?;
+            let token_amount = TokenAccount::try_deserialize(&mut &share_account.try_borrow_data()?[..])?.amount;
+            if token_amount == 0 { continue }
+
+            let cpi_accounts = Burn {
+                mint: share_mint.clone(),
+                from: share_account.clone(),
+                authority: self.owner.clone()
+            };
+            burn(CpiContext::new(self.token_program.to_account_info(), cpi_accounts), token_amount)?;
+
+            market_portfolio.add_to_portfolio(sub_market_id, choice_id, token_amount as u128)?;
+        }
+
+        Ok(())
+    }
+}
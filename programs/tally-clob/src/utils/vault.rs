@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+
+use crate::errors::TallyClobErrors;
+
+/// Moves tokens out of the program vault, signing for it with its seeds.
+pub fn transfer_from_vault<'info>(
+    token_program: &Program<'info, Token>,
+    vault: &Account<'info, TokenAccount>,
+    destination: &Account<'info, TokenAccount>,
+    vault_bump: u8,
+    amount: u64
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(())
+    }
+
+    let mint = vault.mint;
+    let seeds: &[&[u8]] = &[b"vault".as_ref(), mint.as_ref(), &[vault_bump]];
+    let signer_seeds = &[seeds];
+
+    let cpi_accounts = Transfer {
+        from: vault.to_account_info(),
+        to: destination.to_account_info(),
+        authority: vault.to_account_info()
+    };
+
+    transfer(
+        CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds),
+        amount
+    )
+}
+
+/// Moves tokens from an account `authority` owns into the program vault.
+pub fn transfer_to_vault<'info>(
+    token_program: &Program<'info, Token>,
+    source: &Account<'info, TokenAccount>,
+    vault: &Account<'info, TokenAccount>,
+    authority: &Signer<'info>,
+    amount: u64
+) -> Result<()> {
+    require!(source.owner == authority.key(), TallyClobErrors::NotAuthorized);
+
+    let cpi_accounts = Transfer {
+        from: source.to_account_info(),
+        to: vault.to_account_info(),
+        authority: authority.to_account_info()
+    };
+
+    transfer(
+        CpiContext::new(token_program.to_account_info(), cpi_accounts),
+        amount
+    )
+}
+
+
+/// Internal balances are tracked with 9 decimals regardless of the mint.
+pub const BALANCE_DECIMALS: u8 = 9;
+
+pub fn to_token_amount(amount: u128, decimals: u8) -> Result<u64> {
+    require!(decimals <= BALANCE_DECIMALS, TallyClobErrors::NotUSDC);
+
+    let factor = 10_u128.pow((BALANCE_DECIMALS - decimals) as u32);
+
+    u64::try_from(amount / factor).map_err(|_| error!(TallyClobErrors::NotAValidOrder))
+}
+
+pub fn to_balance_amount(token_amount: u64, decimals: u8) -> Result<u128> {
+    require!(decimals <= BALANCE_DECIMALS, TallyClobErrors::NotUSDC);
+
+    let factor = 10_u128.pow((BALANCE_DECIMALS - decimals) as u32);
+
+    Ok(token_amount as u128 * factor)
+}
@@ -4,12 +4,16 @@ import {  PublicKey } from "@solana/web3.js";
 import { expect } from "chai";
 import { getAuthorizedUserKeypair, getOwnerKeypair } from "./utils/wallets";
 import { getProgram } from "./utils/program";
+import { getConfigPDA } from "./utils/pdas";
+import { initConfigIfNeeded, initFeeLedgerIfNeeded, initVaultIfNeeded } from "./utils/config";
+import { ALL_ROLES, ROLE_RESOLVER } from "./utils/roles";
 
 describe("authorize user instruction", () => {
 
   const provider = anchor.AnchorProvider.env()
   anchor.setProvider(provider);
   const program = getProgram();
+  const configPDA = getConfigPDA(program);
 
   let userKeypair = getAuthorizedUserKeypair();
   let owner = getOwnerKeypair();
@@ -22,12 +26,19 @@ describe("authorize user instruction", () => {
     program.programId
   );
 
+  before(async () => {
+    const mint = new PublicKey("5DUWZLh3zPKAAJKu7ftMJJrkBrKnq3zHPPmguzVkhSes");
+    await initConfigIfNeeded(program, mint);
+    await initVaultIfNeeded(program, mint);
+    await initFeeLedgerIfNeeded(program);
+  });
+
   it ("unauthorized authorize fails", async () => {
     try {
         await program.methods
-        .authorizeUser(true, userKeypair.publicKey)
+        .authorizeUser(ALL_ROLES, null, null, userKeypair.publicKey)
         .signers([userKeypair])
-        .accounts({signer: userKeypair.publicKey, authorizedUser: authorizedUserPda })
+        .accounts({ config: configPDA, signer: userKeypair.publicKey, authorizedUser: authorizedUserPda })
         .rpc()
 
     } catch (err) {
@@ -39,36 +50,52 @@ describe("authorize user instruction", () => {
   })
   
   it ("authorizes a user", async () => {
-    await program.methods.authorizeUser(true, userKeypair.publicKey)
+    await program.methods.authorizeUser(ALL_ROLES, null, null, userKeypair.publicKey)
         .signers([owner])
-        .accounts({signer: owner.publicKey, authorizedUser: authorizedUserPda })
+        .accounts({ config: configPDA, signer: owner.publicKey, authorizedUser: authorizedUserPda })
         .rpc()
 
         const user = await program.account.authorizedUser.fetch(authorizedUserPda);
 
-        expect(user.authorized).to.equal(true);
+        expect(user.roles).to.equal(ALL_ROLES);
   })
 
   it ("unauthorizes a user", async () => {
-    await program.methods.authorizeUser(false, userKeypair.publicKey)
+    await program.methods.authorizeUser(0, null, null, userKeypair.publicKey)
+        .signers([owner])
+        .accounts({ config: configPDA, signer: owner.publicKey, authorizedUser: authorizedUserPda })
+        .rpc()
+
+        const user = await program.account.authorizedUser.fetch(authorizedUserPda);
+
+        expect(user.roles).to.equal(0);
+  })
+
+  it ("authorizes a scoped, expiring resolver", async () => {
+    const market = anchor.web3.Keypair.generate().publicKey;
+    const expiresAt = new anchor.BN(Math.floor(Date.now() / 1000) + 60 * 60);
+
+    await program.methods.authorizeUser(ROLE_RESOLVER, market, expiresAt, userKeypair.publicKey)
         .signers([owner])
-        .accounts({signer: owner.publicKey, authorizedUser: authorizedUserPda })
+        .accounts({ config: configPDA, signer: owner.publicKey, authorizedUser: authorizedUserPda })
         .rpc()
 
         const user = await program.account.authorizedUser.fetch(authorizedUserPda);
 
-        expect(user.authorized).to.equal(false);
+        expect(user.roles).to.equal(ROLE_RESOLVER);
+        expect(user.market.toBase58()).to.equal(market.toBase58());
+        expect(user.expiresAt.toNumber()).to.equal(expiresAt.toNumber());
   })
 
   it ("authorizes a user again", async () => {
-    await program.methods.authorizeUser(true, userKeypair.publicKey)
+    await program.methods.authorizeUser(ALL_ROLES, null, null, userKeypair.publicKey)
         .signers([owner])
-        .accounts({signer: owner.publicKey, authorizedUser: authorizedUserPda })
+        .accounts({ config: configPDA, signer: owner.publicKey, authorizedUser: authorizedUserPda })
         .rpc()
 
         const user = await program.account.authorizedUser.fetch(authorizedUserPda);
 
-        expect(user.authorized).to.equal(true);
+        expect(user.roles).to.equal(ALL_ROLES);
   })
 
 });
@@ -0,0 +1,128 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  getAuthorizedUserKeypair,
+  getFeeManagerKeypair,
+  getOwnerKeypair,
+  getUserKeypair,
+  getWalletManagerKeypair,
+} from "./utils/wallets";
+import { getProgram } from "./utils/program";
+import { getAuthorizedPDA, getConfigPDA } from "./utils/pdas";
+import { initConfigIfNeeded, initVaultIfNeeded } from "./utils/config";
+import { ALL_ROLES } from "./utils/roles";
+
+describe("program config", () => {
+  const MINT = new PublicKey("5DUWZLh3zPKAAJKu7ftMJJrkBrKnq3zHPPmguzVkhSes");
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = getProgram();
+  const configPDA = getConfigPDA(program);
+
+  let owner = getOwnerKeypair();
+  let userKeypair = getUserKeypair();
+  let authorizedKeypair = getAuthorizedUserKeypair();
+  const authorizedUserPda = getAuthorizedPDA(authorizedKeypair.publicKey, program);
+
+  before(async () => {
+    await initConfigIfNeeded(program, MINT);
+    await initVaultIfNeeded(program, MINT);
+  });
+
+  it("stores the roles and mint", async () => {
+    const config = await program.account.programConfig.fetch(configPDA);
+
+    expect(config.owner.toBase58()).to.equal(owner.publicKey.toBase58());
+    expect(config.walletManager.toBase58()).to.equal(getWalletManagerKeypair().publicKey.toBase58());
+    expect(config.feeOwner.toBase58()).to.equal(getFeeManagerKeypair().publicKey.toBase58());
+    expect(config.usdcMint.toBase58()).to.equal(MINT.toBase58());
+  });
+
+  it("unauthorized update fails", async () => {
+    try {
+      await program.methods
+        .updateConfig({
+          walletManager: userKeypair.publicKey,
+          clobManager: null,
+          usdcMint: null,
+          disputePeriod: null,
+          proposalBond: null,
+          feeSchedule: null,
+        })
+        .signers([userKeypair])
+        .accounts({ signer: userKeypair.publicKey, config: configPDA })
+        .rpc();
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      let expectedMsg =
+        "You do not have the authorization to use this instruction.";
+      expect(error.error.errorMessage).to.equal(expectedMsg);
+    }
+  });
+
+  it("fee admin updates the fee owner", async () => {
+    const feeOwner = getFeeManagerKeypair().publicKey;
+
+    await program.methods
+      .authorizeUser(ALL_ROLES, null, null, authorizedKeypair.publicKey)
+      .signers([owner])
+      .accounts({ config: configPDA, signer: owner.publicKey, authorizedUser: authorizedUserPda })
+      .rpc();
+
+    await program.methods
+      .setFeeOwner(feeOwner)
+      .signers([authorizedKeypair])
+      .accounts({ signer: authorizedKeypair.publicKey, config: configPDA, authorizedUser: authorizedUserPda })
+      .rpc();
+
+    const config = await program.account.programConfig.fetch(configPDA);
+
+    expect(config.feeOwner.toBase58()).to.equal(feeOwner.toBase58());
+  });
+
+  it("fails to change the collateral mint once the vault is initialized", async () => {
+    try {
+      await program.methods
+        .updateConfig({
+          walletManager: null,
+          clobManager: null,
+          usdcMint: anchor.web3.Keypair.generate().publicKey,
+          disputePeriod: null,
+          proposalBond: null,
+          feeSchedule: null,
+        })
+        .signers([owner])
+        .accounts({ signer: owner.publicKey, config: configPDA })
+        .rpc();
+      expect.fail("update should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal("The collateral mint can't change once the vault is initialized.");
+    }
+
+    const config = await program.account.programConfig.fetch(configPDA);
+    expect(config.usdcMint.toBase58()).to.equal(MINT.toBase58());
+  });
+
+  it("pauser pauses and unpauses the program", async () => {
+    await program.methods
+      .setPaused(true)
+      .signers([authorizedKeypair])
+      .accounts({ signer: authorizedKeypair.publicKey, config: configPDA, authorizedUser: authorizedUserPda })
+      .rpc();
+
+    let config = await program.account.programConfig.fetch(configPDA);
+    expect(config.paused).to.equal(true);
+
+    await program.methods
+      .setPaused(false)
+      .signers([authorizedKeypair])
+      .accounts({ signer: authorizedKeypair.publicKey, config: configPDA, authorizedUser: authorizedUserPda })
+      .rpc();
+
+    config = await program.account.programConfig.fetch(configPDA);
+    expect(config.paused).to.equal(false);
+  });
+});
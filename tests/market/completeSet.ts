@@ -0,0 +1,144 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import {
+  getAuthorizedUserKeypair,
+  getUserKeypair,
+  getWalletManagerKeypair,
+} from "../utils/wallets";
+import { getProgram } from "../utils/program";
+import {
+  getAuthorizedPDA,
+  getConfigPDA,
+  getMarketPDA,
+  getMarketPortfolioPDA,
+  getUserPDA,
+  getVaultPDA,
+} from "../utils/pdas";
+
+describe("complete sets", () => {
+  const MINT = new PublicKey("5DUWZLh3zPKAAJKu7ftMJJrkBrKnq3zHPPmguzVkhSes");
+
+  const program = getProgram();
+
+  const marketKeypair = anchor.web3.Keypair.generate();
+
+  const authorizedKeypair = getAuthorizedUserKeypair();
+  const walletManager = getWalletManagerKeypair();
+  const userKeypair = getUserKeypair();
+
+  const configPDA = getConfigPDA(program);
+  const vaultPDA = getVaultPDA(MINT, program);
+  const marketPDA = getMarketPDA(marketKeypair.publicKey, program);
+  const authorizedUserPda = getAuthorizedPDA(authorizedKeypair.publicKey, program);
+
+  const userPDA = getUserPDA(userKeypair.publicKey, program);
+  const marketPortfolioPDA = getMarketPortfolioPDA(marketPDA, userPDA, program);
+
+  const from = getAssociatedTokenAddressSync(MINT, walletManager.publicKey);
+
+  const now = new Date();
+
+  const initMarketData = [
+    {
+      id: new anchor.BN(1),
+      initPot: new anchor.BN(100 * Math.pow(10,9)),
+      curve: { constantProduct: {} },
+      choiceIds: [new anchor.BN(1), new anchor.BN(2), new anchor.BN(3)],
+      fairLaunchStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 3),
+      fairLaunchEnd: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 2),
+      tradingStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60),
+      tradingEnd: new anchor.BN(now.valueOf() / 1000 + 60 * 60),
+    },
+  ];
+
+  const accounts = {
+    signer: userKeypair.publicKey,
+    config: configPDA,
+    user: userPDA,
+    market: marketPDA,
+    marketPortfolio: marketPortfolioPDA,
+  };
+
+  before(async () => {
+    await program.methods
+      .initMarket(initMarketData, marketKeypair.publicKey)
+      .signers([authorizedKeypair])
+      .accounts({
+        config: configPDA,
+        signer: authorizedKeypair.publicKey,
+        market: marketPDA,
+        authorizedUser: authorizedUserPda,
+      })
+      .rpc();
+
+    await program.methods
+      .deposit(new anchor.BN(5 * Math.pow(10,9)))
+      .signers([walletManager])
+      .accounts({
+        config: configPDA,
+        user: userPDA,
+        signer: walletManager.publicKey,
+        fromUsdcAccount: from,
+        vault: vaultPDA,
+        mint: MINT,
+      })
+      .rpc();
+  });
+
+  it("mints complete sets without moving the pots", async () => {
+    const before = await program.account.user.fetch(userPDA);
+
+    await program.methods
+      .mintCompleteSet(new anchor.BN(1), new anchor.BN(2 * Math.pow(10,9)))
+      .signers([userKeypair])
+      .accounts(accounts)
+      .rpc();
+
+    const user = await program.account.user.fetch(userPDA);
+    const market = await program.account.market.fetch(marketPDA);
+    const marketPortfolio = await program.account.marketPortfolio.fetch(marketPortfolioPDA);
+
+    expect(before.balance.sub(user.balance).toNumber() / Math.pow(10,9)).to.equal(2);
+    expect(market.subMarkets[0].completeSetCollateral.toNumber() / Math.pow(10,9)).to.equal(2);
+    market.subMarkets[0].choices.forEach((choice) => {
+      expect(choice.mintedShares.toNumber() / Math.pow(10,9)).to.equal(2);
+      expect(choice.potShares.toNumber() / Math.pow(10,9)).to.equal(100);
+    });
+    marketPortfolio.subMarketPortfolio[0].choicePortfolio.forEach((choice) => {
+      expect(choice.shares.toNumber() / Math.pow(10,9)).to.equal(2);
+    });
+  });
+
+  it("redeems a complete set for its collateral", async () => {
+    const before = await program.account.user.fetch(userPDA);
+
+    await program.methods
+      .redeemCompleteSet(new anchor.BN(1), new anchor.BN(1 * Math.pow(10,9)))
+      .signers([userKeypair])
+      .accounts(accounts)
+      .rpc();
+
+    const user = await program.account.user.fetch(userPDA);
+    const market = await program.account.market.fetch(marketPDA);
+
+    expect(user.balance.sub(before.balance).toNumber() / Math.pow(10,9)).to.equal(1);
+    expect(market.subMarkets[0].completeSetCollateral.toNumber() / Math.pow(10,9)).to.equal(1);
+    expect(market.subMarkets[0].choices[0].mintedShares.toNumber() / Math.pow(10,9)).to.equal(1);
+  });
+
+  it("fails to redeem more sets than owned", async () => {
+    try {
+      await program.methods
+        .redeemCompleteSet(new anchor.BN(1), new anchor.BN(2 * Math.pow(10,9)))
+        .signers([userKeypair])
+        .accounts(accounts)
+        .rpc();
+      expect.fail("redeem should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal("Requested shares to sell greater than owned shares.");
+    }
+  });
+});
@@ -0,0 +1,130 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  getAuthorizedUserKeypair,
+  getFeeManagerKeypair,
+} from "../utils/wallets";
+import { getProgram } from "../utils/program";
+import {
+  getAuthorizedPDA,
+  getConfigPDA,
+  getFeeLedgerPDA,
+  getMarketPDA,
+} from "../utils/pdas";
+
+describe("dispute resolution", () => {
+  const program = getProgram();
+
+  const marketKeypair = anchor.web3.Keypair.generate();
+
+  const authorizedKeypair = getAuthorizedUserKeypair();
+  const feeManagerKeypair = getFeeManagerKeypair();
+
+  const configPDA = getConfigPDA(program);
+  const feeLedgerPDA = getFeeLedgerPDA(program);
+  const marketPDA = getMarketPDA(marketKeypair.publicKey, program);
+  const authorizedUserPda = getAuthorizedPDA(authorizedKeypair.publicKey, program);
+
+  const now = new Date();
+
+  const initMarketData = [
+    {
+      id: new anchor.BN(1),
+      initPot: new anchor.BN(100 * Math.pow(10,9)),
+      curve: { constantProduct: {} },
+      choiceIds: [new anchor.BN(1), new anchor.BN(2)],
+      fairLaunchStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 3),
+      fairLaunchEnd: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 2),
+      tradingStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60),
+      tradingEnd: new anchor.BN(now.valueOf() / 1000 + 60 * 60),
+    },
+  ];
+
+  const feeAccounts = {
+    config: configPDA,
+    market: marketPDA,
+    feeLedger: feeLedgerPDA,
+  };
+
+  const disputeResolution = () =>
+    program.methods
+      .disputeResolution(new anchor.BN(1))
+      .signers([authorizedKeypair])
+      .accounts({
+        signer: authorizedKeypair.publicKey,
+        authorizedUser: authorizedUserPda,
+        market: marketPDA,
+      })
+      .rpc();
+
+  before(async () => {
+    await program.methods
+      .initMarket(initMarketData, marketKeypair.publicKey)
+      .signers([authorizedKeypair])
+      .accounts({
+        config: configPDA,
+        signer: authorizedKeypair.publicKey,
+        market: marketPDA,
+        authorizedUser: authorizedUserPda,
+      })
+      .rpc();
+
+    await program.methods
+      .resolveMarket(new anchor.BN(1), new anchor.BN(1))
+      .signers([authorizedKeypair])
+      .accounts({
+        config: configPDA,
+        signer: authorizedKeypair.publicKey,
+        market: marketPDA,
+        authorizedUser: authorizedUserPda,
+      })
+      .rpc();
+  });
+
+  it("fails to finalize during the dispute period", async () => {
+    try {
+      await program.methods
+        .finalizeResolution(new anchor.BN(1))
+        .signers([feeManagerKeypair])
+        .accounts({ signer: feeManagerKeypair.publicKey, ...feeAccounts })
+        .rpc();
+      expect.fail("finalize should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal("Resolution is still in its dispute period, check back later.");
+    }
+  });
+
+  it("disputes a proposed resolution", async () => {
+    await disputeResolution();
+
+    const market = await program.account.market.fetch(marketPDA);
+    expect(market.subMarkets[0].resolution).to.deep.equal({ disputed: {} });
+  });
+
+  it("fails to dispute a resolution twice", async () => {
+    try {
+      await disputeResolution();
+      expect.fail("dispute should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal("Sub market resolution can't move on from its current status.");
+    }
+  });
+
+  it("arbiter corrects the outcome", async () => {
+    await program.methods
+      .correctResolution(new anchor.BN(1), new anchor.BN(2))
+      .signers([authorizedKeypair])
+      .accounts({ signer: authorizedKeypair.publicKey, authorizedUser: authorizedUserPda, ...feeAccounts })
+      .rpc();
+
+    const market = await program.account.market.fetch(marketPDA);
+    expect(market.subMarkets[0].resolution).to.deep.equal({ finalized: {} });
+    expect(market.subMarkets[0].choices[0].payoutNumerator.toNumber()).to.equal(0);
+    expect(market.subMarkets[0].choices[1].payoutNumerator.toNumber()).to.equal(Math.pow(10,9));
+    // only the now losing pot pays the resolution fee
+    expect(market.subMarkets[0].choices[0].usdcPot.toNumber() / Math.pow(10,9)).to.equal(45);
+    expect(market.subMarkets[0].choices[1].usdcPot.toNumber() / Math.pow(10,9)).to.equal(50);
+  });
+});
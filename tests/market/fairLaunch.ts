@@ -6,7 +6,6 @@ import {
   getAssociatedTokenAccount,
   getAuthorizedUserKeypair,
   getClobManagerKeypair,
-  getFeeManagerKeypair,
   getUserKeypair,
   getWalletManagerKeypair,
   getWalletManagerTokenAccount,
@@ -14,6 +13,9 @@ import {
 import { getProgram } from "../utils/program";
 import {
   getAuthorizedPDA,
+  getConfigPDA,
+  getFeeLedgerPDA,
+  getVaultPDA,
   getMarketPDA,
   getMarketPortfolioPDA,
   getUserPDA,
@@ -29,9 +31,14 @@ describe("fair launch", () => {
 
   let authorizedKeypair = getAuthorizedUserKeypair();
   let walletManagerKeypair = getWalletManagerKeypair();
-  let feeManagerKeypair = getFeeManagerKeypair()
   let userKeypair = getUserKeypair();
 
+  const configPDA = getConfigPDA(program);
+
+  const feeLedgerPDA = getFeeLedgerPDA(program);
+
+  const vaultPDA = getVaultPDA(MINT, program);
+
   const marketPDA = getMarketPDA(marketKeypair.publicKey, program);
 
   const authorizedUserPda = getAuthorizedPDA(
@@ -45,10 +52,6 @@ describe("fair launch", () => {
     MINT,
     walletManagerKeypair.publicKey
   );
-  const feeAccount = getAssociatedTokenAddressSync(
-    MINT,
-    feeManagerKeypair.publicKey
-  );
 
   const marketPortfolioPDA = getMarketPortfolioPDA(
     marketPDA,
@@ -62,6 +65,7 @@ describe("fair launch", () => {
     {
       id: new anchor.BN(2),
       initPot: new anchor.BN(100 * Math.pow(10,9)),
+      curve: { constantProduct: {} },
       choiceIds: [new anchor.BN(1),new anchor.BN(2)],
       fairLaunchStart: new anchor.BN((now.valueOf() / 1000) - 60 * 60),
       fairLaunchEnd: new anchor.BN((now.valueOf() / 1000) + 60 * 60),
@@ -76,7 +80,7 @@ describe("fair launch", () => {
     await program.methods
       .initWallet(userKeypair.publicKey)
       .signers([walletManagerKeypair])
-      .accounts({ user: userPDA, signer: walletManagerKeypair.publicKey })
+      .accounts({ config: configPDA, user: userPDA, signer: walletManagerKeypair.publicKey })
       .rpc()
       .catch(_ => {});
 
@@ -84,6 +88,7 @@ describe("fair launch", () => {
       .initMarket(initMarketData, marketKeypair.publicKey)
       .signers([authorizedKeypair])
       .accounts({
+        config: configPDA,
         signer: authorizedKeypair.publicKey,
         market: marketPDA,
         authorizedUser: authorizedUserPda,
@@ -98,26 +103,31 @@ describe("fair launch", () => {
         .withdrawFromBalance(user.balance)
         .signers([walletManagerKeypair])
         .accounts({
+          config: configPDA,
           user: userPDA,
           signer: walletManagerKeypair.publicKey,
           mint: MINT,
-          fromUsdcAccount: getWalletManagerTokenAccount(MINT),
+          vault: vaultPDA,
           toUsdcAccount: await getAssociatedTokenAccount(
             MINT,
             userKeypair.publicKey
           ),
-          feeUsdcAccount: feeAccount
+          feeLedger: feeLedgerPDA
         })
         .rpc()
         .catch(err => console.log(err));
     }
 
     await program.methods
-      .addToBalance(new anchor.BN(10 * Math.pow(10,9)))
+      .deposit(new anchor.BN(10 * Math.pow(10,9)))
       .signers([walletManagerKeypair])
       .accounts({
+        config: configPDA,
         user: userPDA,
         signer: walletManagerKeypair.publicKey,
+        fromUsdcAccount: from,
+        vault: vaultPDA,
+        mint: MINT,
       })
       .rpc()
       .catch(err => console.log(err));
@@ -141,15 +151,16 @@ describe("fair launch", () => {
             amount: new anchor.BN(5 * Math.pow(10,9)),
             subMarketId: new anchor.BN(2),
             choiceId: new anchor.BN(1),
-            requestedPricePerShare: 0.5 ,
           },
         ])
         .signers([userKeypair])
         .accounts({
+          config: configPDA,
           signer: userKeypair.publicKey,
           user: userPDA,
           market: marketPDA,
           marketPortfolio: marketPortfolioPDA,
+          feeLedger: feeLedgerPDA,
         })
         .rpc();
     } catch (err) {
@@ -168,15 +179,16 @@ describe("fair launch", () => {
             amount: new anchor.BN(100 * Math.pow(10,9)),
             subMarketId: new anchor.BN(2),
             choiceId: new anchor.BN(1),
-            requestedPricePerShare: 0.5,
           },
         ])
         .signers([walletManagerKeypair])
         .accounts({
+          config: configPDA,
           signer: walletManagerKeypair.publicKey,
           user: userPDA,
           market: marketPDA,
           marketPortfolio: marketPortfolioPDA,
+          feeLedger: feeLedgerPDA,
         })
         .rpc();
     } catch (err) {
@@ -195,16 +207,17 @@ describe("fair launch", () => {
           amount: new anchor.BN(5 * Math.pow(10,9)),
           subMarketId: new anchor.BN(2),
           choiceId: new anchor.BN(1),
-          requestedPricePerShare: 0.5,
         },
       ])
       .signers([walletManagerKeypair])
       .preInstructions([additionalComputeBudgetInstruction])
       .accounts({
+        config: configPDA,
         signer: walletManagerKeypair.publicKey,
         user: userPDA,
         market: marketPDA,
         marketPortfolio: marketPortfolioPDA,
+        feeLedger: feeLedgerPDA,
       })
       .rpc().catch(e => console.log(e));
 
@@ -215,8 +228,8 @@ describe("fair launch", () => {
 
     expect(user.balance.toNumber() / Math.pow(10,9)).to.equal(5)
     expect(market.subMarkets[0].choices.map(choice => choice.usdcPot.toNumber()).reduce((sum, current) => sum + current / Math.pow(10,9),0)).to.equal(105);
-    expect(market.subMarkets[0].choices[0].potShares.toNumber() / Math.pow(10,9)).to.equal(100.113571928)
-    expect(market.subMarkets[0].choices[1].potShares.toNumber() / Math.pow(10,9)).to.equal(110.124928994)
+    expect(market.subMarkets[0].choices[0].potShares.toNumber() / Math.pow(10,9)).to.equal(100.113571871)
+    expect(market.subMarkets[0].choices[1].potShares.toNumber() / Math.pow(10,9)).to.equal(110.124929058)
     expect(market.subMarkets[0].choices[0].usdcPot.toNumber() / Math.pow(10,9)).to.equal(55)
     expect(market.subMarkets[0].choices[0].mintedShares.toNumber() / Math.pow(10,9)).to.equal(5)
     expect(market.subMarkets[0].choices[0].fairLaunchPot.toNumber() / Math.pow(10,9)).to.equal(55)
@@ -231,18 +244,16 @@ describe("fair launch", () => {
             amount: new anchor.BN(1 * Math.pow(10,9)),
             subMarketId: new anchor.BN(2),
             choiceId: new anchor.BN(1),
-            requestedPricePerShare: 0.5,
           },
         ])
         .signers([walletManagerKeypair])
         .accounts({
+          config: configPDA,
           signer: walletManagerKeypair.publicKey,
           user: userPDA,
           market: marketPDA,
-          mint: MINT,
           marketPortfolio: marketPortfolioPDA,
-          fromUsdcAccount: from,
-          feeUsdcAccount: feeAccount
+          feeLedger: feeLedgerPDA
         })
         .rpc();
     } catch (err) {
@@ -262,18 +273,16 @@ describe("fair launch", () => {
             amount: new anchor.BN(1 * Math.pow(10,9)),
             subMarketId: new anchor.BN(2),
             choiceId: new anchor.BN(1),
-            requestedPricePerShare: 0.5 ,
           },
         ])
         .signers([walletManagerKeypair])
         .accounts({
+          config: configPDA,
           signer: walletManagerKeypair.publicKey,
           user: userPDA,
           market: marketPDA,
-          mint: MINT,
           marketPortfolio: marketPortfolioPDA,
-          fromUsdcAccount: from,
-          feeUsdcAccount: feeAccount
+          feeLedger: feeLedgerPDA
         })
         .rpc();
     } catch (err) {
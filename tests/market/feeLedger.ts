@@ -0,0 +1,192 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { getAccount, getAssociatedTokenAddressSync, getMint } from "@solana/spl-token";
+import {
+  getAuthorizedUserKeypair,
+  getFeeManagerKeypair,
+  getWalletManagerKeypair,
+} from "../utils/wallets";
+import { getProgram } from "../utils/program";
+import {
+  getAuthorizedPDA,
+  getConfigPDA,
+  getFeeLedgerPDA,
+  getMarketPDA,
+  getMarketPortfolioPDA,
+  getUserPDA,
+  getVaultPDA,
+} from "../utils/pdas";
+import { additionalComputeBudgetInstruction } from "../utils/extra-cpu";
+
+describe("fee ledger", () => {
+  const MINT = new PublicKey("5DUWZLh3zPKAAJKu7ftMJJrkBrKnq3zHPPmguzVkhSes");
+
+  const program = getProgram();
+  const provider = anchor.AnchorProvider.env();
+
+  const marketKeypair = anchor.web3.Keypair.generate();
+  const traderKeypair = anchor.web3.Keypair.generate();
+
+  const authorizedKeypair = getAuthorizedUserKeypair();
+  const feeManagerKeypair = getFeeManagerKeypair();
+  const walletManager = getWalletManagerKeypair();
+
+  const configPDA = getConfigPDA(program);
+  const feeLedgerPDA = getFeeLedgerPDA(program);
+  const vaultPDA = getVaultPDA(MINT, program);
+  const marketPDA = getMarketPDA(marketKeypair.publicKey, program);
+  const authorizedUserPda = getAuthorizedPDA(authorizedKeypair.publicKey, program);
+  const traderPDA = getUserPDA(traderKeypair.publicKey, program);
+  const marketPortfolioPDA = getMarketPortfolioPDA(marketPDA, traderPDA, program);
+
+  const from = getAssociatedTokenAddressSync(MINT, walletManager.publicKey);
+  const feeAccount = getAssociatedTokenAddressSync(MINT, feeManagerKeypair.publicKey);
+
+  const now = new Date();
+
+  const initMarketData = [
+    {
+      id: new anchor.BN(1),
+      initPot: new anchor.BN(100 * Math.pow(10,9)),
+      curve: { constantProduct: {} },
+      choiceIds: [new anchor.BN(1), new anchor.BN(2)],
+      fairLaunchStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 3),
+      fairLaunchEnd: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 2),
+      tradingStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60),
+      tradingEnd: new anchor.BN(now.valueOf() / 1000 + 60 * 60),
+    },
+  ];
+
+  const sweepFees = () =>
+    program.methods
+      .sweepFees()
+      .signers([authorizedKeypair])
+      .accounts({
+        signer: authorizedKeypair.publicKey,
+        config: configPDA,
+        authorizedUser: authorizedUserPda,
+        feeLedger: feeLedgerPDA,
+        vault: vaultPDA,
+        feeUsdcAccount: feeAccount,
+        mint: MINT,
+      })
+      .rpc();
+
+  before(async () => {
+    await program.methods
+      .initWallet(traderKeypair.publicKey)
+      .signers([walletManager])
+      .accounts({
+        config: configPDA,
+        user: traderPDA,
+        signer: walletManager.publicKey,
+        referrer: null,
+      })
+      .rpc();
+
+    await program.methods
+      .initMarket(initMarketData, marketKeypair.publicKey)
+      .signers([authorizedKeypair])
+      .accounts({
+        config: configPDA,
+        signer: authorizedKeypair.publicKey,
+        market: marketPDA,
+        authorizedUser: authorizedUserPda,
+        creator: null,
+      })
+      .rpc();
+
+    await program.methods
+      .setMarketFeeSchedule(null, {
+        tradeBps: 100,
+        takerBps: 0,
+        makerBps: 0,
+        withdrawalBps: 500,
+        resolutionBps: 1000,
+        fairLaunchBps: 0,
+        minFee: new anchor.BN(0),
+        maxFee: new anchor.BN(Math.pow(10,9)),
+      })
+      .signers([authorizedKeypair])
+      .accounts({
+        signer: authorizedKeypair.publicKey,
+        authorizedUser: authorizedUserPda,
+        market: marketPDA,
+      })
+      .rpc();
+
+    await program.methods
+      .deposit(new anchor.BN(10 * Math.pow(10,9)))
+      .signers([walletManager])
+      .accounts({
+        config: configPDA,
+        user: traderPDA,
+        signer: walletManager.publicKey,
+        fromUsdcAccount: from,
+        vault: vaultPDA,
+        mint: MINT,
+      })
+      .rpc();
+  });
+
+  it("accrues the protocol's fee on the ledger", async () => {
+    const before = await program.account.feeLedger.fetch(feeLedgerPDA);
+
+    await program.methods
+      .bulkBuyByPrice([
+        {
+          amount: new anchor.BN(5 * Math.pow(10,9)),
+          subMarketId: new anchor.BN(1),
+          choiceId: new anchor.BN(1),
+        },
+      ])
+      .signers([walletManager])
+      .preInstructions([additionalComputeBudgetInstruction])
+      .accounts({
+        config: configPDA,
+        signer: walletManager.publicKey,
+        user: traderPDA,
+        market: marketPDA,
+        marketPortfolio: marketPortfolioPDA,
+        feeLedger: feeLedgerPDA,
+        creator: null,
+        referrer: null,
+      })
+      .rpc();
+
+    const feeLedger = await program.account.feeLedger.fetch(feeLedgerPDA);
+
+    expect(feeLedger.accrued.sub(before.accrued).toNumber()).to.equal(5 * Math.pow(10,7));
+  });
+
+  it("sweeps whole token units to the fee owner", async () => {
+    const mint = await getMint(provider.connection, MINT);
+    const unit = new anchor.BN(10).pow(new anchor.BN(9 - mint.decimals));
+
+    const before = await program.account.feeLedger.fetch(feeLedgerPDA);
+    const feeAccountBefore = await getAccount(provider.connection, feeAccount);
+
+    await sweepFees();
+
+    const feeLedger = await program.account.feeLedger.fetch(feeLedgerPDA);
+    const feeAccountAfter = await getAccount(provider.connection, feeAccount);
+
+    const remainder = before.accrued.mod(unit);
+    const swept = before.accrued.sub(remainder);
+
+    expect(feeLedger.accrued.toString()).to.equal(remainder.toString());
+    expect(feeLedger.swept.sub(before.swept).toString()).to.equal(swept.toString());
+    expect((feeAccountAfter.amount - feeAccountBefore.amount).toString()).to.equal(swept.div(unit).toString());
+  });
+
+  it("fails to sweep less than a token unit", async () => {
+    try {
+      await sweepFees();
+      expect.fail("sweep should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal("Not enough fees accrued to sweep a whole token unit.");
+    }
+  });
+});
@@ -0,0 +1,160 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  getAuthorizedUserKeypair,
+  getUserKeypair,
+  getWalletManagerKeypair,
+} from "../utils/wallets";
+import { getProgram } from "../utils/program";
+import {
+  getAuthorizedPDA,
+  getConfigPDA,
+  getFeeLedgerPDA,
+  getVaultPDA,
+  getMarketPDA,
+  getMarketPortfolioPDA,
+  getUserPDA,
+} from "../utils/pdas";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { additionalComputeBudgetInstruction } from "../utils/extra-cpu";
+
+describe("fee schedule", () => {
+  const MINT = new PublicKey("5DUWZLh3zPKAAJKu7ftMJJrkBrKnq3zHPPmguzVkhSes");
+
+  const program = getProgram();
+
+  const marketKeypair = anchor.web3.Keypair.generate();
+
+  const authorizedKeypair = getAuthorizedUserKeypair();
+  const walletManager = getWalletManagerKeypair();
+  const userKeypair = getUserKeypair();
+
+  const configPDA = getConfigPDA(program);
+  const feeLedgerPDA = getFeeLedgerPDA(program);
+  const vaultPDA = getVaultPDA(MINT, program);
+  const marketPDA = getMarketPDA(marketKeypair.publicKey, program);
+  const authorizedUserPda = getAuthorizedPDA(authorizedKeypair.publicKey, program);
+  const userPDA = getUserPDA(userKeypair.publicKey, program);
+  const marketPortfolioPDA = getMarketPortfolioPDA(marketPDA, userPDA, program);
+
+  const from = getAssociatedTokenAddressSync(MINT, walletManager.publicKey);
+
+  const now = new Date();
+
+  const initMarketData = [
+    {
+      id: new anchor.BN(1),
+      initPot: new anchor.BN(100 * Math.pow(10,9)),
+      curve: { constantProduct: {} },
+      choiceIds: [new anchor.BN(1), new anchor.BN(2)],
+      fairLaunchStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 3),
+      fairLaunchEnd: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 2),
+      tradingStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60),
+      tradingEnd: new anchor.BN(now.valueOf() / 1000 + 60 * 60),
+    },
+  ];
+
+  const feeSchedule = (tradeBps: number) => ({
+    tradeBps,
+    takerBps: 0,
+    makerBps: 0,
+    withdrawalBps: 500,
+    resolutionBps: 1000,
+    fairLaunchBps: 0,
+    minFee: new anchor.BN(0),
+    maxFee: new anchor.BN(Math.pow(10,9)),
+  });
+
+  const setMarketFeeSchedule = (subMarketId: number | null, schedule: any) =>
+    program.methods
+      .setMarketFeeSchedule(subMarketId === null ? null : new anchor.BN(subMarketId), schedule)
+      .signers([authorizedKeypair])
+      .accounts({
+        signer: authorizedKeypair.publicKey,
+        authorizedUser: authorizedUserPda,
+        market: marketPDA,
+      })
+      .rpc();
+
+  const buy = () =>
+    program.methods
+      .bulkBuyByPrice([
+        {
+          amount: new anchor.BN(5 * Math.pow(10,9)),
+          subMarketId: new anchor.BN(1),
+          choiceId: new anchor.BN(1),
+        },
+      ])
+      .signers([walletManager])
+      .preInstructions([additionalComputeBudgetInstruction])
+      .accounts({
+        config: configPDA,
+        signer: walletManager.publicKey,
+        user: userPDA,
+        market: marketPDA,
+        marketPortfolio: marketPortfolioPDA,
+        feeLedger: feeLedgerPDA
+      })
+      .rpc();
+
+  before(async () => {
+    await program.methods
+      .initMarket(initMarketData, marketKeypair.publicKey)
+      .signers([authorizedKeypair])
+      .accounts({
+        config: configPDA,
+        signer: authorizedKeypair.publicKey,
+        market: marketPDA,
+        authorizedUser: authorizedUserPda,
+      })
+      .rpc();
+
+    await program.methods
+      .deposit(new anchor.BN(10 * Math.pow(10,9)))
+      .signers([walletManager])
+      .accounts({
+        config: configPDA,
+        user: userPDA,
+        signer: walletManager.publicKey,
+        fromUsdcAccount: from,
+        vault: vaultPDA,
+        mint: MINT,
+      })
+      .rpc();
+  });
+
+  it("fails to set a fee over 100%", async () => {
+    try {
+      await setMarketFeeSchedule(null, feeSchedule(10001));
+      expect.fail("set should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal(
+        "Fee rates can't be over 100% and the minimum fee can't be above the maximum."
+      );
+    }
+  });
+
+  it("charges the market's trade fee", async () => {
+    await setMarketFeeSchedule(null, feeSchedule(100));
+
+    await buy();
+
+    const market = await program.account.market.fetch(marketPDA);
+
+    expect(market.feeSchedule.tradeBps).to.equal(100);
+    expect(market.subMarkets[0].choices[0].usdcPot.toNumber() / Math.pow(10,9)).to.equal(54.95);
+  });
+
+  it("charges the sub market's trade fee over the market's", async () => {
+    await setMarketFeeSchedule(1, feeSchedule(0));
+
+    await buy();
+
+    const market = await program.account.market.fetch(marketPDA);
+
+    expect(market.subMarkets[0].feeSchedule.tradeBps).to.equal(0);
+    expect(market.subMarkets[0].choices[0].usdcPot.toNumber() / Math.pow(10,9)).to.equal(59.95);
+  });
+});
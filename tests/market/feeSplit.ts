@@ -0,0 +1,206 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import {
+  getAuthorizedUserKeypair,
+  getUserKeypair,
+  getWalletManagerKeypair,
+} from "../utils/wallets";
+import { getProgram } from "../utils/program";
+import {
+  getAuthorizedPDA,
+  getConfigPDA,
+  getFeeLedgerPDA,
+  getMarketPDA,
+  getMarketPortfolioPDA,
+  getUserPDA,
+  getVaultPDA,
+} from "../utils/pdas";
+import { additionalComputeBudgetInstruction } from "../utils/extra-cpu";
+
+describe("fee split", () => {
+  const MINT = new PublicKey("5DUWZLh3zPKAAJKu7ftMJJrkBrKnq3zHPPmguzVkhSes");
+
+  const program = getProgram();
+
+  const marketKeypair = anchor.web3.Keypair.generate();
+  const creatorKeypair = anchor.web3.Keypair.generate();
+  const traderKeypair = anchor.web3.Keypair.generate();
+
+  const authorizedKeypair = getAuthorizedUserKeypair();
+  const walletManager = getWalletManagerKeypair();
+  const referrerKeypair = getUserKeypair();
+
+  const configPDA = getConfigPDA(program);
+  const feeLedgerPDA = getFeeLedgerPDA(program);
+  const vaultPDA = getVaultPDA(MINT, program);
+  const marketPDA = getMarketPDA(marketKeypair.publicKey, program);
+  const authorizedUserPda = getAuthorizedPDA(authorizedKeypair.publicKey, program);
+  const creatorPDA = getUserPDA(creatorKeypair.publicKey, program);
+  const traderPDA = getUserPDA(traderKeypair.publicKey, program);
+  const referrerPDA = getUserPDA(referrerKeypair.publicKey, program);
+  const marketPortfolioPDA = getMarketPortfolioPDA(marketPDA, traderPDA, program);
+
+  const from = getAssociatedTokenAddressSync(MINT, walletManager.publicKey);
+
+  const now = new Date();
+
+  const initMarketData = [
+    {
+      id: new anchor.BN(1),
+      initPot: new anchor.BN(100 * Math.pow(10,9)),
+      curve: { constantProduct: {} },
+      choiceIds: [new anchor.BN(1), new anchor.BN(2)],
+      fairLaunchStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 3),
+      fairLaunchEnd: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 2),
+      tradingStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60),
+      tradingEnd: new anchor.BN(now.valueOf() / 1000 + 60 * 60),
+    },
+  ];
+
+  const setFeeSplit = (creatorBps: number, referrerBps: number) =>
+    program.methods
+      .setFeeSplit({ creatorBps, referrerBps }, [])
+      .signers([authorizedKeypair])
+      .accounts({
+        signer: authorizedKeypair.publicKey,
+        config: configPDA,
+        authorizedUser: authorizedUserPda,
+      })
+      .rpc();
+
+  before(async () => {
+    for (const [keypair, referrer] of [[creatorKeypair, null], [traderKeypair, referrerPDA]] as const) {
+      await program.methods
+        .initWallet(keypair.publicKey)
+        .signers([walletManager])
+        .accounts({
+          config: configPDA,
+          user: getUserPDA(keypair.publicKey, program),
+          signer: walletManager.publicKey,
+          referrer,
+        })
+        .rpc();
+    }
+
+    await program.methods
+      .initMarket(initMarketData, marketKeypair.publicKey)
+      .signers([authorizedKeypair])
+      .accounts({
+        config: configPDA,
+        signer: authorizedKeypair.publicKey,
+        market: marketPDA,
+        authorizedUser: authorizedUserPda,
+        creator: creatorPDA,
+      })
+      .rpc();
+
+    await program.methods
+      .deposit(new anchor.BN(5 * Math.pow(10,9)))
+      .signers([walletManager])
+      .accounts({
+        config: configPDA,
+        user: traderPDA,
+        signer: walletManager.publicKey,
+        fromUsdcAccount: from,
+        vault: vaultPDA,
+        mint: MINT,
+      })
+      .rpc();
+  });
+
+  after(async () => {
+    await setFeeSplit(0, 0);
+  });
+
+  it("records the creator and the referrer", async () => {
+    const market = await program.account.market.fetch(marketPDA);
+    const trader = await program.account.user.fetch(traderPDA);
+
+    expect(market.creator.toBase58()).to.equal(creatorPDA.toBase58());
+    expect(trader.referrer.toBase58()).to.equal(referrerPDA.toBase58());
+  });
+
+  it("fails to split out more than the whole fee", async () => {
+    try {
+      await setFeeSplit(6000, 5000);
+      expect.fail("set should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal(
+        "Creator and referrer shares can't add up to more than the whole fee."
+      );
+    }
+  });
+
+  it("splits the trade fee between the creator and the referrer", async () => {
+    await setFeeSplit(2000, 1000);
+
+    const referrerBefore = await program.account.user.fetch(referrerPDA);
+
+    await program.methods
+      .bulkBuyByPrice([
+        {
+          amount: new anchor.BN(5 * Math.pow(10,9)),
+          subMarketId: new anchor.BN(1),
+          choiceId: new anchor.BN(1),
+        },
+      ])
+      .signers([walletManager])
+      .preInstructions([additionalComputeBudgetInstruction])
+      .accounts({
+        config: configPDA,
+        signer: walletManager.publicKey,
+        user: traderPDA,
+        market: marketPDA,
+        marketPortfolio: marketPortfolioPDA,
+        feeLedger: feeLedgerPDA,
+        creator: creatorPDA,
+        referrer: referrerPDA,
+      })
+      .rpc();
+
+    const creator = await program.account.user.fetch(creatorPDA);
+    const referrer = await program.account.user.fetch(referrerPDA);
+
+    // 0.5% of 5 is 0.025, 20% of that to the creator and 10% to the referrer
+    expect(creator.feeRewards.toNumber()).to.equal(5 * Math.pow(10,6));
+    expect(referrer.referralRewards.toNumber() - referrerBefore.referralRewards.toNumber()).to.equal(25 * Math.pow(10,5));
+  });
+
+  it("claims fee rewards into the balance", async () => {
+    await program.methods
+      .claimFeeRewards()
+      .signers([walletManager])
+      .accounts({
+        signer: walletManager.publicKey,
+        config: configPDA,
+        user: creatorPDA,
+      })
+      .rpc();
+
+    const creator = await program.account.user.fetch(creatorPDA);
+
+    expect(creator.feeRewards.toNumber()).to.equal(0);
+    expect(creator.balance.toNumber()).to.equal(5 * Math.pow(10,6));
+  });
+
+  it("fails to claim without fee rewards", async () => {
+    try {
+      await program.methods
+        .claimFeeRewards()
+        .signers([walletManager])
+        .accounts({
+          signer: walletManager.publicKey,
+          config: configPDA,
+          user: creatorPDA,
+        })
+        .rpc();
+      expect.fail("claim should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal("No fee rewards to claim.");
+    }
+  });
+});
@@ -0,0 +1,265 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import {
+  getAuthorizedUserKeypair,
+  getUserKeypair,
+  getWalletManagerKeypair,
+} from "../utils/wallets";
+import { getProgram } from "../utils/program";
+import {
+  getAuthorizedPDA,
+  getConfigPDA,
+  getFeeLedgerPDA,
+  getEventQueuePDA,
+  getMarketPDA,
+  getMarketPortfolioPDA,
+  getOrderBookPDA,
+  getUserPDA,
+  getVaultPDA,
+} from "../utils/pdas";
+
+describe("fee tiers", () => {
+  const MINT = new PublicKey("5DUWZLh3zPKAAJKu7ftMJJrkBrKnq3zHPPmguzVkhSes");
+
+  const program = getProgram();
+
+  const marketKeypair = anchor.web3.Keypair.generate();
+
+  const authorizedKeypair = getAuthorizedUserKeypair();
+  const makerKeypair = getWalletManagerKeypair();
+  const takerKeypair = getUserKeypair();
+
+  const configPDA = getConfigPDA(program);
+  const feeLedgerPDA = getFeeLedgerPDA(program);
+  const vaultPDA = getVaultPDA(MINT, program);
+  const marketPDA = getMarketPDA(marketKeypair.publicKey, program);
+  const authorizedUserPda = getAuthorizedPDA(authorizedKeypair.publicKey, program);
+
+  const makerPDA = getUserPDA(makerKeypair.publicKey, program);
+  const takerPDA = getUserPDA(takerKeypair.publicKey, program);
+  const takerPortfolioPDA = getMarketPortfolioPDA(marketPDA, takerPDA, program);
+
+  const orderBookPDA = getOrderBookPDA(marketPDA, 1, 1, program);
+  const eventQueuePDA = getEventQueuePDA(orderBookPDA, program);
+
+  const makerUsdcAccount = getAssociatedTokenAddressSync(MINT, makerKeypair.publicKey);
+
+  const now = new Date();
+
+  const initMarketData = [
+    {
+      id: new anchor.BN(1),
+      initPot: new anchor.BN(100 * Math.pow(10,9)),
+      curve: { constantProduct: {} },
+      choiceIds: [new anchor.BN(1), new anchor.BN(2)],
+      fairLaunchStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 3),
+      fairLaunchEnd: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 2),
+      tradingStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60),
+      tradingEnd: new anchor.BN(now.valueOf() / 1000 + 60 * 60),
+    },
+  ];
+
+  const setFeeTiers = (tiers: { minVolume: anchor.BN, discountBps: number }[]) =>
+    program.methods
+      .setFeeTiers(tiers)
+      .signers([authorizedKeypair])
+      .accounts({
+        signer: authorizedKeypair.publicKey,
+        config: configPDA,
+        authorizedUser: authorizedUserPda,
+      })
+      .rpc();
+
+  const placeOrder = (keypair: anchor.web3.Keypair, side: any, price: number, shares: number) => {
+    const userPDA = getUserPDA(keypair.publicKey, program);
+
+    return program.methods
+      .placeOrder(
+        new anchor.BN(1),
+        new anchor.BN(1),
+        side,
+        new anchor.BN(price * Math.pow(10,9)),
+        new anchor.BN(shares * Math.pow(10,9))
+      )
+      .signers([keypair])
+      .accounts({
+        signer: keypair.publicKey,
+        config: configPDA,
+        user: userPDA,
+        market: marketPDA,
+        marketPortfolio: getMarketPortfolioPDA(marketPDA, userPDA, program),
+        orderBook: orderBookPDA,
+        eventQueue: eventQueuePDA,
+        feeLedger: feeLedgerPDA,
+      })
+      .rpc();
+  };
+
+  const buy = (choiceId: number, amount: number) =>
+    program.methods
+      .userBulkBuyByPrice([
+        {
+          amount: new anchor.BN(amount * Math.pow(10,9)),
+          subMarketId: new anchor.BN(1),
+          choiceId: new anchor.BN(choiceId),
+        },
+      ])
+      .signers([takerKeypair])
+      .accounts({
+        signer: takerKeypair.publicKey,
+        config: configPDA,
+        user: takerPDA,
+        market: marketPDA,
+        marketPortfolio: takerPortfolioPDA,
+        feeLedger: feeLedgerPDA,
+      })
+      .rpc();
+
+  const trailingVolume = (user: any) =>
+    user.dailyVolume.reduce((sum: number, day: anchor.BN) => sum + day.toNumber(), 0);
+
+  before(async () => {
+    await program.methods
+      .initMarket(initMarketData, marketKeypair.publicKey)
+      .signers([authorizedKeypair])
+      .accounts({
+        config: configPDA,
+        signer: authorizedKeypair.publicKey,
+        market: marketPDA,
+        authorizedUser: authorizedUserPda,
+      })
+      .rpc();
+
+    await program.methods
+      .initOrderBook(new anchor.BN(1), new anchor.BN(1))
+      .signers([authorizedKeypair])
+      .accounts({
+        signer: authorizedKeypair.publicKey,
+        config: configPDA,
+        authorizedUser: authorizedUserPda,
+        market: marketPDA,
+        orderBook: orderBookPDA,
+        eventQueue: eventQueuePDA,
+      })
+      .rpc();
+
+    await program.methods
+      .userInitWallet()
+      .signers([makerKeypair])
+      .accounts({ config: configPDA, user: makerPDA, signer: makerKeypair.publicKey })
+      .rpc()
+      .catch((_) => {});
+
+    for (const userPDA of [makerPDA, takerPDA]) {
+      await program.methods
+        .deposit(new anchor.BN(5 * Math.pow(10,9)))
+        .signers([makerKeypair])
+        .accounts({
+          config: configPDA,
+          user: userPDA,
+          signer: makerKeypair.publicKey,
+          fromUsdcAccount: makerUsdcAccount,
+          vault: vaultPDA,
+          mint: MINT,
+        })
+        .rpc();
+    }
+
+    // the taker needs shares to sell into the book
+    await buy(1, 2);
+
+    await program.methods
+      .setMarketFeeSchedule(null, {
+        tradeBps: 50,
+        takerBps: 100,
+        makerBps: 50,
+        withdrawalBps: 500,
+        resolutionBps: 1000,
+        fairLaunchBps: 0,
+        minFee: new anchor.BN(0),
+        maxFee: new anchor.BN(Math.pow(10,9)),
+      })
+      .signers([authorizedKeypair])
+      .accounts({
+        signer: authorizedKeypair.publicKey,
+        authorizedUser: authorizedUserPda,
+        market: marketPDA,
+      })
+      .rpc();
+  });
+
+  after(async () => {
+    await setFeeTiers([]);
+  });
+
+  it("escrows the maker fee with a resting bid", async () => {
+    const before = await program.account.user.fetch(makerPDA);
+
+    await placeOrder(makerKeypair, { bid: {} }, 0.5, 2);
+
+    const maker = await program.account.user.fetch(makerPDA);
+    const orderBook = await program.account.orderBook.fetch(orderBookPDA);
+
+    // 1 to fill the bid and 0.5% of it for the maker fee
+    expect(before.balance.sub(maker.balance).toNumber()).to.equal(1005 * Math.pow(10,6));
+    expect(orderBook.bids[0].makerBps).to.equal(50);
+    expect(orderBook.bids[0].feeEscrow.toNumber()).to.equal(5 * Math.pow(10,6));
+  });
+
+  it("charges the taker and the maker when the bid fills", async () => {
+    const before = await program.account.user.fetch(takerPDA);
+
+    await placeOrder(takerKeypair, { ask: {} }, 0.5, 2);
+
+    const taker = await program.account.user.fetch(takerPDA);
+    const orderBook = await program.account.orderBook.fetch(orderBookPDA);
+    const eventQueue = await program.account.eventQueue.fetch(eventQueuePDA);
+
+    // 1% of the 1 the bid paid goes to the taker fee
+    expect(taker.balance.sub(before.balance).toNumber()).to.equal(99 * Math.pow(10,7));
+    expect(trailingVolume(taker) - trailingVolume(before)).to.equal(Math.pow(10,9));
+    expect(orderBook.bids.length).to.equal(0);
+    expect(eventQueue.events[0].makerFee.toNumber()).to.equal(5 * Math.pow(10,6));
+  });
+
+  it("fails to set tiers that don't go up in volume", async () => {
+    try {
+      await setFeeTiers([
+        { minVolume: new anchor.BN(Math.pow(10,9)), discountBps: 2000 },
+        { minVolume: new anchor.BN(Math.pow(10,9)), discountBps: 5000 },
+      ]);
+      expect.fail("set should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal(
+        "Fee tiers have to go up in volume, with at most four of them and no discount over the whole fee."
+      );
+    }
+  });
+
+  it("tracks the trader's volume over the last 30 days", async () => {
+    const before = await program.account.user.fetch(takerPDA);
+
+    await buy(2, 1);
+
+    const taker = await program.account.user.fetch(takerPDA);
+
+    expect(trailingVolume(taker) - trailingVolume(before)).to.equal(Math.pow(10,9));
+  });
+
+  it("discounts the trade fee once the trader reaches a tier", async () => {
+    await setFeeTiers([{ minVolume: new anchor.BN(Math.pow(10,9)), discountBps: 5000 }]);
+
+    const before = await program.account.market.fetch(marketPDA);
+
+    await buy(2, 2);
+
+    const market = await program.account.market.fetch(marketPDA);
+
+    // half of the market's 0.5% is taken off 2
+    expect(market.subMarkets[0].choices[1].usdcPot.sub(before.subMarkets[0].choices[1].usdcPot).toNumber())
+      .to.equal(1995 * Math.pow(10,6));
+  });
+});
@@ -8,6 +8,7 @@ import {
   getUserKeypair,
 } from "../utils/wallets";
 import { getProgram } from "../utils/program";
+import { getConfigPDA, getMarketPDA } from "../utils/pdas";
 
 
 
@@ -15,6 +16,7 @@ describe("init market", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
   const program = getProgram();
+  const configPDA = getConfigPDA(program);
 
   let market_id = anchor.web3.Keypair.generate();
 
@@ -48,6 +50,7 @@ describe("init market", () => {
     {
       id: new anchor.BN(1),
       initPot: new anchor.BN(100 * Math.pow(10,9)),
+      curve: { constantProduct: {} },
       choiceIds: [new anchor.BN(1), new anchor.BN(2)],
       fairLaunchStart: new anchor.BN(now.valueOf()),
       fairLaunchEnd: new anchor.BN(now.valueOf()),
@@ -65,6 +68,7 @@ describe("init market", () => {
       )
       .signers([authorizedKeypair])
       .accounts({ 
+        config: configPDA,
         signer: authorizedKeypair.publicKey, 
         market: marketPDA,
         authorizedUser:  authorizedUserPda})
@@ -77,6 +81,61 @@ describe("init market", () => {
     expect(Number(market.subMarkets[0].choices.map(choice => choice.usdcPot).reduce((sum, current) => BigInt(sum) + BigInt(current.toNumber()), BigInt(0)) / BigInt(Math.pow(10,9)))).to.equal(100);
   });
 
+  it("creates a market with several choices", async () => {
+    const electionKeypair = anchor.web3.Keypair.generate();
+    const electionPDA = getMarketPDA(electionKeypair.publicKey, program);
+
+    await program.methods
+      .initMarket(
+        [{
+          ...initMarketData[0],
+          choiceIds: [1, 2, 3, 4, 5].map((id) => new anchor.BN(id)),
+        }],
+        electionKeypair.publicKey
+      )
+      .signers([authorizedKeypair])
+      .accounts({
+        config: configPDA,
+        signer: authorizedKeypair.publicKey,
+        market: electionPDA,
+        authorizedUser: authorizedUserPda})
+      .rpc();
+
+    const market = await program.account.market.fetch(electionPDA);
+
+    expect(market.subMarkets[0].choices.length).to.equal(5);
+    expect(Number(market.subMarkets[0].choices.map(choice => choice.usdcPot).reduce((sum, current) => BigInt(sum) + BigInt(current.toNumber()), BigInt(0)) / BigInt(Math.pow(10,9)))).to.equal(100);
+  });
+
+  it("fails with too many choices", async () => {
+    const electionKeypair = anchor.web3.Keypair.generate();
+    const electionPDA = getMarketPDA(electionKeypair.publicKey, program);
+
+    try {
+      await program.methods
+        .initMarket(
+          [{
+            ...initMarketData[0],
+            choiceIds: [1, 2, 3, 4, 5, 6, 7, 8, 9].map((id) => new anchor.BN(id)),
+          }],
+          electionKeypair.publicKey
+        )
+        .signers([authorizedKeypair])
+        .accounts({
+          config: configPDA,
+          signer: authorizedKeypair.publicKey,
+          market: electionPDA,
+          authorizedUser: authorizedUserPda})
+        .rpc();
+      expect.fail("init should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal(
+        "Sub market needs between 2 and the maximum number of choices, with sorted unique ids."
+      );
+    }
+  });
+
   it("unauthorized create", async () => {
     try {
       await program.methods
@@ -86,6 +145,7 @@ describe("init market", () => {
       )
       .signers([user])
       .accounts({ 
+        config: configPDA,
         signer: user.publicKey, 
         market: marketPDA,
         authorizedUser:  unauthorizedUserPda})
@@ -0,0 +1,191 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  getAuthorizedUserKeypair,
+  getUserKeypair,
+  getWalletManagerKeypair,
+} from "../utils/wallets";
+import { getProgram } from "../utils/program";
+import {
+  getAuthorizedPDA,
+  getConfigPDA,
+  getFeeLedgerPDA,
+  getVaultPDA,
+  getMarketPDA,
+  getMarketPortfolioPDA,
+  getUserPDA,
+} from "../utils/pdas";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+
+describe("lmsr", () => {
+  const MINT = new PublicKey("5DUWZLh3zPKAAJKu7ftMJJrkBrKnq3zHPPmguzVkhSes");
+
+  const additionalComputeBudgetInstruction =
+      anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({
+        units: 1_400_000,
+      });
+
+  const program = getProgram();
+
+  let marketKeypair = anchor.web3.Keypair.generate();
+
+  let authorizedKeypair = getAuthorizedUserKeypair();
+  let walletManager = getWalletManagerKeypair();
+  let userKeypair = getUserKeypair();
+
+  const configPDA = getConfigPDA(program);
+
+  const feeLedgerPDA = getFeeLedgerPDA(program);
+
+  const vaultPDA = getVaultPDA(MINT, program);
+
+  const marketPDA = getMarketPDA(marketKeypair.publicKey, program);
+
+  const authorizedUserPda = getAuthorizedPDA(
+    authorizedKeypair.publicKey,
+    program
+  );
+
+  const userPDA = getUserPDA(userKeypair.publicKey, program);
+
+  const marketPortfolioPDA = getMarketPortfolioPDA(marketPDA, userPDA, program);
+
+  const from = getAssociatedTokenAddressSync(
+    MINT,
+    walletManager.publicKey
+  );
+
+  let now = new Date();
+
+  const initMarketData = [
+    {
+      id: new anchor.BN(1),
+      initPot: new anchor.BN(100 * Math.pow(10,9)),
+      curve: { lmsr: { liquidityParameter: new anchor.BN(100 * Math.pow(10,9)) } },
+      choiceIds: [new anchor.BN(1), new anchor.BN(2)],
+      fairLaunchStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 3),
+      fairLaunchEnd: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 2),
+      tradingStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60),
+      tradingEnd: new anchor.BN(now.valueOf() / 1000 + 60 * 60),
+    },
+  ]
+
+  it("fails to create a market the initial pot can't subsidize", async () => {
+    const otherKeypair = anchor.web3.Keypair.generate();
+
+    try {
+      await program.methods
+        .initMarket(
+          [{ ...initMarketData[0], curve: { lmsr: { liquidityParameter: new anchor.BN(200 * Math.pow(10,9)) } } }],
+          otherKeypair.publicKey
+        )
+        .signers([authorizedKeypair])
+        .accounts({
+          config: configPDA,
+          signer: authorizedKeypair.publicKey,
+          market: getMarketPDA(otherKeypair.publicKey, program),
+          authorizedUser: authorizedUserPda,
+        })
+        .rpc();
+      expect.fail("init should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal(
+        "Liquidity parameter is invalid or the initial pot doesn't cover the maximum loss."
+      );
+    }
+  });
+
+  it("creates an lmsr market", async () => {
+    await program.methods
+      .initMarket(initMarketData, marketKeypair.publicKey)
+      .signers([authorizedKeypair])
+      .accounts({
+        config: configPDA,
+        signer: authorizedKeypair.publicKey,
+        market: marketPDA,
+        authorizedUser: authorizedUserPda,
+      })
+      .rpc();
+
+    await program.methods
+      .deposit(new anchor.BN(5 * Math.pow(10,9)))
+      .signers([walletManager])
+      .accounts({
+        config: configPDA,
+        user: userPDA,
+        signer: walletManager.publicKey,
+        fromUsdcAccount: from,
+        vault: vaultPDA,
+        mint: MINT,
+      })
+      .rpc();
+
+    const market = await program.account.market.fetch(marketPDA);
+
+    expect(market.subMarkets[0].curve.lmsr.liquidityParameter.toNumber() / Math.pow(10,9)).to.equal(100);
+  });
+
+  it("buys by price on the lmsr curve", async () => {
+    const before = await program.account.user.fetch(userPDA);
+
+    await program.methods
+      .bulkBuyByPrice([
+        {
+          amount: new anchor.BN(5 * Math.pow(10,9)),
+          subMarketId: new anchor.BN(1),
+          choiceId: new anchor.BN(1),
+          minSharesOut: new anchor.BN(97 * Math.pow(10,8)),
+        },
+      ])
+      .signers([walletManager])
+      .preInstructions([additionalComputeBudgetInstruction])
+      .accounts({
+        config: configPDA,
+        signer: walletManager.publicKey,
+        user: userPDA,
+        market: marketPDA,
+        marketPortfolio: marketPortfolioPDA,
+        feeLedger: feeLedgerPDA
+      })
+      .rpc();
+
+    const user = await program.account.user.fetch(userPDA);
+    const market = await program.account.market.fetch(marketPDA);
+
+    // b * ln(1 + (e^(4.975 / b) - 1) * 2)
+    expect(before.balance.toNumber() - user.balance.toNumber()).to.equal(5 * Math.pow(10,9));
+    expect(market.subMarkets[0].choices[0].mintedShares.toNumber() / Math.pow(10,9)).to.be.closeTo(9.714179492, 0.000000002);
+    expect(market.subMarkets[0].choices[0].usdcPot.toNumber() / Math.pow(10,9)).to.equal(54.975);
+    expect(market.subMarkets[0].choices[1].usdcPot.toNumber() / Math.pow(10,9)).to.equal(50);
+  });
+
+  it("sells by shares on the lmsr curve", async () => {
+    await program.methods
+      .bulkSellByShares([
+        {
+          amount: new anchor.BN(5 * Math.pow(10,9)),
+          subMarketId: new anchor.BN(1),
+          choiceId: new anchor.BN(1),
+          minProceeds: new anchor.BN(25 * Math.pow(10,8)),
+        },
+      ])
+      .signers([walletManager])
+      .preInstructions([additionalComputeBudgetInstruction])
+      .accounts({
+        config: configPDA,
+        signer: walletManager.publicKey,
+        user: userPDA,
+        market: marketPDA,
+        marketPortfolio: marketPortfolioPDA,
+        feeLedger: feeLedgerPDA
+      })
+      .rpc();
+
+    const market = await program.account.market.fetch(marketPDA);
+
+    expect(market.subMarkets[0].choices[0].mintedShares.toNumber() / Math.pow(10,9)).to.be.closeTo(4.714179492, 0.000000002);
+    expect(market.subMarkets[0].choices[0].usdcPot.toNumber() / Math.pow(10,9)).to.be.closeTo(54.975 - 2.590133466, 0.000000002);
+  });
+});
@@ -0,0 +1,284 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import {
+  getAuthorizedUserKeypair,
+  getOwnerKeypair,
+  getUserKeypair,
+  getWalletManagerKeypair,
+} from "../utils/wallets";
+import { getProgram } from "../utils/program";
+import {
+  getAuthorizedPDA,
+  getConfigPDA,
+  getFeeLedgerPDA,
+  getMarketPDA,
+  getProposalPDA,
+  getUserPDA,
+  getVaultPDA,
+} from "../utils/pdas";
+
+describe("optimistic oracle", () => {
+  const MINT = new PublicKey("5DUWZLh3zPKAAJKu7ftMJJrkBrKnq3zHPPmguzVkhSes");
+  const BOND = 1 * Math.pow(10,9);
+
+  const program = getProgram();
+
+  const marketKeypair = anchor.web3.Keypair.generate();
+
+  const owner = getOwnerKeypair();
+  const authorizedKeypair = getAuthorizedUserKeypair();
+  const walletManager = getWalletManagerKeypair();
+  const proposerKeypair = getUserKeypair();
+  const disputerKeypair = walletManager;
+
+  const configPDA = getConfigPDA(program);
+  const feeLedgerPDA = getFeeLedgerPDA(program);
+  const vaultPDA = getVaultPDA(MINT, program);
+  const marketPDA = getMarketPDA(marketKeypair.publicKey, program);
+  const authorizedUserPda = getAuthorizedPDA(authorizedKeypair.publicKey, program);
+  const proposalPDA = getProposalPDA(marketPDA, 1, program);
+
+  const proposerPDA = getUserPDA(proposerKeypair.publicKey, program);
+  const disputerPDA = getUserPDA(disputerKeypair.publicKey, program);
+
+  const from = getAssociatedTokenAddressSync(MINT, walletManager.publicKey);
+
+  const now = new Date();
+
+  const initMarketData = [
+    {
+      id: new anchor.BN(1),
+      initPot: new anchor.BN(100 * Math.pow(10,9)),
+      curve: { constantProduct: {} },
+      choiceIds: [new anchor.BN(1), new anchor.BN(2)],
+      fairLaunchStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 4),
+      fairLaunchEnd: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 3),
+      tradingStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 2),
+      tradingEnd: new anchor.BN(now.valueOf() / 1000 - 60 * 60),
+    },
+  ];
+  // later sub markets share the first one's schedule
+  for (const id of [2, 3]) {
+    initMarketData.push({ ...initMarketData[0], id: new anchor.BN(id) });
+  }
+
+  const disputeProposal = (signer: anchor.web3.Keypair, user: PublicKey, subMarketId = 1) =>
+    program.methods
+      .disputeProposal(new anchor.BN(subMarketId))
+      .signers([signer])
+      .accounts({
+        signer: signer.publicKey,
+        config: configPDA,
+        user,
+        market: marketPDA,
+        proposal: getProposalPDA(marketPDA, subMarketId, program),
+      })
+      .rpc();
+
+  const proposeOutcome = (subMarketId: number, choiceId: number) =>
+    program.methods
+      .proposeOutcome(new anchor.BN(subMarketId), new anchor.BN(choiceId))
+      .signers([proposerKeypair])
+      .accounts({
+        signer: proposerKeypair.publicKey,
+        config: configPDA,
+        user: proposerPDA,
+        market: marketPDA,
+        proposal: getProposalPDA(marketPDA, subMarketId, program),
+      })
+      .rpc();
+
+  const settleProposal = (subMarketId: number) =>
+    program.methods
+      .settleProposal(new anchor.BN(subMarketId))
+      .signers([authorizedKeypair])
+      .accounts({
+        signer: authorizedKeypair.publicKey,
+        config: configPDA,
+        market: marketPDA,
+        proposal: getProposalPDA(marketPDA, subMarketId, program),
+        proposer: proposerPDA,
+        disputer: disputerPDA,
+        feeLedger: feeLedgerPDA,
+      })
+      .rpc();
+
+  before(async () => {
+    await program.methods
+      .updateConfig({
+        walletManager: null,
+        clobManager: null,
+        usdcMint: null,
+        disputePeriod: null,
+        proposalBond: new anchor.BN(BOND),
+      })
+      .signers([owner])
+      .accounts({ signer: owner.publicKey, config: configPDA })
+      .rpc();
+
+    await program.methods
+      .initMarket(initMarketData, marketKeypair.publicKey)
+      .signers([authorizedKeypair])
+      .accounts({
+        config: configPDA,
+        signer: authorizedKeypair.publicKey,
+        market: marketPDA,
+        authorizedUser: authorizedUserPda,
+      })
+      .rpc();
+
+    await program.methods
+      .userInitWallet()
+      .signers([disputerKeypair])
+      .accounts({ config: configPDA, user: disputerPDA, signer: disputerKeypair.publicKey })
+      .rpc()
+      .catch((_) => {});
+
+    for (const user of [proposerPDA, disputerPDA]) {
+      await program.methods
+        .deposit(new anchor.BN(3 * BOND))
+        .signers([walletManager])
+        .accounts({
+          config: configPDA,
+          user,
+          signer: walletManager.publicKey,
+          fromUsdcAccount: from,
+          vault: vaultPDA,
+          mint: MINT,
+        })
+        .rpc();
+    }
+  });
+
+  it("proposes an outcome with a bond", async () => {
+    const before = await program.account.user.fetch(proposerPDA);
+
+    await program.methods
+      .proposeOutcome(new anchor.BN(1), new anchor.BN(1))
+      .signers([proposerKeypair])
+      .accounts({
+        signer: proposerKeypair.publicKey,
+        config: configPDA,
+        user: proposerPDA,
+        market: marketPDA,
+        proposal: proposalPDA,
+      })
+      .rpc();
+
+    const user = await program.account.user.fetch(proposerPDA);
+    const market = await program.account.market.fetch(marketPDA);
+    const proposal = await program.account.proposal.fetch(proposalPDA);
+
+    expect(before.balance.toNumber() - user.balance.toNumber()).to.equal(BOND);
+    expect(market.subMarkets[0].resolution).to.deep.equal({ proposed: {} });
+    expect(proposal.proposer.toBase58()).to.equal(proposerPDA.toBase58());
+  });
+
+  it("fails to dispute your own proposal", async () => {
+    try {
+      await disputeProposal(proposerKeypair, proposerPDA);
+      expect.fail("dispute should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal("You do not have the authorization to use this instruction.");
+    }
+  });
+
+  it("disputes by matching the bond", async () => {
+    await disputeProposal(disputerKeypair, disputerPDA);
+
+    const market = await program.account.market.fetch(marketPDA);
+    const proposal = await program.account.proposal.fetch(proposalPDA);
+
+    expect(market.subMarkets[0].resolution).to.deep.equal({ disputed: {} });
+    expect(proposal.disputer.toBase58()).to.equal(disputerPDA.toBase58());
+  });
+
+  it("pays both bonds to the disputer once the arbiter overturns the proposal", async () => {
+    const feeAccounts = {
+      config: configPDA,
+      market: marketPDA,
+      feeLedger: feeLedgerPDA,
+    };
+
+    await program.methods
+      .correctResolution(new anchor.BN(1), new anchor.BN(2))
+      .signers([authorizedKeypair])
+      .accounts({ signer: authorizedKeypair.publicKey, authorizedUser: authorizedUserPda, ...feeAccounts })
+      .rpc();
+
+    const before = await program.account.user.fetch(disputerPDA);
+
+    await program.methods
+      .settleProposal(new anchor.BN(1))
+      .signers([authorizedKeypair])
+      .accounts({
+        signer: authorizedKeypair.publicKey,
+        proposal: proposalPDA,
+        proposer: proposerPDA,
+        disputer: disputerPDA,
+        ...feeAccounts,
+      })
+      .rpc();
+
+    const user = await program.account.user.fetch(disputerPDA);
+    const proposal = await program.account.proposal.fetch(proposalPDA);
+
+    expect(user.balance.toNumber() - before.balance.toNumber()).to.equal(2 * BOND);
+    expect(proposal.settled).to.equal(true);
+  });
+
+  it("pays both bonds to the disputer when the proposed winner only shares the pot", async () => {
+    await proposeOutcome(2, 1);
+    await disputeProposal(disputerKeypair, disputerPDA, 2);
+
+    const half = new anchor.BN(Math.pow(10,9) / 2);
+    await program.methods
+      .correctWeightedResolution(new anchor.BN(2), [half, half])
+      .signers([authorizedKeypair])
+      .accounts({
+        config: configPDA,
+        signer: authorizedKeypair.publicKey,
+        market: marketPDA,
+        authorizedUser: authorizedUserPda,
+        feeLedger: feeLedgerPDA,
+      })
+      .rpc();
+
+    const before = await program.account.user.fetch(disputerPDA);
+
+    await settleProposal(2);
+
+    const user = await program.account.user.fetch(disputerPDA);
+
+    expect(user.balance.toNumber() - before.balance.toNumber()).to.equal(2 * BOND);
+  });
+
+  it("gives each bond back when the sub market is voided", async () => {
+    await proposeOutcome(3, 1);
+    await disputeProposal(disputerKeypair, disputerPDA, 3);
+
+    await program.methods
+      .voidSubMarket(new anchor.BN(3), { costBasis: {} })
+      .signers([authorizedKeypair])
+      .accounts({
+        signer: authorizedKeypair.publicKey,
+        authorizedUser: authorizedUserPda,
+        market: marketPDA,
+      })
+      .rpc();
+
+    const proposerBefore = await program.account.user.fetch(proposerPDA);
+    const disputerBefore = await program.account.user.fetch(disputerPDA);
+
+    await settleProposal(3);
+
+    const proposer = await program.account.user.fetch(proposerPDA);
+    const disputer = await program.account.user.fetch(disputerPDA);
+
+    expect(proposer.balance.toNumber() - proposerBefore.balance.toNumber()).to.equal(BOND);
+    expect(disputer.balance.toNumber() - disputerBefore.balance.toNumber()).to.equal(BOND);
+  });
+});
@@ -0,0 +1,132 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { getAuthorizedUserKeypair } from "../utils/wallets";
+import { getProgram } from "../utils/program";
+import {
+  getAuthorizedPDA,
+  getConfigPDA,
+  getMarketPDA,
+  getMockFeedPDA,
+} from "../utils/pdas";
+
+describe("oracle resolution", () => {
+  const program = getProgram();
+
+  const marketKeypair = anchor.web3.Keypair.generate();
+  const feedKeypair = anchor.web3.Keypair.generate();
+  const otherFeedKeypair = anchor.web3.Keypair.generate();
+
+  const authorizedKeypair = getAuthorizedUserKeypair();
+
+  const configPDA = getConfigPDA(program);
+  const marketPDA = getMarketPDA(marketKeypair.publicKey, program);
+  const authorizedUserPda = getAuthorizedPDA(authorizedKeypair.publicKey, program);
+  const mockFeedPDA = getMockFeedPDA(feedKeypair.publicKey, program);
+  const otherMockFeedPDA = getMockFeedPDA(otherFeedKeypair.publicKey, program);
+
+  const now = new Date();
+
+  const subMarketData = {
+    initPot: new anchor.BN(100 * Math.pow(10,9)),
+    curve: { constantProduct: {} },
+    choiceIds: [new anchor.BN(1), new anchor.BN(2)],
+    fairLaunchStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 3),
+    fairLaunchEnd: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 2),
+    tradingStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60),
+    tradingEnd: new anchor.BN(now.valueOf() / 1000 + 60 * 60),
+  };
+
+  const setMockFeed = (feedKey: PublicKey, price: number) =>
+    program.methods
+      .setMockFeed(feedKey, new anchor.BN(price), -2)
+      .signers([authorizedKeypair])
+      .accounts({
+        signer: authorizedKeypair.publicKey,
+        authorizedUser: authorizedUserPda,
+        mockFeed: getMockFeedPDA(feedKey, program),
+      })
+      .rpc();
+
+  const resolveFromOracle = (subMarketId: number, feed: PublicKey) =>
+    program.methods
+      .resolveFromOracle(new anchor.BN(subMarketId))
+      .signers([authorizedKeypair])
+      .accounts({
+        signer: authorizedKeypair.publicKey,
+        config: configPDA,
+        market: marketPDA,
+        feed,
+      })
+      .rpc();
+
+  before(async () => {
+    await program.methods
+      .initMarket(
+        [{ id: new anchor.BN(1), ...subMarketData }, { id: new anchor.BN(2), ...subMarketData }],
+        marketKeypair.publicKey
+      )
+      .signers([authorizedKeypair])
+      .accounts({
+        config: configPDA,
+        signer: authorizedKeypair.publicKey,
+        market: marketPDA,
+        authorizedUser: authorizedUserPda,
+      })
+      .rpc();
+
+    // 65,000.00 against a 60,000 threshold
+    await setMockFeed(feedKeypair.publicKey, 6_500_000);
+    await setMockFeed(otherFeedKeypair.publicKey, 5_500_000);
+
+    await program.methods
+      .setOracleConfig(new anchor.BN(1), {
+        kind: { mock: {} },
+        feed: mockFeedPDA,
+        threshold: new anchor.BN(60_000),
+        thresholdExpo: 0,
+        comparison: { greaterThan: {} },
+        resolveAt: new anchor.BN(now.valueOf() / 1000 - 60),
+        maxDelay: new anchor.BN(60 * 60),
+        choiceIfTrue: new anchor.BN(1),
+        choiceIfFalse: new anchor.BN(2),
+      })
+      .signers([authorizedKeypair])
+      .accounts({
+        signer: authorizedKeypair.publicKey,
+        authorizedUser: authorizedUserPda,
+        market: marketPDA,
+      })
+      .rpc();
+  });
+
+  it("fails to resolve from a feed other than the configured one", async () => {
+    try {
+      await resolveFromOracle(1, otherMockFeedPDA);
+      expect.fail("resolve should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal("Price feed isn't the configured feed or doesn't match its layout.");
+    }
+  });
+
+  it("fails to resolve a sub market without an oracle", async () => {
+    try {
+      await resolveFromOracle(2, mockFeedPDA);
+      expect.fail("resolve should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal("Sub market doesn't resolve from an oracle.");
+    }
+  });
+
+  it("proposes the outcome the feed picks", async () => {
+    await resolveFromOracle(1, mockFeedPDA);
+
+    const market = await program.account.market.fetch(marketPDA);
+
+    expect(market.subMarkets[0].resolution).to.deep.equal({ proposed: {} });
+    expect(market.subMarkets[0].choices[0].payoutNumerator.toNumber()).to.equal(Math.pow(10,9));
+    expect(market.subMarkets[0].choices[1].payoutNumerator.toNumber()).to.equal(0);
+  });
+});
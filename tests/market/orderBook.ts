@@ -0,0 +1,334 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import {
+  getAuthorizedUserKeypair,
+  getUserKeypair,
+  getWalletManagerKeypair,
+} from "../utils/wallets";
+import { getProgram } from "../utils/program";
+import {
+  getAuthorizedPDA,
+  getConfigPDA,
+  getFeeLedgerPDA,
+  getEventQueuePDA,
+  getMarketPDA,
+  getMarketPortfolioPDA,
+  getOrderBookPDA,
+  getUserPDA,
+  getVaultPDA,
+} from "../utils/pdas";
+
+describe("order book", () => {
+  const MINT = new PublicKey("5DUWZLh3zPKAAJKu7ftMJJrkBrKnq3zHPPmguzVkhSes");
+
+  const program = getProgram();
+
+  const marketKeypair = anchor.web3.Keypair.generate();
+
+  const authorizedKeypair = getAuthorizedUserKeypair();
+  // the maker rests orders, the taker crosses them
+  const makerKeypair = getWalletManagerKeypair();
+  const takerKeypair = getUserKeypair();
+
+  const configPDA = getConfigPDA(program);
+  const feeLedgerPDA = getFeeLedgerPDA(program);
+  const vaultPDA = getVaultPDA(MINT, program);
+  const marketPDA = getMarketPDA(marketKeypair.publicKey, program);
+  const authorizedUserPda = getAuthorizedPDA(authorizedKeypair.publicKey, program);
+
+  const makerPDA = getUserPDA(makerKeypair.publicKey, program);
+  const takerPDA = getUserPDA(takerKeypair.publicKey, program);
+  const makerPortfolioPDA = getMarketPortfolioPDA(marketPDA, makerPDA, program);
+  const takerPortfolioPDA = getMarketPortfolioPDA(marketPDA, takerPDA, program);
+
+  const orderBookPDA = getOrderBookPDA(marketPDA, 1, 1, program);
+  const eventQueuePDA = getEventQueuePDA(orderBookPDA, program);
+
+  const makerUsdcAccount = getAssociatedTokenAddressSync(MINT, makerKeypair.publicKey);
+
+  const now = new Date();
+
+  const initMarketData = [
+    {
+      id: new anchor.BN(1),
+      initPot: new anchor.BN(100 * Math.pow(10,9)),
+      curve: { constantProduct: {} },
+      choiceIds: [new anchor.BN(1), new anchor.BN(2)],
+      fairLaunchStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 3),
+      fairLaunchEnd: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 2),
+      tradingStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60),
+      tradingEnd: new anchor.BN(now.valueOf() / 1000 + 60 * 60),
+    },
+  ];
+
+  const placeOrder = (keypair: anchor.web3.Keypair, side: any, price: number, shares: number) => {
+    const userPDA = getUserPDA(keypair.publicKey, program);
+
+    return program.methods
+      .placeOrder(
+        new anchor.BN(1),
+        new anchor.BN(1),
+        side,
+        new anchor.BN(price * Math.pow(10,9)),
+        new anchor.BN(shares * Math.pow(10,9))
+      )
+      .signers([keypair])
+      .accounts({
+        signer: keypair.publicKey,
+        config: configPDA,
+        user: userPDA,
+        market: marketPDA,
+        marketPortfolio: getMarketPortfolioPDA(marketPDA, userPDA, program),
+        orderBook: orderBookPDA,
+        eventQueue: eventQueuePDA,
+        feeLedger: feeLedgerPDA,
+      })
+      .rpc();
+  };
+
+  before(async () => {
+    await program.methods
+      .initMarket(initMarketData, marketKeypair.publicKey)
+      .signers([authorizedKeypair])
+      .accounts({
+        config: configPDA,
+        signer: authorizedKeypair.publicKey,
+        market: marketPDA,
+        authorizedUser: authorizedUserPda,
+      })
+      .rpc();
+
+    await program.methods
+      .userInitWallet()
+      .signers([makerKeypair])
+      .accounts({ config: configPDA, user: makerPDA, signer: makerKeypair.publicKey })
+      .rpc()
+      .catch((_) => {});
+
+    for (const userPDA of [makerPDA, takerPDA]) {
+      await program.methods
+        .deposit(new anchor.BN(5 * Math.pow(10,9)))
+        .signers([makerKeypair])
+        .accounts({
+          config: configPDA,
+          user: userPDA,
+          signer: makerKeypair.publicKey,
+          fromUsdcAccount: makerUsdcAccount,
+          vault: vaultPDA,
+          mint: MINT,
+        })
+        .rpc();
+    }
+
+    // the taker needs shares to sell into the book
+    await program.methods
+      .userBulkBuyByPrice([
+        {
+          amount: new anchor.BN(2 * Math.pow(10,9)),
+          subMarketId: new anchor.BN(1),
+          choiceId: new anchor.BN(1),
+        },
+      ])
+      .signers([takerKeypair])
+      .accounts({
+        signer: takerKeypair.publicKey,
+        config: configPDA,
+        user: takerPDA,
+        market: marketPDA,
+        marketPortfolio: takerPortfolioPDA,
+        feeLedger: feeLedgerPDA,
+      })
+      .rpc();
+  });
+
+  it("creates the order book", async () => {
+    await program.methods
+      .initOrderBook(new anchor.BN(1), new anchor.BN(1))
+      .signers([authorizedKeypair])
+      .accounts({
+        signer: authorizedKeypair.publicKey,
+        config: configPDA,
+        authorizedUser: authorizedUserPda,
+        market: marketPDA,
+        orderBook: orderBookPDA,
+        eventQueue: eventQueuePDA,
+      })
+      .rpc();
+
+    const orderBook = await program.account.orderBook.fetch(orderBookPDA);
+
+    expect(orderBook.market.toBase58()).to.equal(marketPDA.toBase58());
+    expect(orderBook.bids.length).to.equal(0);
+    expect(orderBook.asks.length).to.equal(0);
+  });
+
+  it("rests a bid and escrows its cost", async () => {
+    const before = await program.account.user.fetch(makerPDA);
+
+    await placeOrder(makerKeypair, { bid: {} }, 0.6, 2);
+
+    const maker = await program.account.user.fetch(makerPDA);
+    const orderBook = await program.account.orderBook.fetch(orderBookPDA);
+
+    expect(before.balance.sub(maker.balance).toNumber() / Math.pow(10,9)).to.equal(1.2);
+    expect(orderBook.bids.length).to.equal(1);
+    expect(orderBook.bids[0].shares.toNumber() / Math.pow(10,9)).to.equal(2);
+  });
+
+  it("keeps price time priority", async () => {
+    await placeOrder(makerKeypair, { bid: {} }, 0.55, 1);
+    await placeOrder(makerKeypair, { bid: {} }, 0.6, 1);
+
+    const orderBook = await program.account.orderBook.fetch(orderBookPDA);
+
+    expect(orderBook.bids.map((bid) => bid.orderId.toNumber())).to.deep.equal([0, 2, 1]);
+  });
+
+  it("fills a crossing ask at the resting price", async () => {
+    const before = await program.account.user.fetch(takerPDA);
+
+    await placeOrder(takerKeypair, { ask: {} }, 0.5, 2.5);
+
+    const taker = await program.account.user.fetch(takerPDA);
+    const orderBook = await program.account.orderBook.fetch(orderBookPDA);
+    const eventQueue = await program.account.eventQueue.fetch(eventQueuePDA);
+
+    // 2 shares at 0.6 and 0.5 more at 0.6
+    expect(taker.balance.sub(before.balance).toNumber() / Math.pow(10,9)).to.equal(1.5);
+    expect(orderBook.bids.map((bid) => bid.orderId.toNumber())).to.deep.equal([2, 1]);
+    expect(orderBook.bids[0].shares.toNumber() / Math.pow(10,9)).to.equal(0.5);
+    expect(eventQueue.events.length).to.equal(2);
+  });
+
+  it("settles the maker when events are consumed", async () => {
+    await program.methods
+      .consumeEvents(10)
+      .accounts({
+        signer: makerKeypair.publicKey,
+        orderBook: orderBookPDA,
+        eventQueue: eventQueuePDA,
+      })
+      .remainingAccounts([
+        { pubkey: makerPortfolioPDA, isSigner: false, isWritable: true },
+      ])
+      .signers([makerKeypair])
+      .rpc();
+
+    const eventQueue = await program.account.eventQueue.fetch(eventQueuePDA);
+    const makerPortfolio = await program.account.marketPortfolio.fetch(makerPortfolioPDA);
+
+    expect(eventQueue.events.length).to.equal(0);
+    expect(makerPortfolio.subMarketPortfolio[0].choicePortfolio[0].shares.toNumber() / Math.pow(10,9)).to.equal(2.5);
+  });
+
+  it("cancels a resting bid and refunds its escrow", async () => {
+    const before = await program.account.user.fetch(makerPDA);
+
+    await program.methods
+      .cancelOrder({ bid: {} }, new anchor.BN(1))
+      .signers([makerKeypair])
+      .accounts({
+        signer: makerKeypair.publicKey,
+        user: makerPDA,
+        marketPortfolio: makerPortfolioPDA,
+        orderBook: orderBookPDA,
+      })
+      .rpc();
+
+    const maker = await program.account.user.fetch(makerPDA);
+
+    expect(maker.balance.sub(before.balance).toNumber() / Math.pow(10,9)).to.equal(0.55);
+  });
+
+  it("can't cancel someone else's order", async () => {
+    try {
+      await program.methods
+        .cancelOrder({ bid: {} }, new anchor.BN(2))
+        .signers([takerKeypair])
+        .accounts({
+          signer: takerKeypair.publicKey,
+          user: takerPDA,
+          marketPortfolio: takerPortfolioPDA,
+          orderBook: orderBookPDA,
+        })
+        .rpc();
+      expect.fail("cancel should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal("You do not have the authorization to use this instruction.");
+    }
+  });
+
+  it("routes a bulk sell through bids above the curve", async () => {
+    await program.methods
+      .userBulkSellByShares([
+        {
+          amount: new anchor.BN(1 * Math.pow(10,9)),
+          subMarketId: new anchor.BN(1),
+          choiceId: new anchor.BN(1),
+          minProceeds: new anchor.BN(54 * Math.pow(10,7)),
+        },
+      ])
+      .signers([takerKeypair])
+      .accounts({
+        signer: takerKeypair.publicKey,
+        config: configPDA,
+        user: takerPDA,
+        market: marketPDA,
+        marketPortfolio: takerPortfolioPDA,
+        feeLedger: feeLedgerPDA,
+      })
+      .remainingAccounts([
+        { pubkey: orderBookPDA, isSigner: false, isWritable: true },
+        { pubkey: eventQueuePDA, isSigner: false, isWritable: true },
+      ])
+      .rpc();
+
+    const orderBook = await program.account.orderBook.fetch(orderBookPDA);
+    const eventQueue = await program.account.eventQueue.fetch(eventQueuePDA);
+
+    // the last 0.5 shares bid at 0.6 fill first, the other half goes to the curve
+    expect(orderBook.bids.length).to.equal(0);
+    expect(eventQueue.events.length).to.equal(1);
+    expect(eventQueue.events[0].shares.toNumber() / Math.pow(10,9)).to.equal(0.5);
+  });
+
+  it("routes a bulk buy through asks below the curve", async () => {
+    await placeOrder(makerKeypair, { ask: {} }, 0.3, 1);
+
+    const before = await program.account.user.fetch(takerPDA);
+
+    await program.methods
+      .userBulkBuyByShares([
+        {
+          amount: new anchor.BN(1 * Math.pow(10,9)),
+          subMarketId: new anchor.BN(1),
+          choiceId: new anchor.BN(1),
+          maxCost: new anchor.BN(3 * Math.pow(10,8)),
+        },
+      ])
+      .signers([takerKeypair])
+      .accounts({
+        signer: takerKeypair.publicKey,
+        config: configPDA,
+        user: takerPDA,
+        market: marketPDA,
+        marketPortfolio: takerPortfolioPDA,
+        feeLedger: feeLedgerPDA,
+      })
+      .remainingAccounts([
+        { pubkey: orderBookPDA, isSigner: false, isWritable: true },
+        { pubkey: eventQueuePDA, isSigner: false, isWritable: true },
+      ])
+      .rpc();
+
+    const taker = await program.account.user.fetch(takerPDA);
+    const orderBook = await program.account.orderBook.fetch(orderBookPDA);
+
+    // the whole order fits in the ask, so the curve and its fee are skipped
+    expect(before.balance.sub(taker.balance).toNumber() / Math.pow(10,9)).to.equal(0.3);
+    expect(orderBook.asks.length).to.equal(0);
+  });
+});
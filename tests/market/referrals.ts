@@ -0,0 +1,237 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import {
+  getAuthorizedUserKeypair,
+  getWalletManagerKeypair,
+} from "../utils/wallets";
+import { getProgram } from "../utils/program";
+import {
+  getAuthorizedPDA,
+  getConfigPDA,
+  getFeeLedgerPDA,
+  getMarketPDA,
+  getMarketPortfolioPDA,
+  getUserPDA,
+  getVaultPDA,
+} from "../utils/pdas";
+import { additionalComputeBudgetInstruction } from "../utils/extra-cpu";
+
+describe("referrals", () => {
+  const MINT = new PublicKey("5DUWZLh3zPKAAJKu7ftMJJrkBrKnq3zHPPmguzVkhSes");
+
+  const program = getProgram();
+
+  const marketKeypair = anchor.web3.Keypair.generate();
+  const referrerKeypair = anchor.web3.Keypair.generate();
+  const traderKeypair = anchor.web3.Keypair.generate();
+
+  const authorizedKeypair = getAuthorizedUserKeypair();
+  const walletManager = getWalletManagerKeypair();
+
+  const configPDA = getConfigPDA(program);
+  const feeLedgerPDA = getFeeLedgerPDA(program);
+  const vaultPDA = getVaultPDA(MINT, program);
+  const marketPDA = getMarketPDA(marketKeypair.publicKey, program);
+  const authorizedUserPda = getAuthorizedPDA(authorizedKeypair.publicKey, program);
+  const referrerPDA = getUserPDA(referrerKeypair.publicKey, program);
+  const traderPDA = getUserPDA(traderKeypair.publicKey, program);
+  const marketPortfolioPDA = getMarketPortfolioPDA(marketPDA, traderPDA, program);
+
+  const from = getAssociatedTokenAddressSync(MINT, walletManager.publicKey);
+
+  const now = new Date();
+
+  const initMarketData = [
+    {
+      id: new anchor.BN(1),
+      initPot: new anchor.BN(100 * Math.pow(10,9)),
+      curve: { constantProduct: {} },
+      choiceIds: [new anchor.BN(1), new anchor.BN(2)],
+      fairLaunchStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 3),
+      fairLaunchEnd: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 2),
+      tradingStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60),
+      tradingEnd: new anchor.BN(now.valueOf() / 1000 + 60 * 60),
+    },
+  ];
+
+  const setReferralTiers = (referrerBps: number, tiers: { minVolume: anchor.BN, referrerBps: number }[]) =>
+    program.methods
+      .setFeeSplit({ creatorBps: 0, referrerBps }, tiers)
+      .signers([authorizedKeypair])
+      .accounts({
+        signer: authorizedKeypair.publicKey,
+        config: configPDA,
+        authorizedUser: authorizedUserPda,
+      })
+      .rpc();
+
+  const buy = () =>
+    program.methods
+      .bulkBuyByPrice([
+        {
+          amount: new anchor.BN(5 * Math.pow(10,9)),
+          subMarketId: new anchor.BN(1),
+          choiceId: new anchor.BN(1),
+        },
+      ])
+      .signers([walletManager])
+      .preInstructions([additionalComputeBudgetInstruction])
+      .accounts({
+        config: configPDA,
+        signer: walletManager.publicKey,
+        user: traderPDA,
+        market: marketPDA,
+        marketPortfolio: marketPortfolioPDA,
+        feeLedger: feeLedgerPDA,
+        creator: null,
+        referrer: referrerPDA,
+      })
+      .rpc();
+
+  const claimReferralRewards = () =>
+    program.methods
+      .claimReferralRewards()
+      .signers([walletManager])
+      .accounts({
+        signer: walletManager.publicKey,
+        config: configPDA,
+        user: referrerPDA,
+      })
+      .rpc();
+
+  before(async () => {
+    for (const [keypair, referrer] of [[referrerKeypair, null], [traderKeypair, referrerPDA]] as const) {
+      await program.methods
+        .initWallet(keypair.publicKey)
+        .signers([walletManager])
+        .accounts({
+          config: configPDA,
+          user: getUserPDA(keypair.publicKey, program),
+          signer: walletManager.publicKey,
+          referrer,
+        })
+        .rpc();
+    }
+
+    await program.methods
+      .initMarket(initMarketData, marketKeypair.publicKey)
+      .signers([authorizedKeypair])
+      .accounts({
+        config: configPDA,
+        signer: authorizedKeypair.publicKey,
+        market: marketPDA,
+        authorizedUser: authorizedUserPda,
+        creator: null,
+      })
+      .rpc();
+
+    await program.methods
+      .deposit(new anchor.BN(10 * Math.pow(10,9)))
+      .signers([walletManager])
+      .accounts({
+        config: configPDA,
+        user: traderPDA,
+        signer: walletManager.publicKey,
+        fromUsdcAccount: from,
+        vault: vaultPDA,
+        mint: MINT,
+      })
+      .rpc();
+  });
+
+  after(async () => {
+    await setReferralTiers(0, []);
+  });
+
+  it("fails to set tiers that don't go up in volume", async () => {
+    try {
+      await setReferralTiers(1000, [
+        { minVolume: new anchor.BN(5 * Math.pow(10,9)), referrerBps: 2000 },
+        { minVolume: new anchor.BN(5 * Math.pow(10,9)), referrerBps: 3000 },
+      ]);
+      expect.fail("set should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal(
+        "Referral tiers have to go up in volume, with at most four of them."
+      );
+    }
+  });
+
+  it("fails to trade without the trader's referrer", async () => {
+    await setReferralTiers(1000, [
+      { minVolume: new anchor.BN(5 * Math.pow(10,9)), referrerBps: 2000 },
+    ]);
+
+    try {
+      await program.methods
+        .bulkBuyByPrice([
+          {
+            amount: new anchor.BN(5 * Math.pow(10,9)),
+            subMarketId: new anchor.BN(1),
+            choiceId: new anchor.BN(1),
+          },
+        ])
+        .signers([walletManager])
+        .preInstructions([additionalComputeBudgetInstruction])
+        .accounts({
+          config: configPDA,
+          signer: walletManager.publicKey,
+          user: traderPDA,
+          market: marketPDA,
+          marketPortfolio: marketPortfolioPDA,
+          feeLedger: feeLedgerPDA,
+          creator: null,
+          referrer: null,
+        })
+        .rpc();
+      expect.fail("buy should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal(
+        "Fee recipient accounts are missing or don't match the market creator and referrer."
+      );
+    }
+  });
+
+  it("pays the base rate until the referrer reaches a tier", async () => {
+    await buy();
+
+    const referrer = await program.account.user.fetch(referrerPDA);
+
+    // 10% of the 0.025 fee on 5
+    expect(referrer.referralRewards.toNumber()).to.equal(25 * Math.pow(10,5));
+    expect(referrer.referredVolume.toNumber()).to.equal(5 * Math.pow(10,9));
+  });
+
+  it("pays the tier rate once the referred volume reaches it", async () => {
+    await buy();
+
+    const referrer = await program.account.user.fetch(referrerPDA);
+
+    // 20% of the second 0.025 fee
+    expect(referrer.referralRewards.toNumber()).to.equal(75 * Math.pow(10,5));
+    expect(referrer.referredVolume.toNumber()).to.equal(10 * Math.pow(10,9));
+  });
+
+  it("claims referral rewards into the balance", async () => {
+    await claimReferralRewards();
+
+    const referrer = await program.account.user.fetch(referrerPDA);
+
+    expect(referrer.referralRewards.toNumber()).to.equal(0);
+    expect(referrer.balance.toNumber()).to.equal(75 * Math.pow(10,5));
+  });
+
+  it("fails to claim without referral rewards", async () => {
+    try {
+      await claimReferralRewards();
+      expect.fail("claim should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal("No referral rewards to claim.");
+    }
+  });
+});
@@ -5,7 +5,6 @@ import {
   getAssociatedTokenAccount,
   getAuthorizedUserKeypair,
   getClobManagerKeypair,
-  getFeeManagerKeypair,
   getUserKeypair,
   getWalletManagerKeypair,
   getWalletManagerTokenAccount,
@@ -13,6 +12,9 @@ import {
 import { getProgram } from "../utils/program";
 import {
   getAuthorizedPDA,
+  getConfigPDA,
+  getFeeLedgerPDA,
+  getVaultPDA,
   getMarketPDA,
   getMarketPortfolioPDA,
   getUserPDA,
@@ -33,10 +35,15 @@ describe("resolved", () => {
   let marketKeypair = anchor.web3.Keypair.generate();
 
   let authorizedKeypair = getAuthorizedUserKeypair();
-  let feeManagerKeypair = getFeeManagerKeypair();
   let walletManager = getWalletManagerKeypair();
   let userKeypair = getUserKeypair();
 
+  const configPDA = getConfigPDA(program);
+
+  const feeLedgerPDA = getFeeLedgerPDA(program);
+
+  const vaultPDA = getVaultPDA(MINT, program);
+
   const marketPDA = getMarketPDA(marketKeypair.publicKey, program);
 
   const authorizedUserPda = getAuthorizedPDA(
@@ -52,10 +59,6 @@ describe("resolved", () => {
     MINT,
     walletManager.publicKey
   );
-  const feeAccount = getAssociatedTokenAddressSync(
-    MINT,
-    feeManagerKeypair.publicKey
-  );
 
   let now = new Date();
 
@@ -63,6 +66,7 @@ describe("resolved", () => {
     {
       id: new anchor.BN(1),
       initPot: new anchor.BN(100 * Math.pow(10,9)),
+      curve: { constantProduct: {} },
       choiceIds: [new anchor.BN(1), new anchor.BN(2)],
       fairLaunchStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 3),
       fairLaunchEnd: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 2),
@@ -72,6 +76,7 @@ describe("resolved", () => {
     {
       id: new anchor.BN(2),
       initPot: new anchor.BN(100 * Math.pow(10,9)),
+      curve: { constantProduct: {} },
       choiceIds: [new anchor.BN(1), new anchor.BN(2)],
       fairLaunchStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 3),
       fairLaunchEnd: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 2),
@@ -86,6 +91,7 @@ describe("resolved", () => {
       .initMarket(initMarketData, marketKeypair.publicKey)
       .signers([authorizedKeypair])
       .accounts({
+        config: configPDA,
         signer: authorizedKeypair.publicKey,
         market: marketPDA,
         authorizedUser: authorizedUserPda,
@@ -100,25 +106,30 @@ describe("resolved", () => {
         .withdrawFromBalance(user.balance)
         .signers([walletManager])
         .accounts({
+          config: configPDA,
           user: userPDA,
           signer: walletManager.publicKey,
           mint: MINT,
-          fromUsdcAccount: getWalletManagerTokenAccount(MINT),
+          vault: vaultPDA,
           toUsdcAccount: await getAssociatedTokenAccount(
             MINT,
             userKeypair.publicKey
           ),
-          feeUsdcAccount: feeAccount
+          feeLedger: feeLedgerPDA
         })
         .rpc();
     }
 
     await program.methods
-      .addToBalance(new anchor.BN(5 * Math.pow(10,9)))
+      .deposit(new anchor.BN(5 * Math.pow(10,9)))
       .signers([walletManager])
       .accounts({
+        config: configPDA,
         user: userPDA,
         signer: walletManager.publicKey,
+        fromUsdcAccount: from,
+        vault: vaultPDA,
+        mint: MINT,
       })
       .rpc();
   });
@@ -140,19 +151,18 @@ describe("resolved", () => {
           amount: new anchor.BN(5 * Math.pow(10,9)),
           subMarketId: new anchor.BN(1),
           choiceId: new anchor.BN(1),
-          requestedPricePerShare: 0.51213,
+          minSharesOut: new anchor.BN(97 * Math.pow(10,8)),
         },
       ])
       .signers([walletManager])
       
       .accounts({
+        config: configPDA,
         signer: walletManager.publicKey,
         user: userPDA,
         market: marketPDA,
         marketPortfolio: marketPortfolioPDA,
-        mint: MINT,
-        fromUsdcAccount: from,
-        feeUsdcAccount: feeAccount
+        feeLedger: feeLedgerPDA
       })
       .rpc().catch(err => console.log(err));
       
@@ -176,6 +186,7 @@ describe("resolved", () => {
         .claimWinnings(new anchor.BN(1), new anchor.BN(1))
         .signers([walletManager])
         .accounts({
+        config: configPDA,
         signer: walletManager.publicKey,
         user: userPDA,
         market: marketPDA,
@@ -193,21 +204,57 @@ describe("resolved", () => {
   it("resolves market", async () => {
     await program.methods
       .resolveMarket(new anchor.BN(1), new anchor.BN(1))
-      .signers([walletManager])
+      .signers([authorizedKeypair])
       .accounts({
-        signer: walletManager.publicKey,
+        config: configPDA,
+        signer: authorizedKeypair.publicKey,
         market: marketPDA,
         authorizedUser: authorizedUserPda,
-        mint: MINT,
-        fromUsdcAccount: from,
-        feeUsdcAccount: feeAccount
       })
       .rpc().catch(err => console.log(err))
 
     const market = await program.account.market.fetch(marketPDA);
-    expect(market.subMarkets[0].choices.map(choice => choice.usdcPot.toNumber()).reduce((sum, current) => sum + current / Math.pow(10,9),0)).to.equal(99.975);
     expect(market.subMarkets[0].resolved).to.equal(true)
-    expect(market.subMarkets[0].choices[0].winningChoice).to.equal(true)
+    expect(market.subMarkets[0].resolution).to.deep.equal({ proposed: {} })
+    expect(market.subMarkets[0].choices[0].payoutNumerator.toNumber()).to.equal(Math.pow(10,9))
+  })
+
+  it("fails to claim during the dispute period", async () => {
+    try {
+      await program.methods
+        .claimWinnings(new anchor.BN(1), new anchor.BN(1))
+        .signers([walletManager])
+        .accounts({
+          config: configPDA,
+          signer: walletManager.publicKey,
+          user: userPDA,
+          market: marketPDA,
+          marketPortfolio: marketPortfolioPDA,
+        })
+        .rpc();
+      expect.fail("claim should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal("Resolution is still in its dispute period, check back later.");
+    }
+  })
+
+  it("arbiter confirms the resolution", async () => {
+    await program.methods
+      .correctResolution(new anchor.BN(1), new anchor.BN(1))
+      .signers([authorizedKeypair])
+      .accounts({
+        config: configPDA,
+        signer: authorizedKeypair.publicKey,
+        market: marketPDA,
+        authorizedUser: authorizedUserPda,
+        feeLedger: feeLedgerPDA
+      })
+      .rpc();
+
+    const market = await program.account.market.fetch(marketPDA);
+    expect(market.subMarkets[0].choices.map(choice => choice.usdcPot.toNumber()).reduce((sum, current) => sum + current / Math.pow(10,9),0)).to.equal(99.975);
+    expect(market.subMarkets[0].resolution).to.deep.equal({ finalized: {} })
   })
 
   it("fails to claim due to not authorized", async () => {
@@ -216,6 +263,7 @@ describe("resolved", () => {
             .claimWinnings(new anchor.BN(1), new anchor.BN(1))
             .signers([userKeypair])
             .accounts({
+            config: configPDA,
             signer: userKeypair.publicKey,
             user: userPDA,
             market: marketPDA,
@@ -236,6 +284,7 @@ describe("resolved", () => {
             .claimWinnings(new anchor.BN(1), new anchor.BN(2))
             .signers([walletManager])
             .accounts({
+            config: configPDA,
             signer: walletManager.publicKey,
             user: userPDA,
             market: marketPDA,
@@ -255,6 +304,7 @@ describe("resolved", () => {
         .claimWinnings(new anchor.BN(1), new anchor.BN(1))
         .signers([walletManager])
         .accounts({
+        config: configPDA,
         signer: walletManager.publicKey,
         user: userPDA,
         market: marketPDA,
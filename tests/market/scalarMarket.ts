@@ -0,0 +1,214 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import {
+  getAuthorizedUserKeypair,
+  getUserKeypair,
+  getWalletManagerKeypair,
+} from "../utils/wallets";
+import { getProgram } from "../utils/program";
+import {
+  getAuthorizedPDA,
+  getConfigPDA,
+  getFeeLedgerPDA,
+  getMarketPDA,
+  getMarketPortfolioPDA,
+  getUserPDA,
+  getVaultPDA,
+} from "../utils/pdas";
+
+describe("scalar market", () => {
+  const MINT = new PublicKey("5DUWZLh3zPKAAJKu7ftMJJrkBrKnq3zHPPmguzVkhSes");
+
+  const program = getProgram();
+
+  const marketKeypair = anchor.web3.Keypair.generate();
+
+  const authorizedKeypair = getAuthorizedUserKeypair();
+  const walletManager = getWalletManagerKeypair();
+  const userKeypair = getUserKeypair();
+
+  const configPDA = getConfigPDA(program);
+  const feeLedgerPDA = getFeeLedgerPDA(program);
+  const vaultPDA = getVaultPDA(MINT, program);
+  const marketPDA = getMarketPDA(marketKeypair.publicKey, program);
+  const authorizedUserPda = getAuthorizedPDA(authorizedKeypair.publicKey, program);
+  const userPDA = getUserPDA(userKeypair.publicKey, program);
+  const marketPortfolioPDA = getMarketPortfolioPDA(marketPDA, userPDA, program);
+
+  const from = getAssociatedTokenAddressSync(MINT, walletManager.publicKey);
+
+  const now = new Date();
+
+  // LONG is choice 1 and SHORT choice 2
+  const initMarketData = [
+    {
+      id: new anchor.BN(1),
+      initPot: new anchor.BN(100 * Math.pow(10,9)),
+      curve: { constantProduct: {} },
+      choiceIds: [new anchor.BN(1), new anchor.BN(2)],
+      fairLaunchStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 3),
+      fairLaunchEnd: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 2),
+      tradingStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60),
+      tradingEnd: new anchor.BN(now.valueOf() / 1000 + 60 * 60),
+      scalar: { lower: new anchor.BN(0), upper: new anchor.BN(100) },
+    },
+  ];
+
+  const claimWinnings = (choiceId: number) =>
+    program.methods
+      .userClaimWinnings(new anchor.BN(1), new anchor.BN(choiceId))
+      .signers([userKeypair])
+      .accounts({
+        signer: userKeypair.publicKey,
+        config: configPDA,
+        user: userPDA,
+        market: marketPDA,
+        marketPortfolio: marketPortfolioPDA,
+      })
+      .rpc();
+
+  it("fails to create a scalar market with an empty range", async () => {
+    const otherKeypair = anchor.web3.Keypair.generate();
+
+    try {
+      await program.methods
+        .initMarket(
+          [{ ...initMarketData[0], scalar: { lower: new anchor.BN(100), upper: new anchor.BN(100) } }],
+          otherKeypair.publicKey
+        )
+        .signers([authorizedKeypair])
+        .accounts({
+          config: configPDA,
+          signer: authorizedKeypair.publicKey,
+          market: getMarketPDA(otherKeypair.publicKey, program),
+          authorizedUser: authorizedUserPda,
+        })
+        .rpc();
+      expect.fail("init should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal(
+        "Scalar sub markets need exactly two choices and a lower bound below the upper bound."
+      );
+    }
+  });
+
+  it("creates a scalar market", async () => {
+    await program.methods
+      .initMarket(initMarketData, marketKeypair.publicKey)
+      .signers([authorizedKeypair])
+      .accounts({
+        config: configPDA,
+        signer: authorizedKeypair.publicKey,
+        market: marketPDA,
+        authorizedUser: authorizedUserPda,
+      })
+      .rpc();
+
+    await program.methods
+      .deposit(new anchor.BN(2 * Math.pow(10,9)))
+      .signers([walletManager])
+      .accounts({
+        config: configPDA,
+        user: userPDA,
+        signer: walletManager.publicKey,
+        fromUsdcAccount: from,
+        vault: vaultPDA,
+        mint: MINT,
+      })
+      .rpc();
+
+    await program.methods
+      .mintCompleteSet(new anchor.BN(1), new anchor.BN(2 * Math.pow(10,9)))
+      .signers([userKeypair])
+      .accounts({
+        signer: userKeypair.publicKey,
+        config: configPDA,
+        user: userPDA,
+        market: marketPDA,
+        marketPortfolio: marketPortfolioPDA,
+      })
+      .rpc();
+
+    const market = await program.account.market.fetch(marketPDA);
+
+    expect(market.subMarkets[0].scalar.lower.toNumber()).to.equal(0);
+    expect(market.subMarkets[0].scalar.upper.toNumber()).to.equal(100);
+  });
+
+  it("fails to resolve a scalar market to a choice", async () => {
+    try {
+      await program.methods
+        .resolveMarket(new anchor.BN(1), new anchor.BN(1))
+        .signers([authorizedKeypair])
+        .accounts({
+          config: configPDA,
+          signer: authorizedKeypair.publicKey,
+          market: marketPDA,
+          authorizedUser: authorizedUserPda,
+        })
+        .rpc();
+      expect.fail("resolve should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal("Scalar sub markets resolve to a value, not a choice.");
+    }
+  });
+
+  it("proposes a value clamped to the range", async () => {
+    await program.methods
+      .resolveScalar(new anchor.BN(1), new anchor.BN(150))
+      .signers([authorizedKeypair])
+      .accounts({
+        config: configPDA,
+        signer: authorizedKeypair.publicKey,
+        market: marketPDA,
+        authorizedUser: authorizedUserPda,
+      })
+      .rpc();
+
+    const market = await program.account.market.fetch(marketPDA);
+
+    expect(market.subMarkets[0].resolution).to.deep.equal({ proposed: {} });
+    expect(market.subMarkets[0].choices[0].payoutNumerator.toNumber()).to.equal(Math.pow(10,9));
+    expect(market.subMarkets[0].choices[1].payoutNumerator.toNumber()).to.equal(0);
+  });
+
+  it("arbiter corrects the value", async () => {
+    await program.methods
+      .correctScalarResolution(new anchor.BN(1), new anchor.BN(75))
+      .signers([authorizedKeypair])
+      .accounts({
+        config: configPDA,
+        signer: authorizedKeypair.publicKey,
+        market: marketPDA,
+        authorizedUser: authorizedUserPda,
+        feeLedger: feeLedgerPDA,
+      })
+      .rpc();
+
+    const market = await program.account.market.fetch(marketPDA);
+
+    expect(market.subMarkets[0].resolution).to.deep.equal({ finalized: {} });
+    expect(market.subMarkets[0].choices[0].payoutNumerator.toNumber()).to.equal(75 * Math.pow(10,7));
+    expect(market.subMarkets[0].choices[1].payoutNumerator.toNumber()).to.equal(25 * Math.pow(10,7));
+  });
+
+  it("pays LONG and SHORT in proportion to the value", async () => {
+    const before = await program.account.user.fetch(userPDA);
+
+    await claimWinnings(1);
+    const afterLong = await program.account.user.fetch(userPDA);
+
+    await claimWinnings(2);
+    const afterShort = await program.account.user.fetch(userPDA);
+
+    const long = afterLong.balance.toNumber() - before.balance.toNumber();
+    const short = afterShort.balance.toNumber() - afterLong.balance.toNumber();
+
+    expect(long).to.be.greaterThan(0);
+    expect(long / short).to.be.closeTo(3, 0.000001);
+  });
+});
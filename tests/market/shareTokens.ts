@@ -0,0 +1,242 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  createAssociatedTokenAccount,
+  getAccount,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import {
+  getAuthorizedUserKeypair,
+  getUserKeypair,
+  getWalletManagerKeypair,
+} from "../utils/wallets";
+import { getProgram } from "../utils/program";
+import {
+  getAuthorizedPDA,
+  getConfigPDA,
+  getEventQueuePDA,
+  getFeeLedgerPDA,
+  getMarketPDA,
+  getMarketPortfolioPDA,
+  getOrderBookPDA,
+  getShareMintPDA,
+  getUserPDA,
+  getVaultPDA,
+} from "../utils/pdas";
+
+describe("share tokens", () => {
+  const MINT = new PublicKey("5DUWZLh3zPKAAJKu7ftMJJrkBrKnq3zHPPmguzVkhSes");
+
+  const provider = anchor.AnchorProvider.env();
+  const program = getProgram();
+
+  const marketKeypair = anchor.web3.Keypair.generate();
+
+  const authorizedKeypair = getAuthorizedUserKeypair();
+  const walletManager = getWalletManagerKeypair();
+  const userKeypair = getUserKeypair();
+
+  const configPDA = getConfigPDA(program);
+  const feeLedgerPDA = getFeeLedgerPDA(program);
+  const vaultPDA = getVaultPDA(MINT, program);
+  const marketPDA = getMarketPDA(marketKeypair.publicKey, program);
+  const authorizedUserPda = getAuthorizedPDA(authorizedKeypair.publicKey, program);
+
+  const userPDA = getUserPDA(userKeypair.publicKey, program);
+  const marketPortfolioPDA = getMarketPortfolioPDA(marketPDA, userPDA, program);
+
+  const shareMints = [1, 2].map((choiceId) => getShareMintPDA(marketPDA, 1, choiceId, program));
+  const shareAccounts = shareMints.map((shareMint) =>
+    getAssociatedTokenAddressSync(shareMint, userKeypair.publicKey)
+  );
+
+  const from = getAssociatedTokenAddressSync(MINT, walletManager.publicKey);
+
+  const now = new Date();
+
+  const initMarketData = [
+    {
+      id: new anchor.BN(1),
+      initPot: new anchor.BN(100 * Math.pow(10,9)),
+      curve: { constantProduct: {} },
+      choiceIds: [new anchor.BN(1), new anchor.BN(2)],
+      fairLaunchStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 3),
+      fairLaunchEnd: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 2),
+      tradingStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60),
+      tradingEnd: new anchor.BN(now.valueOf() / 1000 + 60 * 60),
+    },
+  ];
+
+  const tradeAccounts = {
+    signer: userKeypair.publicKey,
+    config: configPDA,
+    user: userPDA,
+    market: marketPDA,
+    marketPortfolio: marketPortfolioPDA,
+    feeLedger: feeLedgerPDA,
+  };
+
+  const shareRemainingAccounts = [
+    { pubkey: shareMints[0], isSigner: false, isWritable: true },
+    { pubkey: shareAccounts[0], isSigner: false, isWritable: true },
+  ];
+
+  before(async () => {
+    await program.methods
+      .initMarket(initMarketData, marketKeypair.publicKey)
+      .signers([authorizedKeypair])
+      .accounts({
+        config: configPDA,
+        signer: authorizedKeypair.publicKey,
+        market: marketPDA,
+        authorizedUser: authorizedUserPda,
+      })
+      .rpc();
+
+    await program.methods
+      .deposit(new anchor.BN(5 * Math.pow(10,9)))
+      .signers([walletManager])
+      .accounts({
+        config: configPDA,
+        user: userPDA,
+        signer: walletManager.publicKey,
+        fromUsdcAccount: from,
+        vault: vaultPDA,
+        mint: MINT,
+      })
+      .rpc();
+  });
+
+  it("tokenizes a sub market once every choice has a mint", async () => {
+    for (const [index, shareMint] of shareMints.entries()) {
+      await program.methods
+        .initShareMint(new anchor.BN(1), new anchor.BN(index + 1))
+        .signers([authorizedKeypair])
+        .accounts({
+          signer: authorizedKeypair.publicKey,
+          authorizedUser: authorizedUserPda,
+          market: marketPDA,
+          shareMint,
+        })
+        .rpc();
+
+      await createAssociatedTokenAccount(
+        provider.connection,
+        walletManager,
+        shareMint,
+        userKeypair.publicKey
+      );
+    }
+
+    const market = await program.account.market.fetch(marketPDA);
+
+    expect(market.subMarkets[0].shareMintCount).to.equal(2);
+  });
+
+  it("mints bought shares to the user's token account", async () => {
+    await program.methods
+      .userBulkBuyByPrice([
+        {
+          amount: new anchor.BN(2 * Math.pow(10,9)),
+          subMarketId: new anchor.BN(1),
+          choiceId: new anchor.BN(1),
+        },
+      ])
+      .signers([userKeypair])
+      .accounts(tradeAccounts)
+      .remainingAccounts(shareRemainingAccounts)
+      .rpc();
+
+    const shareAccount = await getAccount(provider.connection, shareAccounts[0]);
+    const marketPortfolio = await program.account.marketPortfolio.fetch(marketPortfolioPDA);
+    const market = await program.account.market.fetch(marketPDA);
+
+    expect(Number(shareAccount.amount)).to.equal(market.subMarkets[0].choices[0].mintedShares.toNumber());
+    expect(marketPortfolio.subMarketPortfolio[0].choicePortfolio[0].shares.toNumber()).to.equal(0);
+  });
+
+  it("burns sold shares from the user's token account", async () => {
+    const before = await getAccount(provider.connection, shareAccounts[0]);
+
+    await program.methods
+      .userBulkSellByShares([
+        {
+          amount: new anchor.BN(1 * Math.pow(10,9)),
+          subMarketId: new anchor.BN(1),
+          choiceId: new anchor.BN(1),
+        },
+      ])
+      .signers([userKeypair])
+      .accounts(tradeAccounts)
+      .remainingAccounts(shareRemainingAccounts)
+      .rpc();
+
+    const shareAccount = await getAccount(provider.connection, shareAccounts[0]);
+
+    expect(Number(before.amount - shareAccount.amount) / Math.pow(10,9)).to.equal(1);
+  });
+
+  it("fails to trade a tokenized sub market without its share accounts", async () => {
+    try {
+      await program.methods
+        .userBulkBuyByPrice([
+          {
+            amount: new anchor.BN(1 * Math.pow(10,9)),
+            subMarketId: new anchor.BN(1),
+            choiceId: new anchor.BN(1),
+          },
+        ])
+        .signers([userKeypair])
+        .accounts(tradeAccounts)
+        .rpc();
+      expect.fail("buy should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal("Share mint or share token account is missing or doesn't match.");
+    }
+  });
+
+  it("fails to mint complete sets of a tokenized sub market", async () => {
+    try {
+      await program.methods
+        .mintCompleteSet(new anchor.BN(1), new anchor.BN(1 * Math.pow(10,9)))
+        .signers([userKeypair])
+        .accounts({
+          signer: userKeypair.publicKey,
+          config: configPDA,
+          user: userPDA,
+          market: marketPDA,
+          marketPortfolio: marketPortfolioPDA,
+        })
+        .rpc();
+      expect.fail("mint should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal("Shares of this sub market are held as tokens.");
+    }
+  });
+
+  it("fails to open an order book on a tokenized sub market", async () => {
+    const orderBookPDA = getOrderBookPDA(marketPDA, 1, 1, program);
+
+    try {
+      await program.methods
+        .initOrderBook(new anchor.BN(1), new anchor.BN(1))
+        .signers([authorizedKeypair])
+        .accounts({
+          signer: authorizedKeypair.publicKey,
+          config: configPDA,
+          authorizedUser: authorizedUserPda,
+          market: marketPDA,
+          orderBook: orderBookPDA,
+          eventQueue: getEventQueuePDA(orderBookPDA, program),
+        })
+        .rpc();
+      expect.fail("order book should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal("Shares of this sub market are held as tokens.");
+    }
+  });
+});
@@ -5,7 +5,6 @@ import {
   getAssociatedTokenAccount,
   getAuthorizedUserKeypair,
   getClobManagerKeypair,
-  getFeeManagerKeypair,
   getUserKeypair,
   getWalletManagerKeypair,
   getWalletManagerTokenAccount,
@@ -13,6 +12,9 @@ import {
 import { getProgram } from "../utils/program";
 import {
   getAuthorizedPDA,
+  getConfigPDA,
+  getFeeLedgerPDA,
+  getVaultPDA,
   getMarketPDA,
   getMarketPortfolioPDA,
   getUserPDA,
@@ -33,10 +35,15 @@ describe("trading", () => {
   let marketKeypair = anchor.web3.Keypair.generate();
 
   let authorizedKeypair = getAuthorizedUserKeypair();
-  let feeManagerKeypair = getFeeManagerKeypair();
   let walletManager = getWalletManagerKeypair();
   let userKeypair = getUserKeypair();
 
+  const configPDA = getConfigPDA(program);
+
+  const feeLedgerPDA = getFeeLedgerPDA(program);
+
+  const vaultPDA = getVaultPDA(MINT, program);
+
   const marketPDA = getMarketPDA(marketKeypair.publicKey, program);
 
   const authorizedUserPda = getAuthorizedPDA(
@@ -52,10 +59,6 @@ describe("trading", () => {
     MINT,
     walletManager.publicKey
   );
-  const feeAccount = getAssociatedTokenAddressSync(
-    MINT,
-    feeManagerKeypair.publicKey
-  );
 
   let now = new Date();
 
@@ -63,6 +66,7 @@ describe("trading", () => {
     {
       id: new anchor.BN(1),
       initPot: new anchor.BN(100 * Math.pow(10,9)),
+      curve: { constantProduct: {} },
       choiceIds: [new anchor.BN(1), new anchor.BN(2)],
       fairLaunchStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 3),
       fairLaunchEnd: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 2),
@@ -72,6 +76,7 @@ describe("trading", () => {
     {
       id: new anchor.BN(2),
       initPot: new anchor.BN(100 * Math.pow(10,9)),
+      curve: { constantProduct: {} },
       choiceIds: [new anchor.BN(1), new anchor.BN(2)],
       fairLaunchStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 3),
       fairLaunchEnd: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 2),
@@ -86,6 +91,7 @@ describe("trading", () => {
       .initMarket(initMarketData, marketKeypair.publicKey)
       .signers([authorizedKeypair])
       .accounts({
+        config: configPDA,
         signer: authorizedKeypair.publicKey,
         market: marketPDA,
         authorizedUser: authorizedUserPda,
@@ -100,25 +106,30 @@ describe("trading", () => {
         .withdrawFromBalance(user.balance)
         .signers([walletManager])
         .accounts({
+          config: configPDA,
           user: userPDA,
           signer: walletManager.publicKey,
           mint: MINT,
-          fromUsdcAccount: getWalletManagerTokenAccount(MINT),
+          vault: vaultPDA,
           toUsdcAccount: await getAssociatedTokenAccount(
             MINT,
             userKeypair.publicKey
           ),
-          feeUsdcAccount: feeAccount
+          feeLedger: feeLedgerPDA
         })
         .rpc().catch((err) => console.log(err));
     }
 
     await program.methods
-      .addToBalance(new anchor.BN(6 * Math.pow(10,9)))
+      .deposit(new anchor.BN(6 * Math.pow(10,9)))
       .signers([walletManager])
       .accounts({
+        config: configPDA,
         user: userPDA,
         signer: walletManager.publicKey,
+        fromUsdcAccount: from,
+        vault: vaultPDA,
+        mint: MINT,
       })
       .rpc();
   });
@@ -141,18 +152,16 @@ describe("trading", () => {
             amount: new anchor.BN(1 * Math.pow(10,9)),
             subMarketId: new anchor.BN(1),
             choiceId: new anchor.BN(1),
-            requestedPricePerShare: 0.5,
           },
         ])
         .signers([userKeypair])
         .accounts({
+          config: configPDA,
           signer: userKeypair.publicKey,
           user: userPDA,
           market: marketPDA,
           marketPortfolio: marketPortfolioPDA,
-          mint: MINT,
-          fromUsdcAccount: from,
-          feeUsdcAccount: feeAccount
+          feeLedger: feeLedgerPDA
         })
         .rpc();
     } catch (err) {
@@ -171,31 +180,27 @@ describe("trading", () => {
             amount: new anchor.BN(1 * Math.pow(10,9)),
             subMarketId: new anchor.BN(1),
             choiceId: new anchor.BN(1),
-            requestedPricePerShare: 0.5 ,
           },
           {
             amount: new anchor.BN(1 * Math.pow(10,9)),
             subMarketId: new anchor.BN(1),
             choiceId: new anchor.BN(2),
-            requestedPricePerShare: 0.5,
           },
           {
             amount: new anchor.BN(1 * Math.pow(10,9)),
             subMarketId: new anchor.BN(1),
             choiceId: new anchor.BN(2),
-            requestedPricePerShare: 0.5,
           },
         ])
         .signers([walletManager])
         
         .accounts({
+          config: configPDA,
           signer: walletManager.publicKey,
           user: userPDA,
           market: marketPDA,
           marketPortfolio: marketPortfolioPDA,
-          mint: MINT,
-          fromUsdcAccount: from,
-          feeUsdcAccount: feeAccount
+          feeLedger: feeLedgerPDA
         })
         .rpc();
     } catch (err) {
@@ -213,25 +218,22 @@ describe("trading", () => {
             amount: new anchor.BN(1 * Math.pow(10,9)),
             subMarketId: new anchor.BN(1),
             choiceId: new anchor.BN(1),
-            requestedPricePerShare: 0.5,
           },
           {
             amount: new anchor.BN(1 * Math.pow(10,9)),
             subMarketId: new anchor.BN(1),
             choiceId: new anchor.BN(2),
-            requestedPricePerShare: 0.5,
           },
         ])
         .signers([walletManager])
         
         .accounts({
+          config: configPDA,
           signer: walletManager.publicKey,
           user: userPDA,
           market: marketPDA,
           marketPortfolio: marketPortfolioPDA,
-          mint: MINT,
-          fromUsdcAccount: from,
-          feeUsdcAccount: feeAccount
+          feeLedger: feeLedgerPDA
         })
         .rpc();
     } catch (err) {
@@ -250,19 +252,17 @@ describe("trading", () => {
             amount: new anchor.BN(20 * Math.pow(10,9)),
             subMarketId: new anchor.BN(1),
             choiceId: new anchor.BN(1),
-            requestedPricePerShare: 0.524,
           },
         ])
         .signers([walletManager])
         .preInstructions([additionalComputeBudgetInstruction])
         .accounts({
+          config: configPDA,
           signer: walletManager.publicKey,
           user: userPDA,
           market: marketPDA,
           marketPortfolio: marketPortfolioPDA,
-          mint: MINT,
-          fromUsdcAccount: from,
-          feeUsdcAccount: feeAccount
+          feeLedger: feeLedgerPDA
         })
         .rpc();
     } catch (err) {
@@ -272,7 +272,7 @@ describe("trading", () => {
     }
   });
 
-  it("fails to buy by shares due to estimation off", async () => {
+  it("fails to buy by shares due to slippage", async () => {
     try {
       await program.methods
         .bulkBuyByShares([
@@ -280,25 +280,24 @@ describe("trading", () => {
             amount: new anchor.BN(1 * Math.pow(10,9)),
             subMarketId: new anchor.BN(1),
             choiceId: new anchor.BN(1),
-            requestedPricePerShare: 0.1,
+            maxCost: new anchor.BN(1 * Math.pow(10,8)),
           },
         ])
         .signers([walletManager])
         .preInstructions([additionalComputeBudgetInstruction])
         .accounts({
+          config: configPDA,
           signer: walletManager.publicKey,
           user: userPDA,
           market: marketPDA,
           marketPortfolio: marketPortfolioPDA,
-          mint: MINT,
-          fromUsdcAccount: from,
-          feeUsdcAccount: feeAccount
+          feeLedger: feeLedgerPDA
         })
         .rpc();
     } catch (err) {
       const error = err as anchor.AnchorError;
       let expectedMsg =
-        "Requested price is to far off from acutal price, cancelling order.";
+        "Order filled outside of its slippage bounds, cancelling order.";
       expect(error.error.errorMessage).to.equal(expectedMsg);
     }
   });
@@ -311,19 +310,17 @@ describe("trading", () => {
             amount: new anchor.BN(1 * Math.pow(10,9)),
             subMarketId: new anchor.BN(1),
             choiceId: new anchor.BN(1),
-            requestedPricePerShare: 0.5,
           },
         ])
         .signers([walletManager])
         
         .accounts({
+          config: configPDA,
           signer: walletManager.publicKey,
           user: userPDA,
           market: marketPDA,
           marketPortfolio: marketPortfolioPDA,
-          mint: MINT,
-          fromUsdcAccount: from,
-          feeUsdcAccount: feeAccount
+          feeLedger: feeLedgerPDA
         })
         .rpc();
     } catch (err) {
@@ -342,19 +339,17 @@ describe("trading", () => {
             amount: new anchor.BN(1 * Math.pow(10,9)),
             subMarketId: new anchor.BN(1),
             choiceId: new anchor.BN(1),
-            requestedPricePerShare: 0.5,
           },
         ])
         .signers([walletManager])
         
         .accounts({
+          config: configPDA,
           signer: walletManager.publicKey,
           user: userPDA,
           market: marketPDA,
           marketPortfolio: marketPortfolioPDA,
-          mint: MINT,
-          fromUsdcAccount: from,
-          feeUsdcAccount: feeAccount
+          feeLedger: feeLedgerPDA
         })
         .rpc();
     } catch (err) {
@@ -372,19 +367,18 @@ describe("trading", () => {
           amount: new anchor.BN(5 * Math.pow(10,9)),
           subMarketId: new anchor.BN(1),
           choiceId: new anchor.BN(1),
-          requestedPricePerShare: 0.5121,
+          minSharesOut: new anchor.BN(97 * Math.pow(10,8)),
         },
       ])
       .signers([walletManager])
       
       .accounts({
+        config: configPDA,
         signer: walletManager.publicKey,
         user: userPDA,
         market: marketPDA,
         marketPortfolio: marketPortfolioPDA,
-        mint: MINT,
-        fromUsdcAccount: from,
-        feeUsdcAccount: feeAccount
+        feeLedger: feeLedgerPDA
       })
       .rpc().catch(err => console.log(err));
       
@@ -409,19 +403,19 @@ describe("trading", () => {
           amount: new anchor.BN(1 * Math.pow(10,9)),
           subMarketId: new anchor.BN(1),
           choiceId: new anchor.BN(1),
-          requestedPricePerShare: 0.5433,
+          minSharesOut: new anchor.BN(19 * Math.pow(10,8)),
+          toleranceBps: 100,
         },
       ])
       .signers([walletManager])
       
       .accounts({
+        config: configPDA,
         signer: walletManager.publicKey,
         user: userPDA,
         market: marketPDA,
         marketPortfolio: marketPortfolioPDA,
-        mint: MINT,
-        fromUsdcAccount: from,
-        feeUsdcAccount: feeAccount
+        feeLedger: feeLedgerPDA
       })
       .rpc().catch(err => console.log(err));
       
@@ -447,19 +441,18 @@ describe("trading", () => {
           amount: new anchor.BN(1.88945 * Math.pow(10,9)),
           subMarketId: new anchor.BN(1),
           choiceId: new anchor.BN(1),
-          requestedPricePerShare: 0.54332,
+          minProceeds: new anchor.BN(99 * Math.pow(10,7)),
         },
       ])
       .signers([walletManager])
       .preInstructions([additionalComputeBudgetInstruction])
       .accounts({
+        config: configPDA,
         signer: walletManager.publicKey,
         user: userPDA,
         market: marketPDA,
         marketPortfolio: marketPortfolioPDA,
-        mint: MINT,
-        fromUsdcAccount: from,
-        feeUsdcAccount: feeAccount
+        feeLedger: feeLedgerPDA
       })
       .rpc().catch(err => console.log(err));
 
@@ -486,19 +479,17 @@ describe("trading", () => {
           amount: new anchor.BN(30 * Math.pow(10,9)),
           subMarketId: new anchor.BN(1),
           choiceId: new anchor.BN(1),
-          requestedPricePerShare: 0.2279,
         },
       ])
       .signers([walletManager])
       
       .accounts({
+        config: configPDA,
         signer: walletManager.publicKey,
         user: userPDA,
         market: marketPDA,
         marketPortfolio: marketPortfolioPDA,
-        mint: MINT,
-        fromUsdcAccount: from,
-        feeUsdcAccount: feeAccount
+        feeLedger: feeLedgerPDA
       })
       .rpc();
     } catch (err) {
@@ -509,5 +500,41 @@ describe("trading", () => {
     }
   });
 
-  
+  it("sells by price and pays the trade fee once", async () => {
+    const amount = new anchor.BN(1 * Math.pow(10,9));
+    const config = await program.account.programConfig.fetch(configPDA);
+    const before = await program.account.user.fetch(userPDA);
+    const marketBefore = await program.account.market.fetch(marketPDA);
+
+    await program.methods
+      .bulkSellByPrice([
+        {
+          amount,
+          subMarketId: new anchor.BN(1),
+          choiceId: new anchor.BN(1),
+        },
+      ])
+      .signers([walletManager])
+      .preInstructions([additionalComputeBudgetInstruction])
+      .accounts({
+        config: configPDA,
+        signer: walletManager.publicKey,
+        user: userPDA,
+        market: marketPDA,
+        marketPortfolio: marketPortfolioPDA,
+        feeLedger: feeLedgerPDA
+      })
+      .rpc();
+
+    const user = await program.account.user.fetch(userPDA);
+    const market = await program.account.market.fetch(marketPDA);
+
+    const pot = (subMarket: any) => subMarket.choices
+      .reduce((sum: anchor.BN, choice: any) => sum.add(choice.usdcPot), new anchor.BN(0));
+    const fee = amount.sub(user.balance.sub(before.balance));
+
+    // the curve pays out the whole price and the seller gets it less one fee
+    expect(pot(marketBefore.subMarkets[0]).sub(pot(market.subMarkets[0])).toString()).to.equal(amount.toString());
+    expect(fee.lte(amount.muln(config.feeSchedule.tradeBps).divn(10_000))).to.equal(true);
+  });
 });
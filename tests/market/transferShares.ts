@@ -0,0 +1,145 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import {
+  getAuthorizedUserKeypair,
+  getUserKeypair,
+  getWalletManagerKeypair,
+} from "../utils/wallets";
+import { getProgram } from "../utils/program";
+import {
+  getAuthorizedPDA,
+  getConfigPDA,
+  getMarketPDA,
+  getMarketPortfolioPDA,
+  getUserPDA,
+  getVaultPDA,
+} from "../utils/pdas";
+
+describe("transfer shares", () => {
+  const MINT = new PublicKey("5DUWZLh3zPKAAJKu7ftMJJrkBrKnq3zHPPmguzVkhSes");
+
+  const program = getProgram();
+
+  const marketKeypair = anchor.web3.Keypair.generate();
+
+  const authorizedKeypair = getAuthorizedUserKeypair();
+  const walletManager = getWalletManagerKeypair();
+  const senderKeypair = getUserKeypair();
+
+  const configPDA = getConfigPDA(program);
+  const vaultPDA = getVaultPDA(MINT, program);
+  const marketPDA = getMarketPDA(marketKeypair.publicKey, program);
+  const authorizedUserPda = getAuthorizedPDA(authorizedKeypair.publicKey, program);
+
+  const senderPDA = getUserPDA(senderKeypair.publicKey, program);
+  const recipientPDA = getUserPDA(walletManager.publicKey, program);
+  const senderPortfolioPDA = getMarketPortfolioPDA(marketPDA, senderPDA, program);
+  const recipientPortfolioPDA = getMarketPortfolioPDA(marketPDA, recipientPDA, program);
+
+  const from = getAssociatedTokenAddressSync(MINT, walletManager.publicKey);
+
+  const now = new Date();
+
+  const initMarketData = [
+    {
+      id: new anchor.BN(1),
+      initPot: new anchor.BN(100 * Math.pow(10,9)),
+      curve: { constantProduct: {} },
+      choiceIds: [new anchor.BN(1), new anchor.BN(2)],
+      fairLaunchStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 3),
+      fairLaunchEnd: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 2),
+      tradingStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60),
+      tradingEnd: new anchor.BN(now.valueOf() / 1000 + 60 * 60),
+    },
+  ];
+
+  const transferShares = (signer: anchor.web3.Keypair, sender: PublicKey, recipient: PublicKey, shares: number) =>
+    program.methods
+      .transferShares(new anchor.BN(1), new anchor.BN(1), new anchor.BN(shares * Math.pow(10,9)))
+      .signers([signer])
+      .accounts({
+        signer: signer.publicKey,
+        config: configPDA,
+        sender,
+        recipient,
+        market: marketPDA,
+        senderPortfolio: getMarketPortfolioPDA(marketPDA, sender, program),
+        recipientPortfolio: getMarketPortfolioPDA(marketPDA, recipient, program),
+      })
+      .rpc();
+
+  before(async () => {
+    await program.methods
+      .initMarket(initMarketData, marketKeypair.publicKey)
+      .signers([authorizedKeypair])
+      .accounts({
+        config: configPDA,
+        signer: authorizedKeypair.publicKey,
+        market: marketPDA,
+        authorizedUser: authorizedUserPda,
+      })
+      .rpc();
+
+    await program.methods
+      .userInitWallet()
+      .signers([walletManager])
+      .accounts({ config: configPDA, user: recipientPDA, signer: walletManager.publicKey })
+      .rpc()
+      .catch((_) => {});
+
+    await program.methods
+      .deposit(new anchor.BN(2 * Math.pow(10,9)))
+      .signers([walletManager])
+      .accounts({
+        config: configPDA,
+        user: senderPDA,
+        signer: walletManager.publicKey,
+        fromUsdcAccount: from,
+        vault: vaultPDA,
+        mint: MINT,
+      })
+      .rpc();
+
+    await program.methods
+      .mintCompleteSet(new anchor.BN(1), new anchor.BN(2 * Math.pow(10,9)))
+      .signers([senderKeypair])
+      .accounts({
+        signer: senderKeypair.publicKey,
+        config: configPDA,
+        user: senderPDA,
+        market: marketPDA,
+        marketPortfolio: senderPortfolioPDA,
+      })
+      .rpc();
+  });
+
+  it("transfers shares with the owner's signature", async () => {
+    await transferShares(senderKeypair, senderPDA, recipientPDA, 1.5);
+
+    const senderPortfolio = await program.account.marketPortfolio.fetch(senderPortfolioPDA);
+    const recipientPortfolio = await program.account.marketPortfolio.fetch(recipientPortfolioPDA);
+
+    expect(senderPortfolio.subMarketPortfolio[0].choicePortfolio[0].shares.toNumber() / Math.pow(10,9)).to.equal(0.5);
+    expect(recipientPortfolio.subMarketPortfolio[0].choicePortfolio[0].shares.toNumber() / Math.pow(10,9)).to.equal(1.5);
+  });
+
+  it("transfers shares through the wallet manager", async () => {
+    await transferShares(walletManager, senderPDA, recipientPDA, 0.5);
+
+    const senderPortfolio = await program.account.marketPortfolio.fetch(senderPortfolioPDA);
+
+    expect(senderPortfolio.subMarketPortfolio[0].choicePortfolio[0].shares.toNumber()).to.equal(0);
+  });
+
+  it("fails to transfer more shares than the sender holds", async () => {
+    try {
+      await transferShares(senderKeypair, senderPDA, recipientPDA, 1);
+      expect.fail("transfer should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal("Requested shares to sell greater than owned shares.");
+    }
+  });
+});
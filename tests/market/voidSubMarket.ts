@@ -0,0 +1,307 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import {
+  getAuthorizedUserKeypair,
+  getUserKeypair,
+  getWalletManagerKeypair,
+} from "../utils/wallets";
+import { getProgram } from "../utils/program";
+import {
+  getAuthorizedPDA,
+  getConfigPDA,
+  getEventQueuePDA,
+  getFeeLedgerPDA,
+  getMarketPDA,
+  getMarketPortfolioPDA,
+  getOrderBookPDA,
+  getUserPDA,
+  getVaultPDA,
+} from "../utils/pdas";
+
+describe("void sub market", () => {
+  const MINT = new PublicKey("5DUWZLh3zPKAAJKu7ftMJJrkBrKnq3zHPPmguzVkhSes");
+
+  const program = getProgram();
+
+  const marketKeypair = anchor.web3.Keypair.generate();
+
+  const authorizedKeypair = getAuthorizedUserKeypair();
+  const walletManager = getWalletManagerKeypair();
+  const userKeypair = getUserKeypair();
+
+  const configPDA = getConfigPDA(program);
+  const vaultPDA = getVaultPDA(MINT, program);
+  const marketPDA = getMarketPDA(marketKeypair.publicKey, program);
+  const authorizedUserPda = getAuthorizedPDA(authorizedKeypair.publicKey, program);
+  const userPDA = getUserPDA(userKeypair.publicKey, program);
+  const marketPortfolioPDA = getMarketPortfolioPDA(marketPDA, userPDA, program);
+  const feeLedgerPDA = getFeeLedgerPDA(program);
+
+  // sells into the user's bid on sub market 3
+  const sellerKeypair = walletManager;
+  const sellerPDA = getUserPDA(sellerKeypair.publicKey, program);
+
+  const orderBookPDA = getOrderBookPDA(marketPDA, 3, 1, program);
+  const eventQueuePDA = getEventQueuePDA(orderBookPDA, program);
+
+  const from = getAssociatedTokenAddressSync(MINT, walletManager.publicKey);
+
+  const now = new Date();
+
+  const subMarketData = {
+    initPot: new anchor.BN(100 * Math.pow(10,9)),
+    curve: { constantProduct: {} },
+    choiceIds: [new anchor.BN(1), new anchor.BN(2)],
+    fairLaunchStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 3),
+    fairLaunchEnd: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 2),
+    tradingStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60),
+    tradingEnd: new anchor.BN(now.valueOf() / 1000 + 60 * 60),
+  };
+
+  const initMarketData = [
+    { id: new anchor.BN(1), ...subMarketData },
+    { id: new anchor.BN(2), ...subMarketData },
+    { id: new anchor.BN(3), ...subMarketData, initPot: new anchor.BN(1 * Math.pow(10,8)) },
+  ];
+
+  const voidSubMarket = (subMarketId: number, refundPolicy: any) =>
+    program.methods
+      .voidSubMarket(new anchor.BN(subMarketId), refundPolicy)
+      .signers([authorizedKeypair])
+      .accounts({
+        signer: authorizedKeypair.publicKey,
+        authorizedUser: authorizedUserPda,
+        market: marketPDA,
+      })
+      .rpc();
+
+  const claimRefund = (subMarketId: number, keypair = userKeypair) => {
+    const user = getUserPDA(keypair.publicKey, program);
+
+    return program.methods
+      .userClaimRefund(new anchor.BN(subMarketId))
+      .signers([keypair])
+      .accounts({
+        signer: keypair.publicKey,
+        config: configPDA,
+        user,
+        market: marketPDA,
+        marketPortfolio: getMarketPortfolioPDA(marketPDA, user, program),
+      })
+      .rpc();
+  };
+
+  const placeOrder = (keypair: anchor.web3.Keypair, side: any, price: number, shares: number) => {
+    const user = getUserPDA(keypair.publicKey, program);
+
+    return program.methods
+      .placeOrder(
+        new anchor.BN(3),
+        new anchor.BN(1),
+        side,
+        new anchor.BN(price * Math.pow(10,9)),
+        new anchor.BN(shares * Math.pow(10,9))
+      )
+      .signers([keypair])
+      .accounts({
+        signer: keypair.publicKey,
+        config: configPDA,
+        user,
+        market: marketPDA,
+        marketPortfolio: getMarketPortfolioPDA(marketPDA, user, program),
+        orderBook: orderBookPDA,
+        eventQueue: eventQueuePDA,
+        feeLedger: feeLedgerPDA,
+      })
+      .rpc();
+  };
+
+  before(async () => {
+    await program.methods
+      .initMarket(initMarketData, marketKeypair.publicKey)
+      .signers([authorizedKeypair])
+      .accounts({
+        config: configPDA,
+        signer: authorizedKeypair.publicKey,
+        market: marketPDA,
+        authorizedUser: authorizedUserPda,
+      })
+      .rpc();
+
+    await program.methods
+      .deposit(new anchor.BN(6 * Math.pow(10,9)))
+      .signers([walletManager])
+      .accounts({
+        config: configPDA,
+        user: userPDA,
+        signer: walletManager.publicKey,
+        fromUsdcAccount: from,
+        vault: vaultPDA,
+        mint: MINT,
+      })
+      .rpc();
+
+    for (const subMarketId of [1, 2]) {
+      await program.methods
+        .mintCompleteSet(new anchor.BN(subMarketId), new anchor.BN(2 * Math.pow(10,9)))
+        .signers([userKeypair])
+        .accounts({
+          signer: userKeypair.publicKey,
+          config: configPDA,
+          user: userPDA,
+          market: marketPDA,
+          marketPortfolio: marketPortfolioPDA,
+        })
+        .rpc();
+    }
+  });
+
+  it("voids sub markets with a refund policy", async () => {
+    await voidSubMarket(1, { costBasis: {} });
+    await voidSubMarket(2, { proRata: {} });
+
+    const market = await program.account.market.fetch(marketPDA);
+
+    expect(market.subMarkets[0].voided).to.equal(true);
+    expect(market.subMarkets[0].resolved).to.equal(true);
+    expect(market.subMarkets[0].refundPolicy).to.deep.equal({ costBasis: {} });
+    expect(market.subMarkets[1].refundPolicy).to.deep.equal({ proRata: {} });
+  });
+
+  it("fails to void a sub market twice", async () => {
+    try {
+      await voidSubMarket(1, { proRata: {} });
+      expect.fail("void should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal("Market already resolved.");
+    }
+  });
+
+  it("fails to claim winnings from a voided sub market", async () => {
+    try {
+      await program.methods
+        .userClaimWinnings(new anchor.BN(1), new anchor.BN(1))
+        .signers([userKeypair])
+        .accounts({
+          signer: userKeypair.publicKey,
+          config: configPDA,
+          user: userPDA,
+          market: marketPDA,
+          marketPortfolio: marketPortfolioPDA,
+        })
+        .rpc();
+      expect.fail("claim should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal("Sub market was voided, claim a refund instead.");
+    }
+  });
+
+  it("refunds at cost basis", async () => {
+    const before = await program.account.user.fetch(userPDA);
+
+    await claimRefund(1);
+
+    const user = await program.account.user.fetch(userPDA);
+    const marketPortfolio = await program.account.marketPortfolio.fetch(marketPortfolioPDA);
+
+    expect(user.balance.toNumber() - before.balance.toNumber()).to.equal(2 * Math.pow(10,9));
+    expect(marketPortfolio.subMarketPortfolio[0].choicePortfolio.every(choice => choice.claimed)).to.equal(true);
+  });
+
+  it("refunds pro rata from the pots", async () => {
+    const before = await program.account.user.fetch(userPDA);
+
+    await claimRefund(2);
+
+    const user = await program.account.user.fetch(userPDA);
+
+    // the pots only grew by the set collateral, so every share is worth half a unit
+    expect(user.balance.toNumber() - before.balance.toNumber()).to.equal(2 * Math.pow(10,9));
+  });
+
+  it("fails to claim a refund twice", async () => {
+    try {
+      await claimRefund(1);
+      expect.fail("refund should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal("Nothing left to refund in this sub market.");
+    }
+  });
+
+  it("never refunds more than the sub market holds after a profitable book trade", async () => {
+    await program.methods
+      .userInitWallet()
+      .signers([sellerKeypair])
+      .accounts({ config: configPDA, user: sellerPDA, signer: sellerKeypair.publicKey })
+      .rpc()
+      .catch((_) => {});
+
+    await program.methods
+      .deposit(new anchor.BN(1 * Math.pow(10,9)))
+      .signers([walletManager])
+      .accounts({
+        config: configPDA,
+        user: sellerPDA,
+        signer: walletManager.publicKey,
+        fromUsdcAccount: from,
+        vault: vaultPDA,
+        mint: MINT,
+      })
+      .rpc();
+
+    // one set puts 1 into the sub market at a cost basis of 0.5 a share
+    await program.methods
+      .mintCompleteSet(new anchor.BN(3), new anchor.BN(1 * Math.pow(10,9)))
+      .signers([sellerKeypair])
+      .accounts({
+        signer: sellerKeypair.publicKey,
+        config: configPDA,
+        user: sellerPDA,
+        market: marketPDA,
+        marketPortfolio: getMarketPortfolioPDA(marketPDA, sellerPDA, program),
+      })
+      .rpc();
+
+    await program.methods
+      .initOrderBook(new anchor.BN(3), new anchor.BN(1))
+      .signers([authorizedKeypair])
+      .accounts({
+        signer: authorizedKeypair.publicKey,
+        config: configPDA,
+        authorizedUser: authorizedUserPda,
+        market: marketPDA,
+        orderBook: orderBookPDA,
+        eventQueue: eventQueuePDA,
+      })
+      .rpc();
+
+    // the 0.9 the user pays goes to the seller, not the pot
+    await placeOrder(sellerKeypair, { ask: {} }, 0.9, 1);
+    await placeOrder(userKeypair, { bid: {} }, 0.9, 1);
+
+    await voidSubMarket(3, { costBasis: {} });
+
+    const userBefore = await program.account.user.fetch(userPDA);
+    await claimRefund(3);
+    const user = await program.account.user.fetch(userPDA);
+
+    const sellerBefore = await program.account.user.fetch(sellerPDA);
+    await claimRefund(3, sellerKeypair);
+    const seller = await program.account.user.fetch(sellerPDA);
+
+    const subMarket = (await program.account.market.fetch(marketPDA)).subMarkets[2];
+    const held = subMarket.choices
+      .reduce((sum, choice) => sum.add(choice.usdcPot), new anchor.BN(0))
+      .add(subMarket.completeSetCollateral);
+
+    // 0.9 and 0.5 of cost basis against the 1.1 the sub market holds
+    expect(user.balance.sub(userBefore.balance).toNumber()).to.equal(9 * Math.pow(10,8));
+    expect(seller.balance.sub(sellerBefore.balance).toNumber()).to.equal(2 * Math.pow(10,8));
+    expect(subMarket.claimedCollateral.toString()).to.equal(held.toString());
+  });
+});
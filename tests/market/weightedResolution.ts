@@ -0,0 +1,193 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import {
+  getAuthorizedUserKeypair,
+  getUserKeypair,
+  getWalletManagerKeypair,
+} from "../utils/wallets";
+import { getProgram } from "../utils/program";
+import {
+  getAuthorizedPDA,
+  getConfigPDA,
+  getFeeLedgerPDA,
+  getMarketPDA,
+  getMarketPortfolioPDA,
+  getUserPDA,
+  getVaultPDA,
+} from "../utils/pdas";
+
+describe("weighted resolution", () => {
+  const MINT = new PublicKey("5DUWZLh3zPKAAJKu7ftMJJrkBrKnq3zHPPmguzVkhSes");
+
+  const program = getProgram();
+
+  const marketKeypair = anchor.web3.Keypair.generate();
+
+  const authorizedKeypair = getAuthorizedUserKeypair();
+  const walletManager = getWalletManagerKeypair();
+  const userKeypair = getUserKeypair();
+
+  const configPDA = getConfigPDA(program);
+  const feeLedgerPDA = getFeeLedgerPDA(program);
+  const vaultPDA = getVaultPDA(MINT, program);
+  const marketPDA = getMarketPDA(marketKeypair.publicKey, program);
+  const authorizedUserPda = getAuthorizedPDA(authorizedKeypair.publicKey, program);
+  const userPDA = getUserPDA(userKeypair.publicKey, program);
+  const marketPortfolioPDA = getMarketPortfolioPDA(marketPDA, userPDA, program);
+
+  const from = getAssociatedTokenAddressSync(MINT, walletManager.publicKey);
+
+  const now = new Date();
+
+  const initMarketData = [
+    {
+      id: new anchor.BN(1),
+      initPot: new anchor.BN(150 * Math.pow(10,9)),
+      curve: { constantProduct: {} },
+      choiceIds: [new anchor.BN(1), new anchor.BN(2), new anchor.BN(3)],
+      fairLaunchStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 3),
+      fairLaunchEnd: new anchor.BN(now.valueOf() / 1000 - 60 * 60 * 2),
+      tradingStart: new anchor.BN(now.valueOf() / 1000 - 60 * 60),
+      tradingEnd: new anchor.BN(now.valueOf() / 1000 + 60 * 60),
+    },
+  ];
+
+  const half = new anchor.BN(5 * Math.pow(10,8));
+
+  const claimWinnings = (choiceId: number) =>
+    program.methods
+      .userClaimWinnings(new anchor.BN(1), new anchor.BN(choiceId))
+      .signers([userKeypair])
+      .accounts({
+        signer: userKeypair.publicKey,
+        config: configPDA,
+        user: userPDA,
+        market: marketPDA,
+        marketPortfolio: marketPortfolioPDA,
+      })
+      .rpc();
+
+  before(async () => {
+    await program.methods
+      .initMarket(initMarketData, marketKeypair.publicKey)
+      .signers([authorizedKeypair])
+      .accounts({
+        config: configPDA,
+        signer: authorizedKeypair.publicKey,
+        market: marketPDA,
+        authorizedUser: authorizedUserPda,
+      })
+      .rpc();
+
+    await program.methods
+      .deposit(new anchor.BN(3 * Math.pow(10,9)))
+      .signers([walletManager])
+      .accounts({
+        config: configPDA,
+        user: userPDA,
+        signer: walletManager.publicKey,
+        fromUsdcAccount: from,
+        vault: vaultPDA,
+        mint: MINT,
+      })
+      .rpc();
+
+    await program.methods
+      .mintCompleteSet(new anchor.BN(1), new anchor.BN(3 * Math.pow(10,9)))
+      .signers([userKeypair])
+      .accounts({
+        signer: userKeypair.publicKey,
+        config: configPDA,
+        user: userPDA,
+        market: marketPDA,
+        marketPortfolio: marketPortfolioPDA,
+      })
+      .rpc();
+  });
+
+  it("fails to propose payouts that don't add up to a whole share", async () => {
+    try {
+      await program.methods
+        .resolveWeighted(new anchor.BN(1), [half, half, half])
+        .signers([authorizedKeypair])
+        .accounts({
+          config: configPDA,
+          signer: authorizedKeypair.publicKey,
+          market: marketPDA,
+          authorizedUser: authorizedUserPda,
+        })
+        .rpc();
+      expect.fail("resolve should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal(
+        "Payouts need one numerator per choice and have to add up to a whole share."
+      );
+    }
+  });
+
+  it("proposes a dead heat", async () => {
+    await program.methods
+      .resolveWeighted(new anchor.BN(1), [half, half, new anchor.BN(0)])
+      .signers([authorizedKeypair])
+      .accounts({
+        config: configPDA,
+        signer: authorizedKeypair.publicKey,
+        market: marketPDA,
+        authorizedUser: authorizedUserPda,
+      })
+      .rpc();
+
+    const market = await program.account.market.fetch(marketPDA);
+
+    expect(market.subMarkets[0].resolution).to.deep.equal({ proposed: {} });
+    expect(market.subMarkets[0].choices.map(choice => choice.payoutNumerator.toNumber()))
+      .to.deep.equal([5 * Math.pow(10,8), 5 * Math.pow(10,8), 0]);
+  });
+
+  it("arbiter confirms the dead heat", async () => {
+    await program.methods
+      .correctWeightedResolution(new anchor.BN(1), [half, half, new anchor.BN(0)])
+      .signers([authorizedKeypair])
+      .accounts({
+        config: configPDA,
+        signer: authorizedKeypair.publicKey,
+        market: marketPDA,
+        authorizedUser: authorizedUserPda,
+        feeLedger: feeLedgerPDA,
+      })
+      .rpc();
+
+    const market = await program.account.market.fetch(marketPDA);
+
+    expect(market.subMarkets[0].resolution).to.deep.equal({ finalized: {} });
+  });
+
+  it("pays both dead heat choices the same", async () => {
+    const before = await program.account.user.fetch(userPDA);
+
+    await claimWinnings(1);
+    const afterFirst = await program.account.user.fetch(userPDA);
+
+    await claimWinnings(2);
+    const afterSecond = await program.account.user.fetch(userPDA);
+
+    const first = afterFirst.balance.toNumber() - before.balance.toNumber();
+    const second = afterSecond.balance.toNumber() - afterFirst.balance.toNumber();
+
+    expect(first).to.be.greaterThan(0);
+    expect(first).to.equal(second);
+  });
+
+  it("fails to claim a choice with no payout", async () => {
+    try {
+      await claimWinnings(3);
+      expect.fail("claim should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal("This is not a winning choice.");
+    }
+  });
+});
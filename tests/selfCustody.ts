@@ -0,0 +1,151 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { before } from "mocha";
+import {
+  createAssociatedTokenAccount,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { getUserKeypair, getWalletManagerKeypair } from "./utils/wallets";
+import { getProgram } from "./utils/program";
+import { getConfigPDA, getFeeLedgerPDA, getUserPDA, getVaultPDA } from "./utils/pdas";
+import { initConfigIfNeeded, initFeeLedgerIfNeeded, initVaultIfNeeded } from "./utils/config";
+
+describe("self-custodial wallet instructions", () => {
+  const MINT = new PublicKey("5DUWZLh3zPKAAJKu7ftMJJrkBrKnq3zHPPmguzVkhSes");
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = getProgram();
+  const configPDA = getConfigPDA(program);
+  const feeLedgerPDA = getFeeLedgerPDA(program);
+  const vaultPDA = getVaultPDA(MINT, program);
+
+  let userKeypair = getUserKeypair();
+  let walletManagerKeypair = getWalletManagerKeypair();
+
+  const userWalletPDA = getUserPDA(userKeypair.publicKey, program);
+  const selfCustodialWalletPDA = getUserPDA(walletManagerKeypair.publicKey, program);
+
+  const userUsdcAccount = getAssociatedTokenAddressSync(MINT, userKeypair.publicKey);
+
+  before(async () => {
+    await initConfigIfNeeded(program, MINT);
+    await initVaultIfNeeded(program, MINT);
+    await initFeeLedgerIfNeeded(program);
+
+    await createAssociatedTokenAccount(
+      provider.connection,
+      walletManagerKeypair,
+      MINT,
+      userKeypair.publicKey
+    ).catch((_) => {});
+
+    await program.methods
+      .userInitWallet()
+      .signers([userKeypair])
+      .accounts({ config: configPDA, user: userWalletPDA, signer: userKeypair.publicKey })
+      .rpc()
+      .catch((_) => {});
+
+    await program.methods
+      .userInitWallet()
+      .signers([walletManagerKeypair])
+      .accounts({ config: configPDA, user: selfCustodialWalletPDA, signer: walletManagerKeypair.publicKey })
+      .rpc()
+      .catch((_) => {});
+  });
+
+  it("stores the owner", async () => {
+    const user = await program.account.user.fetch(userWalletPDA);
+
+    expect(user.owner.toBase58()).to.equal(userKeypair.publicKey.toBase58());
+  });
+
+  it("deposits into the vault", async () => {
+    const before = await program.account.user.fetch(userWalletPDA);
+
+    await program.methods
+      .deposit(new anchor.BN(1 * Math.pow(10, 9)))
+      .signers([userKeypair])
+      .accounts({
+        signer: userKeypair.publicKey,
+        config: configPDA,
+        user: userWalletPDA,
+        fromUsdcAccount: userUsdcAccount,
+        vault: vaultPDA,
+        mint: MINT,
+      })
+      .rpc();
+
+    const user = await program.account.user.fetch(userWalletPDA);
+
+    expect(user.balance.sub(before.balance).toString()).to.equal((1 * Math.pow(10, 9)).toString());
+  });
+
+  it("withdraws from the vault", async () => {
+    const before = await program.account.user.fetch(userWalletPDA);
+
+    await program.methods
+      .userWithdraw(new anchor.BN(1 * Math.pow(10, 9)))
+      .signers([userKeypair])
+      .accounts({
+        signer: userKeypair.publicKey,
+        config: configPDA,
+        user: userWalletPDA,
+        vault: vaultPDA,
+        toUsdcAccount: userUsdcAccount,
+        feeLedger: feeLedgerPDA,
+        mint: MINT,
+      })
+      .rpc();
+
+    const user = await program.account.user.fetch(userWalletPDA);
+
+    expect(before.balance.sub(user.balance).toString()).to.equal((1 * Math.pow(10, 9)).toString());
+  });
+
+  it("someone else can't withdraw", async () => {
+    try {
+      await program.methods
+        .userWithdraw(new anchor.BN(1 * Math.pow(10, 9)))
+        .signers([walletManagerKeypair])
+        .accounts({
+          signer: walletManagerKeypair.publicKey,
+          config: configPDA,
+          user: userWalletPDA,
+          vault: vaultPDA,
+          toUsdcAccount: userUsdcAccount,
+          feeLedger: feeLedgerPDA,
+          mint: MINT,
+        })
+        .rpc();
+      expect.fail("withdraw should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorCode.code).to.equal("ConstraintSeeds");
+    }
+  });
+
+  it("the wallet manager can't use the custodial instructions on a self-custodial wallet", async () => {
+    try {
+      await program.methods
+        .withdrawFromBalance(new anchor.BN(1 * Math.pow(10, 9)))
+        .signers([walletManagerKeypair])
+        .accounts({
+          config: configPDA,
+          user: selfCustodialWalletPDA,
+          signer: walletManagerKeypair.publicKey,
+          mint: MINT,
+          vault: vaultPDA,
+          toUsdcAccount: getAssociatedTokenAddressSync(MINT, walletManagerKeypair.publicKey),
+          feeLedger: feeLedgerPDA,
+        })
+        .rpc();
+      expect.fail("withdraw should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal("This wallet is self-custodial, only its owner can sign for it.");
+    }
+  });
+});
@@ -0,0 +1,125 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { before } from "mocha";
+import { getUserKeypair } from "./utils/wallets";
+import { getProgram } from "./utils/program";
+import { getConfigPDA, getFeeLedgerPDA, getVaultPDA } from "./utils/pdas";
+import { initConfigIfNeeded, initFeeLedgerIfNeeded, initVaultIfNeeded } from "./utils/config";
+
+describe("solvency audit", () => {
+  const MINT = new PublicKey("5DUWZLh3zPKAAJKu7ftMJJrkBrKnq3zHPPmguzVkhSes");
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = getProgram();
+  const configPDA = getConfigPDA(program);
+  const vaultPDA = getVaultPDA(MINT, program);
+  const feeLedgerPDA = getFeeLedgerPDA(program);
+
+  // anyone can run an audit
+  let auditor = getUserKeypair();
+  const auditId = new anchor.BN(Date.now());
+
+  const [auditPDA, _] = PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("solvency_audits"),
+      auditor.publicKey.toBuffer(),
+      auditId.toArrayLike(Buffer, "le", 8),
+    ],
+    program.programId
+  );
+
+  const byKey = (a: { publicKey: PublicKey }, b: { publicKey: PublicKey }) =>
+    Buffer.compare(a.publicKey.toBuffer(), b.publicKey.toBuffer());
+
+  before(async () => {
+    await initConfigIfNeeded(program, MINT);
+    await initVaultIfNeeded(program, MINT);
+    await initFeeLedgerIfNeeded(program);
+  });
+
+  it("starts an audit", async () => {
+    await program.methods
+      .startSolvencyAudit(auditId)
+      .signers([auditor])
+      .accounts({ signer: auditor.publicKey, config: configPDA, audit: auditPDA })
+      .rpc();
+
+    const audit = await program.account.solvencyAudit.fetch(auditPDA);
+    const config = await program.account.programConfig.fetch(configPDA);
+
+    expect(audit.userCount.toString()).to.equal(config.userCount.toString());
+    expect(audit.marketCount.toString()).to.equal(config.marketCount.toString());
+    expect(audit.orderBookCount.toString()).to.equal(config.orderBookCount.toString());
+    expect(audit.proposalCount.toString()).to.equal(config.proposalCount.toString());
+    expect(audit.finalized).to.equal(false);
+  });
+
+  it("rejects accounts out of order", async () => {
+    const users = (await program.account.user.all()).sort(byKey);
+    if (users.length < 2) return;
+
+    try {
+      await program.methods
+        .auditSolvency()
+        .signers([auditor])
+        .accounts({ signer: auditor.publicKey, config: configPDA, audit: auditPDA, vault: vaultPDA, mint: MINT, feeLedger: feeLedgerPDA })
+        .remainingAccounts(
+          [users[1], users[0]].map((user) => ({ pubkey: user.publicKey, isSigner: false, isWritable: false }))
+        )
+        .rpc();
+      expect.fail("audit should have failed");
+    } catch (err) {
+      const error = err as anchor.AnchorError;
+      expect(error.error.errorMessage).to.equal("Account is not part of this audit or is out of order.");
+    }
+  });
+
+  it("walks every user, market, order book and proposal and records the result", async () => {
+    const users = (await program.account.user.all()).sort(byKey);
+    const markets = (await program.account.market.all()).sort(byKey);
+    const orderBooks = (await program.account.orderBook.all()).sort(byKey);
+    const eventQueues = (await program.account.eventQueue.all()).sort(byKey);
+    const proposals = (await program.account.proposal.all()).sort(byKey);
+
+    const accounts = [...users, ...markets, ...orderBooks, ...eventQueues, ...proposals].map((account) => ({
+      pubkey: account.publicKey,
+      isSigner: false,
+      isWritable: false,
+    }));
+
+    for (let i = 0; i < accounts.length; i += 5) {
+      await program.methods
+        .auditSolvency()
+        .signers([auditor])
+        .accounts({ signer: auditor.publicKey, config: configPDA, audit: auditPDA, vault: vaultPDA, mint: MINT, feeLedger: feeLedgerPDA })
+        .remainingAccounts(accounts.slice(i, i + 5))
+        .rpc();
+    }
+
+    const audit = await program.account.solvencyAudit.fetch(auditPDA);
+
+    const feeLedger = await program.account.feeLedger.fetch(feeLedgerPDA);
+
+    const bonds = proposals
+      .filter((proposal) => !proposal.account.settled)
+      .reduce(
+        (sum, proposal) => sum.add(proposal.account.disputer ? proposal.account.bond.muln(2) : proposal.account.bond),
+        new anchor.BN(0)
+      );
+
+    expect(audit.finalized).to.equal(true);
+    expect(audit.feeLiabilities.toString()).to.equal(feeLedger.accrued.toString());
+    expect(audit.bondLiabilities.toString()).to.equal(bonds.toString());
+    expect(audit.solvent).to.equal(
+      audit.vaultBalance.gte(
+        audit.userLiabilities
+          .add(audit.marketLiabilities)
+          .add(audit.escrowLiabilities)
+          .add(audit.bondLiabilities)
+          .add(audit.feeLiabilities)
+      )
+    );
+  });
+});
@@ -0,0 +1,98 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { TallyClob } from "../../target/types/tally_clob";
+import { getConfigPDA, getFeeLedgerPDA, getVaultPDA } from "./pdas";
+import {
+  getClobManagerKeypair,
+  getFeeManagerKeypair,
+  getOwnerKeypair,
+  getWalletManagerKeypair,
+} from "./wallets";
+
+const BPF_LOADER_UPGRADEABLE = new PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111"
+);
+
+export function getProgramDataAddress(program: anchor.Program<TallyClob>): PublicKey {
+  const [programData, _] = PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    BPF_LOADER_UPGRADEABLE
+  );
+
+  return programData;
+}
+
+export async function initConfigIfNeeded(
+  program: anchor.Program<TallyClob>,
+  mint: PublicKey
+) {
+  const configPDA = getConfigPDA(program);
+  const existing = await program.account.programConfig
+    .fetchNullable(configPDA);
+
+  if (existing) return;
+
+  const owner = getOwnerKeypair();
+
+  await program.methods
+    .initConfig({
+      owner: owner.publicKey,
+      walletManager: getWalletManagerKeypair().publicKey,
+      clobManager: getClobManagerKeypair().publicKey,
+      feeOwner: getFeeManagerKeypair().publicKey,
+    })
+    .signers([owner])
+    .accounts({
+      signer: owner.publicKey,
+      config: configPDA,
+      mint,
+      program: program.programId,
+      programData: getProgramDataAddress(program),
+    })
+    .rpc();
+}
+
+
+export async function initVaultIfNeeded(
+  program: anchor.Program<TallyClob>,
+  mint: PublicKey
+) {
+  const vaultPDA = getVaultPDA(mint, program);
+  const existing = await program.provider.connection.getAccountInfo(vaultPDA);
+
+  if (existing) return;
+
+  const owner = getOwnerKeypair();
+
+  await program.methods
+    .initVault()
+    .signers([owner])
+    .accounts({
+      signer: owner.publicKey,
+      config: getConfigPDA(program),
+      vault: vaultPDA,
+      mint,
+    })
+    .rpc();
+}
+
+export async function initFeeLedgerIfNeeded(
+  program: anchor.Program<TallyClob>
+) {
+  const feeLedgerPDA = getFeeLedgerPDA(program);
+  const existing = await program.account.feeLedger.fetchNullable(feeLedgerPDA);
+
+  if (existing) return;
+
+  const owner = getOwnerKeypair();
+
+  await program.methods
+    .initFeeLedger()
+    .signers([owner])
+    .accounts({
+      signer: owner.publicKey,
+      config: getConfigPDA(program),
+      feeLedger: feeLedgerPDA,
+    })
+    .rpc();
+}
\ No newline at end of file
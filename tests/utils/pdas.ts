@@ -53,4 +53,118 @@ export function getMarketPortfolioPDA(
       );
 
       return marketPortfolioPDA;
-}
\ No newline at end of file
+}
+
+export function getConfigPDA(program: anchor.Program<TallyClob>): PublicKey {
+    const [configPDA, _] = PublicKey.findProgramAddressSync(
+        [
+            anchor.utils.bytes.utf8.encode("program_config"),
+        ],
+        program.programId
+      );
+
+      return configPDA;
+}
+
+
+export function getVaultPDA(mint: PublicKey, program: anchor.Program<TallyClob>): PublicKey {
+    const [vaultPDA, _] = PublicKey.findProgramAddressSync(
+        [
+          anchor.utils.bytes.utf8.encode("vault"),
+          mint.toBuffer(),
+        ],
+        program.programId
+      );
+
+      return vaultPDA;
+}
+
+export function getFeeLedgerPDA(program: anchor.Program<TallyClob>): PublicKey {
+    const [feeLedgerPDA, _] = PublicKey.findProgramAddressSync(
+        [
+          anchor.utils.bytes.utf8.encode("fee_ledger"),
+        ],
+        program.programId
+      );
+
+      return feeLedgerPDA;
+}
+
+export function getOrderBookPDA(
+    marketPDA: PublicKey,
+    subMarketId: number,
+    choiceId: number,
+    program: anchor.Program<TallyClob>
+): PublicKey {
+    const [orderBookPDA, _] = PublicKey.findProgramAddressSync(
+        [
+          anchor.utils.bytes.utf8.encode("order_books"),
+          marketPDA.toBuffer(),
+          new anchor.BN(subMarketId).toArrayLike(Buffer, "le", 8),
+          new anchor.BN(choiceId).toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+
+      return orderBookPDA;
+}
+
+export function getEventQueuePDA(orderBookPDA: PublicKey, program: anchor.Program<TallyClob>): PublicKey {
+    const [eventQueuePDA, _] = PublicKey.findProgramAddressSync(
+        [
+          anchor.utils.bytes.utf8.encode("event_queues"),
+          orderBookPDA.toBuffer(),
+        ],
+        program.programId
+      );
+
+      return eventQueuePDA;
+}
+
+export function getShareMintPDA(
+    marketPDA: PublicKey,
+    subMarketId: number,
+    choiceId: number,
+    program: anchor.Program<TallyClob>
+): PublicKey {
+    const [shareMintPDA, _] = PublicKey.findProgramAddressSync(
+        [
+          anchor.utils.bytes.utf8.encode("share_mints"),
+          marketPDA.toBuffer(),
+          new anchor.BN(subMarketId).toArrayLike(Buffer, "le", 8),
+          new anchor.BN(choiceId).toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+
+      return shareMintPDA;
+}
+
+export function getMockFeedPDA(feedKey: PublicKey, program: anchor.Program<TallyClob>): PublicKey {
+    const [mockFeedPDA, _] = PublicKey.findProgramAddressSync(
+        [
+          anchor.utils.bytes.utf8.encode("mock_feeds"),
+          feedKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      return mockFeedPDA;
+}
+
+export function getProposalPDA(
+    marketPDA: PublicKey,
+    subMarketId: number,
+    program: anchor.Program<TallyClob>
+): PublicKey {
+    const [proposalPDA, _] = PublicKey.findProgramAddressSync(
+        [
+          anchor.utils.bytes.utf8.encode("proposals"),
+          marketPDA.toBuffer(),
+          new anchor.BN(subMarketId).toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+
+      return proposalPDA;
+}
@@ -0,0 +1,16 @@
+export const ROLE_MARKET_CREATOR = 1 << 0;
+export const ROLE_RESOLVER = 1 << 1;
+export const ROLE_SCHEDULER = 1 << 2;
+export const ROLE_FEE_ADMIN = 1 << 3;
+export const ROLE_WALLET_MANAGER = 1 << 4;
+export const ROLE_PAUSER = 1 << 5;
+export const ROLE_ARBITER = 1 << 6;
+
+export const ALL_ROLES =
+  ROLE_MARKET_CREATOR |
+  ROLE_RESOLVER |
+  ROLE_SCHEDULER |
+  ROLE_FEE_ADMIN |
+  ROLE_WALLET_MANAGER |
+  ROLE_PAUSER |
+  ROLE_ARBITER;
@@ -2,13 +2,15 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { PublicKey } from "@solana/web3.js";
 import { expect } from "chai";
-import { getFeeManagerKeypair, getUserKeypair, getWalletManagerKeypair } from "./utils/wallets";
+import { getUserKeypair, getWalletManagerKeypair } from "./utils/wallets";
 import { before, beforeEach } from "mocha";
 import {
   createAssociatedTokenAccount,
+  getAccount,
   getAssociatedTokenAddressSync,
 } from "@solana/spl-token";
 import { getProgram } from "./utils/program";
+import { getConfigPDA, getFeeLedgerPDA, getVaultPDA } from "./utils/pdas";
 
 describe("wallet instructions", () => {
   const MINT = new PublicKey("5DUWZLh3zPKAAJKu7ftMJJrkBrKnq3zHPPmguzVkhSes");
@@ -16,10 +18,12 @@ describe("wallet instructions", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
   const program = getProgram();
+  const configPDA = getConfigPDA(program);
+  const feeLedgerPDA = getFeeLedgerPDA(program);
+  const vaultPDA = getVaultPDA(MINT, program);
 
   let userKeypair = getUserKeypair();
   let walletManagerKeypair = getWalletManagerKeypair();
-  let feeManagerKeypair = getFeeManagerKeypair()
 
   const [userWalletPDA, _] = PublicKey.findProgramAddressSync(
     [anchor.utils.bytes.utf8.encode("users"), userKeypair.publicKey.toBuffer()],
@@ -30,10 +34,6 @@ describe("wallet instructions", () => {
     MINT,
     walletManagerKeypair.publicKey
   );
-  const feeAccount = getAssociatedTokenAddressSync(
-    MINT,
-    feeManagerKeypair.publicKey
-  );
 
   let to: PublicKey;
 
@@ -50,7 +50,7 @@ describe("wallet instructions", () => {
     await program.methods
       .initWallet(userKeypair.publicKey)
       .signers([walletManagerKeypair])
-      .accounts({ user: userWalletPDA, signer: walletManagerKeypair.publicKey })
+      .accounts({ config: configPDA, user: userWalletPDA, signer: walletManagerKeypair.publicKey })
       .rpc()
       .catch((_) => {});
 
@@ -61,12 +61,13 @@ describe("wallet instructions", () => {
       .withdrawFromBalance(balance)
       .signers([walletManagerKeypair])
       .accounts({
+        config: configPDA,
         user: userWalletPDA,
         signer: walletManagerKeypair.publicKey,
         mint: MINT,
-        fromUsdcAccount: from,
+        vault: vaultPDA,
         toUsdcAccount: to,
-        feeUsdcAccount: feeAccount
+        feeLedger: feeLedgerPDA
       })
       .rpc()
       .catch((_) => {});
@@ -77,15 +78,23 @@ describe("wallet instructions", () => {
   it("creates a wallet", async () => {
     const user = await program.account.user.fetch(userWalletPDA);
 
-    expect(Number(BigInt(user.balance.toNumber()))).to.equal(0);
+    // anything below a token unit can't be withdrawn and stays in the balance
+    expect(user.balance.toNumber()).to.be.lessThan(Math.pow(10,9));
   });
 
-  it("adds to balance", async () => {
+  it("deposits into the vault", async () => {
     // Add your test here.
     await program.methods
-      .addToBalance(new anchor.BN(10 * Math.pow(10,9)))
+      .deposit(new anchor.BN(10 * Math.pow(10,9)))
       .signers([walletManagerKeypair])
-      .accounts({ user: userWalletPDA, signer: walletManagerKeypair.publicKey })
+      .accounts({
+        config: configPDA,
+        user: userWalletPDA,
+        signer: walletManagerKeypair.publicKey,
+        fromUsdcAccount: from,
+        vault: vaultPDA,
+        mint: MINT
+      })
       .rpc();
 
     const user = await program.account.user.fetch(userWalletPDA);
@@ -93,13 +102,20 @@ describe("wallet instructions", () => {
     expect(Number(BigInt(user.balance.toNumber()) / BigInt(Math.pow(10,9)))).to.equal(10);
   });
 
-  it("unauthorized add", async () => {
+  it("deposit from someone else's account fails", async () => {
     // Add your test here.
     try {
       await program.methods
-        .addToBalance(new anchor.BN(10 * Math.pow(10,9)))
+        .deposit(new anchor.BN(10 * Math.pow(10,9)))
         .signers([userKeypair])
-        .accounts({ user: userWalletPDA, signer: userKeypair.publicKey })
+        .accounts({
+          config: configPDA,
+          user: userWalletPDA,
+          signer: userKeypair.publicKey,
+          fromUsdcAccount: from,
+          vault: vaultPDA,
+          mint: MINT
+        })
         .rpc();
     } catch (err) {
       const error = err as anchor.AnchorError;
@@ -109,18 +125,43 @@ describe("wallet instructions", () => {
     }
   });
 
+  it("backs unredeemable balance with tokens in the vault", async () => {
+    const vaultBefore = await getAccount(provider.connection, vaultPDA);
+    const before = await program.account.user.fetch(userWalletPDA);
+
+    await program.methods
+      .addToUnreedeemable(new anchor.BN(1 * Math.pow(10,9)))
+      .signers([walletManagerKeypair])
+      .accounts({
+        config: configPDA,
+        user: userWalletPDA,
+        signer: walletManagerKeypair.publicKey,
+        fromUsdcAccount: from,
+        vault: vaultPDA,
+        mint: MINT
+      })
+      .rpc();
+
+    const vault = await getAccount(provider.connection, vaultPDA);
+    const user = await program.account.user.fetch(userWalletPDA);
+
+    expect(vault.amount > vaultBefore.amount).to.equal(true);
+    expect(user.unreedemableBalance.sub(before.unreedemableBalance).toNumber()).to.equal(Math.pow(10,9));
+  });
+
   it("withdraws from balance", async () => {
     // Add your test here.
     await program.methods
       .withdrawFromBalance(new anchor.BN(5 * Math.pow(10,9)))
       .signers([walletManagerKeypair])
       .accounts({
+        config: configPDA,
         user: userWalletPDA,
         signer: walletManagerKeypair.publicKey,
         mint: MINT,
-        fromUsdcAccount: from,
+        vault: vaultPDA,
         toUsdcAccount: to,
-        feeUsdcAccount: feeAccount
+        feeLedger: feeLedgerPDA
       })
       .rpc();
 
@@ -135,12 +176,13 @@ describe("wallet instructions", () => {
         .withdrawFromBalance(new anchor.BN(10 * Math.pow(10,9)))
         .signers([walletManagerKeypair])
         .accounts({
+          config: configPDA,
           user: userWalletPDA,
           signer: walletManagerKeypair.publicKey,
           mint: MINT,
-          fromUsdcAccount: from,
+          vault: vaultPDA,
           toUsdcAccount: to,
-          feeUsdcAccount: feeAccount
+          feeLedger: feeLedgerPDA
         })
         .rpc();
     } catch (err) {
@@ -157,12 +199,13 @@ describe("wallet instructions", () => {
         .withdrawFromBalance(new anchor.BN(10 * Math.pow(10,9)))
         .signers([userKeypair])
         .accounts({
+          config: configPDA,
           user: userWalletPDA,
           signer: userKeypair.publicKey,
           mint: MINT,
-          fromUsdcAccount: from,
+          vault: vaultPDA,
           toUsdcAccount: to,
-          feeUsdcAccount: feeAccount
+          feeLedger: feeLedgerPDA
         })
         .rpc();
     } catch (err) {